@@ -1,33 +1,39 @@
-use pb_rs::{types::FileDescriptor, ConfigBuilder};
-use std::path::{Path, PathBuf};
-use walkdir::WalkDir;
+// The bindings are checked in under src/opentelemetry_types. With the
+// `regenerate` feature they are generated from the protos instead, either the
+// bundled ones or those in `OPENTELEMETRY_PROTO_DIR`.
+
+#[cfg(feature = "regenerate")]
+#[path = "build/codegen.rs"]
+mod codegen;
 
 fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+
+    #[cfg(feature = "regenerate")]
+    regenerate();
+}
+
+#[cfg(feature = "regenerate")]
+fn regenerate() {
+    use std::path::{Path, PathBuf};
+
+    println!("cargo:rerun-if-changed=build/codegen.rs");
+    println!("cargo:rerun-if-env-changed=OPENTELEMETRY_PROTO_DIR");
+
     let out_dir = std::env::var("OUT_DIR").unwrap();
     let out_dir = Path::new(&out_dir).join("proto");
 
-    let in_dir = PathBuf::from(::std::env::var("CARGO_MANIFEST_DIR").unwrap()).join("proto");
+    let in_dir = match std::env::var_os("OPENTELEMETRY_PROTO_DIR") {
+        Some(dir) => PathBuf::from(dir),
+        None => PathBuf::from(std::env::var("CARGO_MANIFEST_DIR").unwrap()).join("proto"),
+    };
 
     // Re-run this build.rs if the protos dir changes (i.e. a new file is added)
     println!("cargo:rerun-if-changed={}", in_dir.to_str().unwrap());
-
-    // Find all *.proto files in the `in_dir` and add them to the list of files
-    let mut protos = Vec::new();
-    let proto_ext = Some(Path::new("proto").as_os_str());
-    for entry in WalkDir::new(&in_dir) {
-        let path = entry.unwrap().into_path();
-        if path.extension() == proto_ext {
-            // Re-run this build.rs if any of the files in the protos dir change
-            println!("cargo:rerun-if-changed={}", path.to_str().unwrap());
-            protos.push(path);
-        }
+    // Re-run this build.rs if any of the files in the protos dir change
+    for path in codegen::protos(&in_dir) {
+        println!("cargo:rerun-if-changed={}", path.to_str().unwrap());
     }
 
-    // Delete all old generated files before re-generating new ones
-    if out_dir.exists() {
-        std::fs::remove_dir_all(&out_dir).unwrap();
-    }
-    std::fs::DirBuilder::new().create(&out_dir).unwrap();
-    let config_builder = ConfigBuilder::new(&protos, None, Some(&out_dir), &[in_dir]).unwrap();
-    FileDescriptor::run(&config_builder.build()).unwrap()
+    codegen::generate(&in_dir, &out_dir);
 }
@@ -0,0 +1,239 @@
+// Generates the quick-protobuf bindings for the protos in `in_dir`.
+//
+// Shared by build.rs (with the `regenerate` feature) and the test that keeps
+// the bindings checked in under src/opentelemetry_types in sync with proto/.
+// The output does not depend on the enabled features: serde derives are
+// emitted behind a `cfg_attr` instead, and every message gets an
+// `unknown_fields` member that only the `unknown-fields` feature fills.
+
+use pb_rs::{types::FileDescriptor, ConfigBuilder};
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+const SERDE_DERIVE: &str =
+    "#[cfg_attr(feature = \"serde\", derive(serde::Serialize, serde::Deserialize))]";
+
+const UNKNOWN_FIELDS: &str = concat!(
+    "    #[cfg_attr(feature = \"serde\", ",
+    "serde(default, skip_serializing_if = \"crate::UnknownFields::is_empty\"))]\n",
+    "    pub unknown_fields: crate::UnknownFields,",
+);
+
+/// Lists every `*.proto` file under `in_dir`, sorted so the output does not
+/// depend on directory iteration order.
+pub fn protos(in_dir: &Path) -> Vec<PathBuf> {
+    let proto_ext = Some(Path::new("proto").as_os_str());
+    let mut protos: Vec<_> = WalkDir::new(in_dir)
+        .into_iter()
+        .map(|entry| entry.unwrap().into_path())
+        .filter(|path| path.extension() == proto_ext)
+        .collect();
+    protos.sort();
+    protos
+}
+
+pub fn generate(in_dir: &Path, out_dir: &Path) {
+    // Delete all old generated files before re-generating new ones
+    if out_dir.exists() {
+        std::fs::remove_dir_all(out_dir).unwrap();
+    }
+    std::fs::create_dir_all(out_dir).unwrap();
+
+    let out_dir = out_dir.to_owned();
+    let config_builder =
+        ConfigBuilder::new(&protos(in_dir), None, Some(&out_dir), &[in_dir.to_owned()]).unwrap();
+    FileDescriptor::run(&config_builder.build()).unwrap();
+
+    let optional = optional_fields(in_dir);
+    let mut rewrites = vec![[0; 4]; optional.len()];
+    let mut unknown = vec![];
+    for path in generated_files(&out_dir) {
+        let code = std::fs::read_to_string(&path).unwrap();
+        let code = if path.file_name() == Some("mod.rs".as_ref()) {
+            sort_modules(&code)
+        } else {
+            post_process(&code, &optional, &mut rewrites, &mut unknown)
+        };
+        std::fs::write(&path, code).unwrap();
+    }
+
+    // A change in the pb-rs output must not leave a field half rewritten
+    for ((message, name), rewrites) in optional.iter().zip(rewrites) {
+        assert_eq!(
+            rewrites,
+            [1; 4],
+            "optional field {message}.{name}: expected the declaration, read, size and write to be \
+             rewritten once each"
+        );
+    }
+    assert!(!unknown.is_empty());
+    for (message, rewrites) in unknown {
+        assert_eq!(
+            rewrites, [1; 4],
+            "message {message}: expected the declaration, read, size and write of its unknown \
+             fields to be added once each"
+        );
+    }
+}
+
+/// Lists the generated `*.rs` files under `dir`, relative paths included.
+pub fn generated_files(dir: &Path) -> Vec<PathBuf> {
+    let mut files: Vec<_> = WalkDir::new(dir)
+        .into_iter()
+        .map(|entry| entry.unwrap().into_path())
+        .filter(|path| path.extension() == Some("rs".as_ref()))
+        .collect();
+    files.sort();
+    files
+}
+
+// pb-rs lists modules in hash order, rustfmt would sort them anyway
+fn sort_modules(code: &str) -> String {
+    let (mut modules, other): (Vec<_>, Vec<_>) =
+        code.lines().partition(|line| line.starts_with("pub mod "));
+    modules.sort();
+
+    let mut out = String::with_capacity(code.len());
+    for line in other.into_iter().chain(modules) {
+        out.push_str(line);
+        out.push('\n');
+    }
+    out
+}
+
+// The message and field names of the proto3 `optional` scalar fields, which
+// pb-rs generates as plain values that cannot tell unset from zero. Only
+// done for profiles: the metrics bindings have long exposed plain values.
+fn optional_fields(in_dir: &Path) -> Vec<(String, String)> {
+    let mut fields = vec![];
+    for proto in protos(in_dir)
+        .into_iter()
+        .filter(|proto| proto.file_name() == Some("profiles.proto".as_ref()))
+    {
+        let mut message = String::new();
+        for line in std::fs::read_to_string(proto).unwrap().lines() {
+            let words: Vec<_> = line.split_whitespace().collect();
+            match words[..] {
+                ["message", name, "{"] => message = name.to_owned(),
+                ["optional", _, name, "=", _] => fields.push((message.clone(), name.to_owned())),
+                _ => {}
+            }
+        }
+    }
+    fields
+}
+
+// Turns `pub name: T` into `pub name: Option<T>`, with `None` for unset.
+// Returns the rewritten line and which of the declaration, read, size and
+// write it was, or `None` if the line does not mention the field.
+fn make_optional(line: &str, name: &str) -> Option<(String, usize)> {
+    let field = format!("self.{name}");
+    if let Some((head, ty)) = line.split_once(&format!("pub {name}: ")) {
+        let line = format!("{head}pub {name}: Option<{}>,", ty.trim_end_matches(','));
+        Some((line, 0))
+    } else if let Some((head, value)) = line.split_once(&format!("msg.{name} = ")) {
+        let line = format!("{head}msg.{name} = Some({}),", value.trim_end_matches(','));
+        Some((line, 1))
+    } else if let Some((head, size)) = line.split_once(&format!("if {field} == ")) {
+        // `+ if self.name == 0 { 0 } else { size }`
+        let size = size.split_once("else { ").unwrap().1.trim_end_matches(" }");
+        let size = size.replace(&format!("&{field}"), "m");
+        Some((format!("{head}{field}.as_ref().map_or(0, |m| {size})"), 2))
+    } else if let Some((head, write)) = line.split_once(&format!("if {field} != ")) {
+        // `if self.name != 0 { write }`
+        let write = write.split_once(" { ").unwrap().1;
+        let write = write.replace(&format!("&{field}"), "m");
+        Some((format!("{head}if let Some(ref m) = {field} {{ {write}"), 3))
+    } else {
+        None
+    }
+}
+
+// Keeps the fields the reader of a message skips in its `unknown_fields`
+// and writes them back after the known ones. Returns the rewritten line and
+// which of the declaration, read, size and write it was, or `None` if the
+// line is none of them.
+fn add_unknown_fields(line: &str, in_struct: bool, in_write: bool) -> Option<(String, usize)> {
+    if in_struct && line == "}" {
+        Some((format!("{UNKNOWN_FIELDS}\n}}"), 0))
+    } else if line.contains("Ok(t) => { r.read_unknown(bytes, t)?; }") {
+        let line = line.replace(
+            "r.read_unknown(bytes, t)",
+            "msg.unknown_fields.read(r, bytes, t)",
+        );
+        Some((line, 1))
+    } else if line == "        0" {
+        Some((format!("{line}\n        + self.unknown_fields.len()"), 2))
+    } else if let Some(head) = line.strip_suffix("Ok(())").filter(|_| in_write) {
+        // After a oneof, the line also closes its `match`
+        let line = format!("{head}self.unknown_fields.write(w)?;\n        Ok(())");
+        Some((line, 3))
+    } else {
+        None
+    }
+}
+
+fn post_process(
+    code: &str,
+    optional: &[(String, String)],
+    rewrites: &mut [[usize; 4]],
+    unknown: &mut Vec<(String, [usize; 4])>,
+) -> String {
+    let mut out = String::with_capacity(code.len());
+    let mut message = "";
+    let mut in_struct = false;
+    let mut in_write = false;
+    for line in code.lines() {
+        if line.starts_with("pub struct ") {
+            in_struct = true;
+        } else if line.contains("fn write_message<") {
+            in_write = true;
+        }
+        // Struct, reader and writer of each message follow each other
+        let name = match line.split_once(" for ") {
+            Some((_, name)) if line.starts_with("impl") => Some(name),
+            _ => line.strip_prefix("pub struct "),
+        };
+        if let Some(name) = name {
+            message = name.split(['<', ' ']).next().unwrap();
+        }
+        let mut line = line.to_owned();
+        for ((m, name), rewrites) in optional.iter().zip(rewrites.iter_mut()) {
+            if m != message {
+                continue;
+            }
+            if let Some((rewritten, pattern)) = make_optional(&line, name) {
+                line = rewritten;
+                rewrites[pattern] += 1;
+            }
+        }
+        if let Some((rewritten, pattern)) = add_unknown_fields(&line, in_struct, in_write) {
+            line = rewritten;
+            match unknown.last_mut() {
+                Some((m, rewrites)) if m == message => rewrites[pattern] += 1,
+                _ => {
+                    let mut rewrites = [0; 4];
+                    rewrites[pattern] += 1;
+                    unknown.push((message.to_owned(), rewrites));
+                }
+            }
+            in_struct = false;
+            if pattern == 3 {
+                in_write = false;
+            }
+        }
+        out.push_str(&line);
+        out.push('\n');
+        // pb-rs only applies custom derives to messages, and unconditionally
+        if line.starts_with("#[derive(") {
+            out.push_str(SERDE_DERIVE);
+            out.push('\n');
+        }
+    }
+
+    // Route packed fixed-size fields through our alignment-safe reader
+    out.replace(
+        "r.read_packed_fixed(bytes)?.into()",
+        "crate::opentelemetry_types::read_packed_fixed(r, bytes)?",
+    )
+}
@@ -0,0 +1,7 @@
+#!/usr/bin/env bash
+# Regenerates the bindings checked in under src/opentelemetry_types from the
+# protos in proto/
+set -euo pipefail
+
+cd "$(dirname "$0")/.."
+UPDATE_BINDINGS=1 cargo test --test generated_bindings
@@ -0,0 +1,400 @@
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+
+use crate::opentelemetry::common::{
+    AnyValue, AnyValueOneOfvalue, ArrayValue, KeyValue, KeyValueList,
+};
+
+impl<'a> From<AnyValueOneOfvalue<'a>> for AnyValue<'a> {
+    fn from(value: AnyValueOneOfvalue<'a>) -> Self {
+        AnyValue {
+            value,
+            ..Default::default()
+        }
+    }
+}
+
+impl<'a> From<&'a str> for AnyValue<'a> {
+    fn from(value: &'a str) -> Self {
+        AnyValueOneOfvalue::string_value(Cow::Borrowed(value)).into()
+    }
+}
+
+impl From<String> for AnyValue<'_> {
+    fn from(value: String) -> Self {
+        AnyValueOneOfvalue::string_value(Cow::Owned(value)).into()
+    }
+}
+
+impl<'a> From<Cow<'a, str>> for AnyValue<'a> {
+    fn from(value: Cow<'a, str>) -> Self {
+        AnyValueOneOfvalue::string_value(value).into()
+    }
+}
+
+impl From<i64> for AnyValue<'_> {
+    fn from(value: i64) -> Self {
+        AnyValueOneOfvalue::int_value(value).into()
+    }
+}
+
+impl From<f64> for AnyValue<'_> {
+    fn from(value: f64) -> Self {
+        AnyValueOneOfvalue::double_value(value).into()
+    }
+}
+
+impl From<bool> for AnyValue<'_> {
+    fn from(value: bool) -> Self {
+        AnyValueOneOfvalue::bool_value(value).into()
+    }
+}
+
+impl From<Vec<u8>> for AnyValue<'_> {
+    fn from(value: Vec<u8>) -> Self {
+        AnyValueOneOfvalue::bytes_value(Cow::Owned(value)).into()
+    }
+}
+
+impl<'a> From<&'a [u8]> for AnyValue<'a> {
+    fn from(value: &'a [u8]) -> Self {
+        AnyValueOneOfvalue::bytes_value(Cow::Borrowed(value)).into()
+    }
+}
+
+/// An array value; `Vec<u8>` is a bytes value instead.
+impl<'a, T: Into<AnyValue<'a>>> From<Vec<T>> for AnyValue<'a> {
+    fn from(values: Vec<T>) -> Self {
+        AnyValueOneOfvalue::array_value(ArrayValue {
+            values: values.into_iter().map(Into::into).collect(),
+            ..Default::default()
+        })
+        .into()
+    }
+}
+
+impl<'a, K, V> From<(K, V)> for KeyValue<'a>
+where
+    K: Into<Cow<'a, str>>,
+    V: Into<AnyValue<'a>>,
+{
+    fn from((key, value): (K, V)) -> Self {
+        KeyValue {
+            key: key.into(),
+            value: Some(value.into()),
+            ..Default::default()
+        }
+    }
+}
+
+fn kvlist<'a, K, V>(entries: impl IntoIterator<Item = (K, V)>) -> AnyValue<'a>
+where
+    K: Into<Cow<'a, str>>,
+    V: Into<AnyValue<'a>>,
+{
+    AnyValueOneOfvalue::kvlist_value(KeyValueList {
+        values: entries.into_iter().map(KeyValue::from).collect(),
+        ..Default::default()
+    })
+    .into()
+}
+
+/// A key-value list value, in key order.
+impl<'a, K, V> From<BTreeMap<K, V>> for AnyValue<'a>
+where
+    K: Into<Cow<'a, str>>,
+    V: Into<AnyValue<'a>>,
+{
+    fn from(map: BTreeMap<K, V>) -> Self {
+        kvlist(map)
+    }
+}
+
+/// A key-value list value, in the map's iteration order.
+impl<'a, K, V, S> From<HashMap<K, V, S>> for AnyValue<'a>
+where
+    K: Into<Cow<'a, str>>,
+    V: Into<AnyValue<'a>>,
+{
+    fn from(map: HashMap<K, V, S>) -> Self {
+        kvlist(map)
+    }
+}
+
+impl AnyValueOneOfvalue<'_> {
+    fn type_name(&self) -> &'static str {
+        match self {
+            AnyValueOneOfvalue::string_value(_) => "string",
+            AnyValueOneOfvalue::bool_value(_) => "bool",
+            AnyValueOneOfvalue::int_value(_) => "int",
+            AnyValueOneOfvalue::double_value(_) => "double",
+            AnyValueOneOfvalue::array_value(_) => "array",
+            AnyValueOneOfvalue::kvlist_value(_) => "kvlist",
+            AnyValueOneOfvalue::bytes_value(_) => "bytes",
+            AnyValueOneOfvalue::None => "empty",
+        }
+    }
+}
+
+fn unexpected(expected: &'static str, value: &AnyValue) -> crate::Error {
+    crate::Error::UnexpectedAnyValue {
+        expected,
+        found: value.value.type_name(),
+    }
+}
+
+impl<'r, 'a: 'r> TryFrom<&'r AnyValue<'a>> for &'r str {
+    type Error = crate::Error;
+
+    fn try_from(value: &'r AnyValue<'a>) -> Result<Self, Self::Error> {
+        match &value.value {
+            AnyValueOneOfvalue::string_value(s) => Ok(s),
+            _ => Err(unexpected("string", value)),
+        }
+    }
+}
+
+impl TryFrom<&AnyValue<'_>> for String {
+    type Error = crate::Error;
+
+    fn try_from(value: &AnyValue<'_>) -> Result<Self, Self::Error> {
+        <&str>::try_from(value).map(str::to_string)
+    }
+}
+
+impl TryFrom<&AnyValue<'_>> for i64 {
+    type Error = crate::Error;
+
+    fn try_from(value: &AnyValue<'_>) -> Result<Self, Self::Error> {
+        match value.value {
+            AnyValueOneOfvalue::int_value(n) => Ok(n),
+            _ => Err(unexpected("int", value)),
+        }
+    }
+}
+
+impl TryFrom<&AnyValue<'_>> for f64 {
+    type Error = crate::Error;
+
+    fn try_from(value: &AnyValue<'_>) -> Result<Self, Self::Error> {
+        match value.value {
+            AnyValueOneOfvalue::double_value(n) => Ok(n),
+            _ => Err(unexpected("double", value)),
+        }
+    }
+}
+
+impl TryFrom<&AnyValue<'_>> for bool {
+    type Error = crate::Error;
+
+    fn try_from(value: &AnyValue<'_>) -> Result<Self, Self::Error> {
+        match value.value {
+            AnyValueOneOfvalue::bool_value(b) => Ok(b),
+            _ => Err(unexpected("bool", value)),
+        }
+    }
+}
+
+impl<'r, 'a: 'r> TryFrom<&'r AnyValue<'a>> for &'r [u8] {
+    type Error = crate::Error;
+
+    fn try_from(value: &'r AnyValue<'a>) -> Result<Self, Self::Error> {
+        match &value.value {
+            AnyValueOneOfvalue::bytes_value(bytes) => Ok(bytes),
+            _ => Err(unexpected("bytes", value)),
+        }
+    }
+}
+
+impl TryFrom<&AnyValue<'_>> for Vec<u8> {
+    type Error = crate::Error;
+
+    fn try_from(value: &AnyValue<'_>) -> Result<Self, Self::Error> {
+        <&[u8]>::try_from(value).map(<[u8]>::to_vec)
+    }
+}
+
+/// Strings are written as they are, bytes as hex, and arrays and key-value
+/// lists as `[1, "a"]` and `{key: "value"}`, with nested strings quoted.
+impl fmt::Display for AnyValue<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.value {
+            AnyValueOneOfvalue::string_value(s) => f.write_str(s),
+            value => write_nested(value, f),
+        }
+    }
+}
+
+fn write_nested(value: &AnyValueOneOfvalue, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match value {
+        AnyValueOneOfvalue::string_value(s) => write!(f, "{s:?}"),
+        AnyValueOneOfvalue::bool_value(b) => write!(f, "{b}"),
+        AnyValueOneOfvalue::int_value(n) => write!(f, "{n}"),
+        AnyValueOneOfvalue::double_value(n) => write!(f, "{n}"),
+        AnyValueOneOfvalue::bytes_value(bytes) => {
+            bytes.iter().try_for_each(|byte| write!(f, "{byte:02x}"))
+        }
+        AnyValueOneOfvalue::array_value(array) => {
+            f.write_str("[")?;
+            for (i, value) in array.values.iter().enumerate() {
+                if i > 0 {
+                    f.write_str(", ")?;
+                }
+                write_nested(&value.value, f)?;
+            }
+            f.write_str("]")
+        }
+        AnyValueOneOfvalue::kvlist_value(kvlist) => {
+            f.write_str("{")?;
+            for (i, kv) in kvlist.values.iter().enumerate() {
+                if i > 0 {
+                    f.write_str(", ")?;
+                }
+                write!(f, "{}: ", kv.key)?;
+                match &kv.value {
+                    Some(value) => write_nested(&value.value, f)?,
+                    None => f.write_str("null")?,
+                }
+            }
+            f.write_str("}")
+        }
+        AnyValueOneOfvalue::None => f.write_str("null"),
+    }
+}
+
+/// Plain JSON, unlike OTLP/JSON: arrays and key-value lists become arrays
+/// and objects, bytes a base64 string and an empty value `null`.
+#[cfg(feature = "json")]
+impl From<&AnyValue<'_>> for serde_json::Value {
+    fn from(value: &AnyValue<'_>) -> Self {
+        use serde_json::Value;
+
+        match &value.value {
+            AnyValueOneOfvalue::string_value(s) => Value::from(s.as_ref()),
+            AnyValueOneOfvalue::bool_value(b) => Value::from(*b),
+            AnyValueOneOfvalue::int_value(n) => Value::from(*n),
+            AnyValueOneOfvalue::double_value(n) => crate::json::from_f64(*n),
+            AnyValueOneOfvalue::bytes_value(bytes) => Value::from(crate::json::to_base64(bytes)),
+            AnyValueOneOfvalue::array_value(array) => {
+                array.values.iter().map(Value::from).collect()
+            }
+            AnyValueOneOfvalue::kvlist_value(kvlist) => Value::Object(
+                kvlist
+                    .values
+                    .iter()
+                    .map(|kv| {
+                        let value = kv.value.as_ref().map_or(Value::Null, Value::from);
+                        (kv.key.to_string(), value)
+                    })
+                    .collect(),
+            ),
+            AnyValueOneOfvalue::None => Value::Null,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn conversions() {
+        assert_eq!(
+            AnyValue::from("x").value,
+            AnyValueOneOfvalue::string_value(Cow::Borrowed("x"))
+        );
+        assert_eq!(AnyValue::from(String::from("x")), AnyValue::from("x"));
+        assert_eq!(AnyValue::from(1i64).value, AnyValueOneOfvalue::int_value(1));
+        assert_eq!(
+            AnyValue::from(1.5).value,
+            AnyValueOneOfvalue::double_value(1.5)
+        );
+        assert_eq!(
+            AnyValue::from(true).value,
+            AnyValueOneOfvalue::bool_value(true)
+        );
+        assert_eq!(
+            AnyValue::from(vec![1u8, 2]).value,
+            AnyValueOneOfvalue::bytes_value(Cow::Borrowed(&[1, 2]))
+        );
+
+        let array = AnyValue::from(vec![1i64, 2]);
+        assert!(
+            matches!(&array.value, AnyValueOneOfvalue::array_value(array) if array.values.len() == 2)
+        );
+        let kv = KeyValue::from(("k", "v"));
+        assert_eq!(kv.key, "k");
+        assert_eq!(kv.value, Some(AnyValue::from("v")));
+
+        let map = AnyValue::from(BTreeMap::from([("b", 2i64), ("a", 1)]));
+        let AnyValueOneOfvalue::kvlist_value(kvlist) = &map.value else {
+            panic!("expected a kvlist, got {map:?}");
+        };
+        assert_eq!(
+            kvlist.values,
+            [KeyValue::from(("a", 1i64)), KeyValue::from(("b", 2i64))]
+        );
+        assert_eq!(
+            AnyValue::from(HashMap::from([("a", 1i64)])),
+            AnyValue::from(BTreeMap::from([("a", 1i64)]))
+        );
+    }
+
+    #[test]
+    fn try_from_any_value() {
+        let value = AnyValue::from("x");
+        assert_eq!(<&str>::try_from(&value).unwrap(), "x");
+        assert_eq!(String::try_from(&value).unwrap(), "x");
+        assert!(matches!(
+            i64::try_from(&value),
+            Err(crate::Error::UnexpectedAnyValue {
+                expected: "int",
+                found: "string"
+            })
+        ));
+        assert_eq!(i64::try_from(&AnyValue::from(3i64)).unwrap(), 3);
+        assert_eq!(f64::try_from(&AnyValue::from(0.5)).unwrap(), 0.5);
+        assert!(bool::try_from(&AnyValue::from(true)).unwrap());
+        assert_eq!(
+            Vec::<u8>::try_from(&AnyValue::from(vec![7u8])).unwrap(),
+            [7]
+        );
+        assert!(bool::try_from(&AnyValue::default()).is_err());
+    }
+
+    #[test]
+    fn display() {
+        let value = AnyValue::from(BTreeMap::from([
+            (
+                "list",
+                AnyValue::from(vec![AnyValue::from(1i64), AnyValue::from("a")]),
+            ),
+            ("bytes", AnyValue::from(vec![0xabu8, 1])),
+            ("empty", AnyValue::default()),
+        ]));
+        assert_eq!(
+            value.to_string(),
+            r#"{bytes: ab01, empty: null, list: [1, "a"]}"#
+        );
+        assert_eq!(AnyValue::from("plain").to_string(), "plain");
+        assert_eq!(AnyValue::from(2.5).to_string(), "2.5");
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn to_json_value() {
+        let value = AnyValue::from(BTreeMap::from([
+            (
+                "list",
+                AnyValue::from(vec![AnyValue::from(1i64), AnyValue::from(f64::NAN)]),
+            ),
+            ("bytes", AnyValue::from(vec![1u8, 2])),
+            ("empty", AnyValue::default()),
+        ]));
+        assert_eq!(
+            serde_json::Value::from(&value),
+            serde_json::json!({"list": [1, "NaN"], "bytes": "AQI=", "empty": null})
+        );
+    }
+}
@@ -0,0 +1,255 @@
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+
+use crate::opentelemetry::common::{
+    AnyValue, AnyValueOneOfvalue, InstrumentationScope, KeyValue, Resource,
+};
+use crate::opentelemetry::logs::LogRecord;
+use crate::opentelemetry::metrics::{
+    ExponentialHistogramDataPoint, HistogramDataPoint, NumberDataPoint, SummaryDataPoint,
+};
+use crate::opentelemetry::trace::Span;
+
+/// Access to the `attributes` of a message by key.
+///
+/// Lookups return the first attribute with the key, as keys are meant to be
+/// unique, and skip attributes without a value.
+pub trait Attributes<'a> {
+    fn attributes(&self) -> &[KeyValue<'a>];
+
+    fn attributes_mut(&mut self) -> &mut Vec<KeyValue<'a>>;
+
+    fn get_attr(&self, key: &str) -> Option<&AnyValue<'a>> {
+        self.attributes()
+            .iter()
+            .find(|attribute| attribute.key == key && attribute.value.is_some())?
+            .value
+            .as_ref()
+    }
+
+    fn get_str<'s>(&'s self, key: &str) -> Option<&'s str>
+    where
+        'a: 's,
+    {
+        match &self.get_attr(key)?.value {
+            AnyValueOneOfvalue::string_value(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    fn get_i64(&self, key: &str) -> Option<i64> {
+        match self.get_attr(key)?.value {
+            AnyValueOneOfvalue::int_value(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    fn get_bool(&self, key: &str) -> Option<bool> {
+        match self.get_attr(key)?.value {
+            AnyValueOneOfvalue::bool_value(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Sets the value of the attribute with `key`, adding it if missing.
+    fn set_attr(&mut self, key: impl Into<Cow<'a, str>>, value: impl Into<AnyValue<'a>>) {
+        let key = key.into();
+        let value = Some(value.into());
+        let attributes = self.attributes_mut();
+        match attributes.iter_mut().find(|attribute| attribute.key == key) {
+            Some(attribute) => attribute.value = value,
+            None => attributes.push(KeyValue {
+                key,
+                value,
+                ..Default::default()
+            }),
+        }
+    }
+
+    /// Removes every attribute with `key`, returning the value
+    /// [`Attributes::get_attr`] would have.
+    fn remove_attr(&mut self, key: &str) -> Option<AnyValue<'a>> {
+        let mut removed = None;
+        self.attributes_mut().retain_mut(|attribute| {
+            if attribute.key != key {
+                return true;
+            }
+            if removed.is_none() {
+                removed = attribute.value.take();
+            }
+            false
+        });
+        removed
+    }
+
+    /// The attributes by key, as [`Attributes::get_attr`] sees them.
+    fn attribute_map(&self) -> BTreeMap<&str, &AnyValue<'a>> {
+        let mut map = BTreeMap::new();
+        for attribute in self.attributes() {
+            if let Some(value) = &attribute.value {
+                map.entry(attribute.key.as_ref()).or_insert(value);
+            }
+        }
+        map
+    }
+}
+
+impl<'a> Attributes<'a> for Resource<'a> {
+    fn attributes(&self) -> &[KeyValue<'a>] {
+        &self.attributes
+    }
+
+    fn attributes_mut(&mut self) -> &mut Vec<KeyValue<'a>> {
+        &mut self.attributes
+    }
+}
+
+impl<'a> Attributes<'a> for InstrumentationScope<'a> {
+    fn attributes(&self) -> &[KeyValue<'a>] {
+        &self.attributes
+    }
+
+    fn attributes_mut(&mut self) -> &mut Vec<KeyValue<'a>> {
+        &mut self.attributes
+    }
+}
+
+impl<'a> Attributes<'a> for Span<'a> {
+    fn attributes(&self) -> &[KeyValue<'a>] {
+        &self.attributes
+    }
+
+    fn attributes_mut(&mut self) -> &mut Vec<KeyValue<'a>> {
+        &mut self.attributes
+    }
+}
+
+impl<'a> Attributes<'a> for LogRecord<'a> {
+    fn attributes(&self) -> &[KeyValue<'a>] {
+        &self.attributes
+    }
+
+    fn attributes_mut(&mut self) -> &mut Vec<KeyValue<'a>> {
+        &mut self.attributes
+    }
+}
+
+impl<'a> Attributes<'a> for NumberDataPoint<'a> {
+    fn attributes(&self) -> &[KeyValue<'a>] {
+        &self.attributes
+    }
+
+    fn attributes_mut(&mut self) -> &mut Vec<KeyValue<'a>> {
+        &mut self.attributes
+    }
+}
+
+impl<'a> Attributes<'a> for HistogramDataPoint<'a> {
+    fn attributes(&self) -> &[KeyValue<'a>] {
+        &self.attributes
+    }
+
+    fn attributes_mut(&mut self) -> &mut Vec<KeyValue<'a>> {
+        &mut self.attributes
+    }
+}
+
+impl<'a> Attributes<'a> for ExponentialHistogramDataPoint<'a> {
+    fn attributes(&self) -> &[KeyValue<'a>] {
+        &self.attributes
+    }
+
+    fn attributes_mut(&mut self) -> &mut Vec<KeyValue<'a>> {
+        &mut self.attributes
+    }
+}
+
+impl<'a> Attributes<'a> for SummaryDataPoint<'a> {
+    fn attributes(&self) -> &[KeyValue<'a>] {
+        &self.attributes
+    }
+
+    fn attributes_mut(&mut self) -> &mut Vec<KeyValue<'a>> {
+        &mut self.attributes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::opentelemetry::trace::ExportTraceServiceRequest;
+
+    #[test]
+    fn typed_getters() {
+        let mut resource = Resource::default();
+        resource.set_attr("service.name", "api");
+        resource.set_attr("pid", 42i64);
+        resource.set_attr("debug", true);
+
+        assert_eq!(resource.get_str("service.name"), Some("api"));
+        assert_eq!(resource.get_i64("pid"), Some(42));
+        assert_eq!(resource.get_bool("debug"), Some(true));
+        assert_eq!(resource.get_i64("service.name"), None);
+        assert_eq!(resource.get_str("missing"), None);
+
+        // A duplicate without a value does not hide the one with a value
+        resource.attributes.insert(
+            0,
+            KeyValue {
+                key: Cow::from("pid"),
+                value: None,
+                ..Default::default()
+            },
+        );
+        assert_eq!(resource.get_i64("pid"), Some(42));
+        assert_eq!(
+            resource.attribute_map().keys().collect::<Vec<_>>(),
+            [&"debug", &"pid", &"service.name"]
+        );
+    }
+
+    #[test]
+    fn set_and_remove() {
+        let mut span = Span::default();
+        span.set_attr("http.route", "/a");
+        span.set_attr(String::from("http.route"), "/b");
+        assert_eq!(span.attributes.len(), 1);
+        assert_eq!(span.get_str("http.route"), Some("/b"));
+
+        span.attributes.insert(
+            0,
+            KeyValue {
+                key: Cow::from("http.route"),
+                value: None,
+                ..Default::default()
+            },
+        );
+        span.attributes.push(span.attributes[1].clone());
+        assert_eq!(span.remove_attr("http.route"), Some(AnyValue::from("/b")));
+        assert!(span.attributes.is_empty());
+        assert_eq!(span.remove_attr("http.route"), None);
+    }
+
+    #[test]
+    fn borrowed_values() {
+        let mut request = ExportTraceServiceRequest::default();
+        let mut resource = Resource::default();
+        resource.set_attr("service.name", "api");
+        request
+            .resource_spans
+            .push(crate::opentelemetry::trace::ResourceSpans {
+                resource: Some(resource),
+                ..Default::default()
+            });
+        let bytes = request.encode_to_vec();
+
+        let decoded = ExportTraceServiceRequest::try_from(&bytes[..]).unwrap();
+        let resource = decoded.resource_spans[0].resource.as_ref().unwrap();
+        let value = resource.get_attr("service.name").unwrap();
+        assert!(matches!(
+            &value.value,
+            AnyValueOneOfvalue::string_value(Cow::Borrowed(_))
+        ));
+        assert_eq!(resource.get_str("service.name"), Some("api"));
+    }
+}
@@ -0,0 +1,679 @@
+use std::mem::take;
+
+use quick_protobuf::sizeofs::sizeof_len;
+use quick_protobuf::MessageWrite;
+
+use crate::opentelemetry::logs::{ExportLogsServiceRequest, LogRecord, ResourceLogs, ScopeLogs};
+use crate::opentelemetry::metrics::{
+    ExponentialHistogram, ExportMetricsServiceRequest, Gauge, Histogram, Metric, MetricOneOfdata,
+    ResourceMetrics, ScopeMetrics, Sum, Summary,
+};
+use crate::opentelemetry::trace::{ExportTraceServiceRequest, ResourceSpans, ScopeSpans, Span};
+
+// Upper bound of a field's tag and length prefix, for the resources and
+// scopes whose length is not known yet
+const MAX_PREFIX_LEN: usize = 6;
+
+// An export request, a `Resource*` or a `Scope*`: a list of children, and
+// for the latter two the fields that give them their context
+trait Group: Default {
+    type Child;
+
+    fn children(&mut self) -> &mut Vec<Self::Child>;
+
+    /// Whether both have the same fields, children aside.
+    fn same_context(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+// A `Scope*`, whose children are the items counted against `max_items`
+trait Scope: Group + Clone + MessageWrite
+where
+    Self::Child: MessageWrite,
+{
+    fn count(_item: &Self::Child) -> usize {
+        1
+    }
+
+    /// Number of leading parts of `item` that fit in an item of at most
+    /// `max_bytes` encoded bytes, or 0 if it cannot be split.
+    fn fitting(_item: &Self::Child, _max_bytes: usize) -> usize {
+        0
+    }
+
+    /// Moves the parts of `item` past the first `at` to a new item, if it
+    /// can be split.
+    fn split_off(_item: &mut Self::Child, _at: usize) -> Option<Self::Child> {
+        None
+    }
+}
+
+struct Splitter<R, S> {
+    max_items: usize,
+    max_bytes: usize,
+    done: Vec<R>,
+    // The request without its resources, which every part starts from
+    empty: R,
+    current: R,
+    items: usize,
+    bytes: usize,
+    // Indices of the input resource and scope the last item came from
+    last: Option<(usize, usize)>,
+    _scope: std::marker::PhantomData<S>,
+}
+
+impl<R, S> Splitter<R, S>
+where
+    R: Group + Clone,
+    R::Child: Group<Child = S> + Clone + MessageWrite,
+    S: Scope,
+    S::Child: MessageWrite,
+{
+    fn split(mut request: R, max_items: usize, max_bytes: usize) -> Vec<R> {
+        let resources = take(request.children());
+        let mut splitter = Splitter {
+            max_items,
+            max_bytes,
+            done: vec![],
+            empty: request.clone(),
+            current: request,
+            items: 0,
+            bytes: 0,
+            last: None,
+            _scope: std::marker::PhantomData,
+        };
+
+        for (r, mut resource) in resources.into_iter().enumerate() {
+            let scopes = take(resource.children());
+            for (s, mut scope) in scopes.into_iter().enumerate() {
+                for item in take(scope.children()) {
+                    splitter.push((r, s), &resource, &scope, item);
+                }
+            }
+        }
+
+        if splitter.items > 0 {
+            splitter.done.push(splitter.current);
+        }
+        splitter.done
+    }
+
+    fn push(&mut self, ids: (usize, usize), resource: &R::Child, scope: &S, mut item: S::Child) {
+        loop {
+            let count = S::count(&item);
+            let context = self.context_size(ids, resource, scope);
+            let size = 1 + sizeof_len(item.get_size());
+            if self.items + count <= self.max_items && self.bytes + context + size <= self.max_bytes
+            {
+                self.bytes += context + size;
+                self.append(ids, resource, scope, item);
+                return;
+            }
+
+            // Fill the current request with as much of the item as fits. An
+            // empty one takes at least one part, even over the limits.
+            let room = self.max_items.saturating_sub(self.items);
+            let budget = self
+                .max_bytes
+                .saturating_sub(self.bytes + context + MAX_PREFIX_LEN);
+            let mut at = S::fitting(&item, budget).min(room);
+            if self.items == 0 {
+                at = at.max(1);
+            }
+            if at > 0 && at < count {
+                if let Some(rest) = S::split_off(&mut item, at) {
+                    self.bytes += context + 1 + sizeof_len(item.get_size());
+                    self.append(ids, resource, scope, item);
+                    self.flush();
+                    item = rest;
+                    continue;
+                }
+            }
+            if self.items > 0 {
+                self.flush();
+                continue;
+            }
+
+            self.bytes += context + size;
+            self.append(ids, resource, scope, item);
+            return;
+        }
+    }
+
+    // Bytes taken by the resource and scope of the next item, if they are
+    // not the ones of the last item
+    fn context_size(&self, ids: (usize, usize), resource: &R::Child, scope: &S) -> usize {
+        let mut size = 0;
+        if self.last.map(|(r, _)| r) != Some(ids.0) {
+            size += MAX_PREFIX_LEN + resource.get_size();
+        }
+        if self.last != Some(ids) {
+            size += MAX_PREFIX_LEN + scope.get_size();
+        }
+        size
+    }
+
+    fn append(&mut self, ids: (usize, usize), resource: &R::Child, scope: &S, item: S::Child) {
+        if self.last.map(|(r, _)| r) != Some(ids.0) {
+            self.current.children().push(resource.clone());
+        }
+        let resource = self.current.children().last_mut().expect("pushed above");
+        if self.last != Some(ids) {
+            resource.children().push(scope.clone());
+        }
+        let scope = resource.children().last_mut().expect("pushed above");
+        self.items += S::count(&item);
+        scope.children().push(item);
+        self.last = Some(ids);
+    }
+
+    fn flush(&mut self) {
+        let empty = self.empty.clone();
+        self.done.push(std::mem::replace(&mut self.current, empty));
+        self.items = 0;
+        self.bytes = 0;
+        self.last = None;
+    }
+}
+
+fn merge<R>(requests: impl IntoIterator<Item = R>) -> R
+where
+    R: Group,
+    R::Child: Group,
+    <R::Child as Group>::Child: Group,
+{
+    // The fields of the first request other than its resources are kept
+    let mut requests = requests.into_iter();
+    let mut merged = requests.next().unwrap_or_default();
+    let first = take(merged.children());
+    let rest = requests.flat_map(|mut request| take(request.children()));
+    for mut resource in first.into_iter().chain(rest) {
+        let resources = merged.children();
+        let Some(existing) = resources.iter_mut().find(|r| r.same_context(&resource)) else {
+            resources.push(resource);
+            continue;
+        };
+        for mut scope in take(resource.children()) {
+            let scopes = existing.children();
+            match scopes.iter_mut().find(|s| s.same_context(&scope)) {
+                Some(existing) => existing.children().append(scope.children()),
+                None => scopes.push(scope),
+            }
+        }
+    }
+    merged
+}
+
+impl<'a> Group for ExportTraceServiceRequest<'a> {
+    type Child = ResourceSpans<'a>;
+
+    fn children(&mut self) -> &mut Vec<Self::Child> {
+        &mut self.resource_spans
+    }
+}
+
+impl<'a> Group for ResourceSpans<'a> {
+    type Child = ScopeSpans<'a>;
+
+    fn children(&mut self) -> &mut Vec<Self::Child> {
+        &mut self.scope_spans
+    }
+
+    fn same_context(&self, other: &Self) -> bool {
+        self.resource == other.resource
+            && self.schema_url == other.schema_url
+            && self.unknown_fields == other.unknown_fields
+    }
+}
+
+impl<'a> Group for ScopeSpans<'a> {
+    type Child = Span<'a>;
+
+    fn children(&mut self) -> &mut Vec<Self::Child> {
+        &mut self.spans
+    }
+
+    fn same_context(&self, other: &Self) -> bool {
+        self.scope == other.scope
+            && self.schema_url == other.schema_url
+            && self.unknown_fields == other.unknown_fields
+    }
+}
+
+impl Scope for ScopeSpans<'_> {}
+
+impl<'a> Group for ExportLogsServiceRequest<'a> {
+    type Child = ResourceLogs<'a>;
+
+    fn children(&mut self) -> &mut Vec<Self::Child> {
+        &mut self.resource_logs
+    }
+}
+
+impl<'a> Group for ResourceLogs<'a> {
+    type Child = ScopeLogs<'a>;
+
+    fn children(&mut self) -> &mut Vec<Self::Child> {
+        &mut self.scope_logs
+    }
+
+    fn same_context(&self, other: &Self) -> bool {
+        self.resource == other.resource
+            && self.schema_url == other.schema_url
+            && self.unknown_fields == other.unknown_fields
+    }
+}
+
+impl<'a> Group for ScopeLogs<'a> {
+    type Child = LogRecord<'a>;
+
+    fn children(&mut self) -> &mut Vec<Self::Child> {
+        &mut self.log_records
+    }
+
+    fn same_context(&self, other: &Self) -> bool {
+        self.scope == other.scope
+            && self.schema_url == other.schema_url
+            && self.unknown_fields == other.unknown_fields
+    }
+}
+
+impl Scope for ScopeLogs<'_> {}
+
+impl<'a> Group for ExportMetricsServiceRequest<'a> {
+    type Child = ResourceMetrics<'a>;
+
+    fn children(&mut self) -> &mut Vec<Self::Child> {
+        &mut self.resource_metrics
+    }
+}
+
+impl<'a> Group for ResourceMetrics<'a> {
+    type Child = ScopeMetrics<'a>;
+
+    fn children(&mut self) -> &mut Vec<Self::Child> {
+        &mut self.scope_metrics
+    }
+
+    fn same_context(&self, other: &Self) -> bool {
+        self.resource == other.resource
+            && self.schema_url == other.schema_url
+            && self.unknown_fields == other.unknown_fields
+    }
+}
+
+impl<'a> Group for ScopeMetrics<'a> {
+    type Child = Metric<'a>;
+
+    fn children(&mut self) -> &mut Vec<Self::Child> {
+        &mut self.metrics
+    }
+
+    fn same_context(&self, other: &Self) -> bool {
+        self.scope == other.scope
+            && self.schema_url == other.schema_url
+            && self.unknown_fields == other.unknown_fields
+    }
+}
+
+pub(crate) fn data_points_len(metric: &Metric) -> usize {
+    match &metric.data {
+        MetricOneOfdata::gauge(gauge) => gauge.data_points.len(),
+        MetricOneOfdata::sum(sum) => sum.data_points.len(),
+        MetricOneOfdata::histogram(histogram) => histogram.data_points.len(),
+        MetricOneOfdata::exponential_histogram(histogram) => histogram.data_points.len(),
+        MetricOneOfdata::summary(summary) => summary.data_points.len(),
+        MetricOneOfdata::None => 0,
+    }
+}
+
+// Encoded size of each data point of a metric, tag and length included
+fn data_point_sizes(metric: &Metric) -> Vec<usize> {
+    fn sizes<M: MessageWrite>(data_points: &[M]) -> Vec<usize> {
+        data_points
+            .iter()
+            .map(|data_point| 1 + sizeof_len(data_point.get_size()))
+            .collect()
+    }
+
+    match &metric.data {
+        MetricOneOfdata::gauge(gauge) => sizes(&gauge.data_points),
+        MetricOneOfdata::sum(sum) => sizes(&sum.data_points),
+        MetricOneOfdata::histogram(histogram) => sizes(&histogram.data_points),
+        MetricOneOfdata::exponential_histogram(histogram) => sizes(&histogram.data_points),
+        MetricOneOfdata::summary(summary) => sizes(&summary.data_points),
+        MetricOneOfdata::None => vec![],
+    }
+}
+
+// Metrics are split between their data points, which are what gets counted
+impl<'a> Scope for ScopeMetrics<'a> {
+    fn count(metric: &Metric<'a>) -> usize {
+        data_points_len(metric)
+    }
+
+    // The rest of the metric only shrinks with fewer data points, as the
+    // length prefix of its data does
+    fn fitting(metric: &Metric<'a>, max_bytes: usize) -> usize {
+        let sizes = data_point_sizes(metric);
+        let mut size = metric.get_size() - sizes.iter().sum::<usize>();
+        sizes
+            .iter()
+            .take_while(|data_point| {
+                size += *data_point;
+                size <= max_bytes
+            })
+            .count()
+    }
+
+    fn split_off(metric: &mut Metric<'a>, at: usize) -> Option<Metric<'a>> {
+        let data = match &mut metric.data {
+            MetricOneOfdata::gauge(gauge) => MetricOneOfdata::gauge(Gauge {
+                data_points: gauge.data_points.split_off(at),
+                unknown_fields: gauge.unknown_fields.clone(),
+            }),
+            MetricOneOfdata::sum(sum) => MetricOneOfdata::sum(Sum {
+                data_points: sum.data_points.split_off(at),
+                aggregation_temporality: sum.aggregation_temporality,
+                is_monotonic: sum.is_monotonic,
+                unknown_fields: sum.unknown_fields.clone(),
+            }),
+            MetricOneOfdata::histogram(histogram) => MetricOneOfdata::histogram(Histogram {
+                data_points: histogram.data_points.split_off(at),
+                aggregation_temporality: histogram.aggregation_temporality,
+                unknown_fields: histogram.unknown_fields.clone(),
+            }),
+            MetricOneOfdata::exponential_histogram(histogram) => {
+                MetricOneOfdata::exponential_histogram(ExponentialHistogram {
+                    data_points: histogram.data_points.split_off(at),
+                    aggregation_temporality: histogram.aggregation_temporality,
+                    unknown_fields: histogram.unknown_fields.clone(),
+                })
+            }
+            MetricOneOfdata::summary(summary) => MetricOneOfdata::summary(Summary {
+                data_points: summary.data_points.split_off(at),
+                unknown_fields: summary.unknown_fields.clone(),
+            }),
+            MetricOneOfdata::None => return None,
+        };
+
+        Some(Metric {
+            name: metric.name.clone(),
+            description: metric.description.clone(),
+            unit: metric.unit.clone(),
+            metadata: metric.metadata.clone(),
+            data,
+            unknown_fields: metric.unknown_fields.clone(),
+        })
+    }
+}
+
+impl<'a> ExportTraceServiceRequest<'a> {
+    /// Splits the request into requests of at most `max_items` spans and
+    /// `max_bytes` encoded bytes each.
+    ///
+    /// Every part keeps the resource, scope and schema urls of its spans,
+    /// and the unknown fields of the request, resource and scope. A span
+    /// larger than `max_bytes` is sent in a request of its own. Empty
+    /// resources and scopes are dropped.
+    pub fn split(self, max_items: usize, max_bytes: usize) -> Vec<Self> {
+        Splitter::split(self, max_items, max_bytes)
+    }
+
+    /// Combines requests into one, coalescing the entries with identical
+    /// resources and scopes. The unknown fields of the merged request are
+    /// those of the first one.
+    pub fn merge(requests: impl IntoIterator<Item = Self>) -> Self {
+        merge(requests)
+    }
+}
+
+impl<'a> ExportLogsServiceRequest<'a> {
+    /// Splits the request into requests of at most `max_items` log records
+    /// and `max_bytes` encoded bytes each.
+    ///
+    /// Every part keeps the resource, scope and schema urls of its records,
+    /// and the unknown fields of the request, resource and scope. A record
+    /// larger than `max_bytes` is sent in a request of its own. Empty
+    /// resources and scopes are dropped.
+    pub fn split(self, max_items: usize, max_bytes: usize) -> Vec<Self> {
+        Splitter::split(self, max_items, max_bytes)
+    }
+
+    /// Combines requests into one, coalescing the entries with identical
+    /// resources and scopes. The unknown fields of the merged request are
+    /// those of the first one.
+    pub fn merge(requests: impl IntoIterator<Item = Self>) -> Self {
+        merge(requests)
+    }
+}
+
+impl<'a> ExportMetricsServiceRequest<'a> {
+    /// Splits the request into requests of at most `max_items` data points
+    /// and `max_bytes` encoded bytes each.
+    ///
+    /// A metric with more data points than fit, by count or by size, is
+    /// split into several with the same name, description, unit, metadata
+    /// and unknown fields. A data point larger than `max_bytes` is sent in a
+    /// request of its own. Every part keeps the resource, scope and schema
+    /// urls of its metrics, and the unknown fields of the request, resource
+    /// and scope. Empty resources and scopes are dropped.
+    pub fn split(self, max_items: usize, max_bytes: usize) -> Vec<Self> {
+        Splitter::split(self, max_items, max_bytes)
+    }
+
+    /// Combines requests into one, coalescing the entries with identical
+    /// resources and scopes. The unknown fields of the merged request are
+    /// those of the first one. Metrics are not merged with each other.
+    pub fn merge(requests: impl IntoIterator<Item = Self>) -> Self {
+        merge(requests)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::opentelemetry::common::{InstrumentationScope, Resource};
+    use crate::opentelemetry::metrics::{NumberDataPoint, NumberDataPointOneOfvalue};
+
+    fn resource_spans(resource: u32, scopes: &[(&'static str, usize)]) -> ResourceSpans<'static> {
+        ResourceSpans {
+            resource: Some(Resource {
+                dropped_attributes_count: resource,
+                ..Default::default()
+            }),
+            scope_spans: scopes
+                .iter()
+                .map(|(name, spans)| ScopeSpans {
+                    scope: Some(InstrumentationScope {
+                        name: (*name).into(),
+                        ..Default::default()
+                    }),
+                    spans: (0..*spans)
+                        .map(|i| Span {
+                            name: format!("{name}-{i}").into(),
+                            ..Default::default()
+                        })
+                        .collect(),
+                    schema_url: "https://some_url.com".into(),
+                    ..Default::default()
+                })
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    fn request() -> ExportTraceServiceRequest<'static> {
+        ExportTraceServiceRequest {
+            resource_spans: vec![
+                resource_spans(1, &[("a", 3), ("b", 2)]),
+                resource_spans(2, &[("c", 4)]),
+            ],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn split_by_count() {
+        let parts = request().split(4, usize::MAX);
+        let counts = parts
+            .iter()
+            .map(|part| {
+                part.resource_spans
+                    .iter()
+                    .flat_map(|resource_spans| &resource_spans.scope_spans)
+                    .map(|scope_spans| scope_spans.spans.len())
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(counts, [vec![3, 1], vec![1, 3], vec![1]]);
+        assert_eq!(
+            parts[1].resource_spans[0].resource,
+            request().resource_spans[0].resource
+        );
+        assert_eq!(
+            parts[1].resource_spans[1].resource,
+            request().resource_spans[1].resource
+        );
+        assert_eq!(
+            parts[1].resource_spans[0].scope_spans[0].scope,
+            request().resource_spans[0].scope_spans[1].scope
+        );
+
+        assert_eq!(ExportTraceServiceRequest::merge(parts), request());
+        assert_eq!(request().split(usize::MAX, usize::MAX), [request()]);
+        assert!(ExportTraceServiceRequest::default().split(1, 1).is_empty());
+    }
+
+    #[test]
+    fn split_by_size() {
+        let max_bytes = request().encode_to_vec().len() / 2;
+        let parts = request().split(usize::MAX, max_bytes);
+        assert!(parts.len() > 1);
+        for part in &parts {
+            assert!(part.encode_to_vec().len() <= max_bytes);
+        }
+        assert_eq!(ExportTraceServiceRequest::merge(parts), request());
+
+        // Spans over the limit go alone
+        assert_eq!(request().split(usize::MAX, 1).len(), 9);
+    }
+
+    #[test]
+    fn merge_coalesces_identical_context() {
+        let merged = ExportTraceServiceRequest::merge([
+            ExportTraceServiceRequest {
+                resource_spans: vec![resource_spans(1, &[("a", 1)])],
+                ..Default::default()
+            },
+            ExportTraceServiceRequest {
+                resource_spans: vec![
+                    resource_spans(2, &[("a", 1)]),
+                    resource_spans(1, &[("b", 1), ("a", 2)]),
+                ],
+                ..Default::default()
+            },
+        ]);
+
+        assert_eq!(merged.resource_spans.len(), 2);
+        let scope_spans = &merged.resource_spans[0].scope_spans;
+        assert_eq!(scope_spans.len(), 2);
+        let names = scope_spans[0]
+            .spans
+            .iter()
+            .map(|span| span.name.as_ref())
+            .collect::<Vec<_>>();
+        assert_eq!(names, ["a-0", "a-0", "a-1"]);
+    }
+
+    #[test]
+    fn split_metric_data_points() {
+        let metric = Metric {
+            name: "requests".into(),
+            unit: "1".into(),
+            data: MetricOneOfdata::sum(Sum {
+                data_points: vec![NumberDataPoint::default(); 5],
+                is_monotonic: true,
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let request = ExportMetricsServiceRequest {
+            resource_metrics: vec![ResourceMetrics {
+                scope_metrics: vec![ScopeMetrics {
+                    metrics: vec![metric.clone()],
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        let parts = request.clone().split(2, usize::MAX);
+        assert_eq!(parts.len(), 3);
+        for (part, len) in parts.iter().zip([2, 2, 1]) {
+            let metrics = &part.resource_metrics[0].scope_metrics[0].metrics;
+            assert_eq!(metrics.len(), 1);
+            assert_eq!(metrics[0].name, "requests");
+            match &metrics[0].data {
+                MetricOneOfdata::sum(sum) => {
+                    assert!(sum.is_monotonic);
+                    assert_eq!(sum.data_points.len(), len);
+                }
+                data => panic!("unexpected data {data:?}"),
+            }
+        }
+
+        // Merging keeps the parts as separate metrics
+        let merged = ExportMetricsServiceRequest::merge(parts);
+        assert_eq!(merged.resource_metrics[0].scope_metrics[0].metrics.len(), 3);
+    }
+
+    #[test]
+    fn split_metric_data_points_by_size() {
+        let metric = Metric {
+            name: "requests".into(),
+            data: MetricOneOfdata::gauge(Gauge {
+                data_points: (0..20)
+                    .map(|i| NumberDataPoint {
+                        time_unix_nano: 1_700_000_000_000_000_000 + i,
+                        value: NumberDataPointOneOfvalue::as_int(i as i64),
+                        ..Default::default()
+                    })
+                    .collect(),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let request = ExportMetricsServiceRequest {
+            resource_metrics: vec![ResourceMetrics {
+                scope_metrics: vec![ScopeMetrics {
+                    metrics: vec![metric],
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        let max_bytes = request.encode_to_vec().len() / 3;
+        let parts = request.clone().split(8, max_bytes);
+        assert!(parts.len() >= 3);
+        let mut data_points = 0;
+        for part in &parts {
+            assert!(part.encode_to_vec().len() <= max_bytes);
+            let count = part.resource_metrics[0].scope_metrics[0]
+                .metrics
+                .iter()
+                .map(data_points_len)
+                .sum::<usize>();
+            assert!(count <= 8);
+            data_points += count;
+        }
+        assert_eq!(data_points, 20);
+
+        // Data points over the limit go alone
+        assert_eq!(request.split(usize::MAX, 1).len(), 20);
+    }
+}
@@ -0,0 +1,982 @@
+use std::borrow::Cow;
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::opentelemetry::common::{AnyValue, InstrumentationScope, KeyValue, Resource};
+use crate::opentelemetry::logs::{
+    ExportLogsServiceRequest, LogRecord, ResourceLogs, ScopeLogs, SeverityNumber,
+    Validate as LogValidate,
+};
+use crate::opentelemetry::metrics::{
+    AggregationTemporality, ExponentialHistogram, ExponentialHistogramDataPoint,
+    ExponentialHistogramDataPointBuckets, ExportMetricsServiceRequest, Gauge, Histogram,
+    HistogramDataPoint, Metric, MetricOneOfdata, NumberDataPoint, NumberDataPointOneOfvalue,
+    ResourceMetrics, ScopeMetrics, Sum, Summary, SummaryDataPoint, SummaryDataPointValueAtQuantile,
+    Validate as MetricValidate,
+};
+use crate::opentelemetry::trace::{
+    ExportTraceServiceRequest, ResourceSpans, ScopeSpans, Span, SpanEvent, SpanKind, Status,
+    StatusCode, Validate as TraceValidate,
+};
+use crate::Attributes;
+
+fn unix_nanos(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH).map_or(0, saturating_nanos)
+}
+
+fn saturating_nanos(duration: Duration) -> u64 {
+    u64::try_from(duration.as_nanos()).unwrap_or(u64::MAX)
+}
+
+// Random enough to tell IDs apart, which is all they need; never all zeros,
+// as that is the invalid ID
+fn random_id<const N: usize>() -> [u8; N] {
+    let mut id = [0; N];
+    while id == [0; N] {
+        for chunk in id.chunks_mut(8) {
+            let random = RandomState::new().build_hasher().finish();
+            chunk.copy_from_slice(&random.to_le_bytes()[..chunk.len()]);
+        }
+    }
+    id
+}
+
+type Scopes<'a, T> = Vec<(InstrumentationScope<'a>, Vec<T>)>;
+
+// Items grouped by resource, then by scope, in the order they were added
+struct Groups<'a, T> {
+    resources: Vec<(Resource<'a>, Scopes<'a, T>)>,
+}
+
+impl<T> Default for Groups<'_, T> {
+    fn default() -> Self {
+        Groups { resources: vec![] }
+    }
+}
+
+impl<'a, T> Groups<'a, T> {
+    fn resource(&mut self, attributes: impl IntoIterator<Item = KeyValue<'a>>) {
+        let resource = Resource {
+            attributes: attributes.into_iter().collect(),
+            ..Default::default()
+        };
+        self.resources.push((resource, vec![]));
+    }
+
+    fn scope(&mut self, name: Cow<'a, str>, version: Cow<'a, str>) {
+        if self.resources.is_empty() {
+            self.resource([]);
+        }
+        let scope = InstrumentationScope {
+            name,
+            version,
+            ..Default::default()
+        };
+        let scopes = &mut self.resources.last_mut().unwrap().1;
+        scopes.push((scope, vec![]));
+    }
+
+    fn push(&mut self, item: T) {
+        if self
+            .resources
+            .last()
+            .is_none_or(|(_, scopes)| scopes.is_empty())
+        {
+            self.scope(Cow::Borrowed(""), Cow::Borrowed(""));
+        }
+        let scopes = &mut self.resources.last_mut().unwrap().1;
+        scopes.last_mut().unwrap().1.push(item);
+    }
+}
+
+/// Builds an [`ExportTraceServiceRequest`].
+///
+/// Spans go to the last [`resource`](Self::resource) and
+/// [`scope`](Self::scope), which default to empty ones.
+#[derive(Default)]
+pub struct TraceRequestBuilder<'a> {
+    groups: Groups<'a, SpanBuilder<'a>>,
+}
+
+impl<'a> TraceRequestBuilder<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Starts a resource with the given attributes.
+    pub fn resource(mut self, attributes: impl IntoIterator<Item = KeyValue<'a>>) -> Self {
+        self.groups.resource(attributes);
+        self
+    }
+
+    /// Starts an instrumentation scope in the current resource.
+    pub fn scope(
+        mut self,
+        name: impl Into<Cow<'a, str>>,
+        version: impl Into<Cow<'a, str>>,
+    ) -> Self {
+        self.groups.scope(name.into(), version.into());
+        self
+    }
+
+    pub fn span(mut self, span: SpanBuilder<'a>) -> Self {
+        self.groups.push(span);
+        self
+    }
+
+    /// Builds every span and validates the request.
+    pub fn build(self) -> Result<ExportTraceServiceRequest<'a>, crate::Error> {
+        let mut resource_spans = vec![];
+        for (resource, scopes) in self.groups.resources {
+            let mut scope_spans = vec![];
+            for (scope, spans) in scopes {
+                scope_spans.push(ScopeSpans {
+                    scope: Some(scope),
+                    spans: spans
+                        .into_iter()
+                        .map(SpanBuilder::build)
+                        .collect::<Result<_, _>>()?,
+                    schema_url: Cow::Borrowed(""),
+                    ..Default::default()
+                });
+            }
+            resource_spans.push(ResourceSpans {
+                resource: Some(resource),
+                scope_spans,
+                schema_url: Cow::Borrowed(""),
+                ..Default::default()
+            });
+        }
+
+        let request = ExportTraceServiceRequest {
+            resource_spans,
+            ..Default::default()
+        };
+        request.validate()?;
+        Ok(request)
+    }
+}
+
+/// Builds a [`Span`] of kind internal, with random trace and span IDs, that
+/// starts and ends when the builder is created.
+pub struct SpanBuilder<'a> {
+    span: Span<'a>,
+}
+
+impl<'a> SpanBuilder<'a> {
+    pub fn new(name: impl Into<Cow<'a, str>>) -> Self {
+        let now = unix_nanos(SystemTime::now());
+        let span = Span {
+            trace_id: Cow::Owned(random_id::<16>().to_vec()),
+            span_id: Cow::Owned(random_id::<8>().to_vec()),
+            name: name.into(),
+            kind: SpanKind::SPAN_KIND_INTERNAL,
+            start_time_unix_nano: now,
+            end_time_unix_nano: now,
+            ..Default::default()
+        };
+        SpanBuilder { span }
+    }
+
+    pub fn trace_id(mut self, trace_id: [u8; 16]) -> Self {
+        self.span.trace_id = Cow::Owned(trace_id.to_vec());
+        self
+    }
+
+    pub fn span_id(mut self, span_id: [u8; 8]) -> Self {
+        self.span.span_id = Cow::Owned(span_id.to_vec());
+        self
+    }
+
+    /// Makes this span a child of `parent`, in the same trace.
+    pub fn child_of(mut self, parent: &SpanBuilder<'_>) -> Self {
+        self.span.trace_id = Cow::Owned(parent.span.trace_id.to_vec());
+        self.span.parent_span_id = Cow::Owned(parent.span.span_id.to_vec());
+        self
+    }
+
+    pub fn kind(mut self, kind: SpanKind) -> Self {
+        self.span.kind = kind;
+        self
+    }
+
+    /// Sets the start time, keeping the duration.
+    pub fn start_time(mut self, time: SystemTime) -> Self {
+        let duration = self
+            .span
+            .end_time_unix_nano
+            .saturating_sub(self.span.start_time_unix_nano);
+        self.span.start_time_unix_nano = unix_nanos(time);
+        self.span.end_time_unix_nano = self.span.start_time_unix_nano.saturating_add(duration);
+        self
+    }
+
+    pub fn end_time(mut self, time: SystemTime) -> Self {
+        self.span.end_time_unix_nano = unix_nanos(time);
+        self
+    }
+
+    pub fn duration(mut self, duration: Duration) -> Self {
+        self.span.end_time_unix_nano = self
+            .span
+            .start_time_unix_nano
+            .saturating_add(saturating_nanos(duration));
+        self
+    }
+
+    pub fn attribute(
+        mut self,
+        key: impl Into<Cow<'a, str>>,
+        value: impl Into<AnyValue<'a>>,
+    ) -> Self {
+        self.span.set_attr(key, value);
+        self
+    }
+
+    pub fn event(mut self, name: impl Into<Cow<'a, str>>, time: SystemTime) -> Self {
+        self.span.events.push(SpanEvent {
+            time_unix_nano: unix_nanos(time),
+            name: name.into(),
+            attributes: vec![],
+            dropped_attributes_count: 0,
+            ..Default::default()
+        });
+        self
+    }
+
+    pub fn status(mut self, code: StatusCode, message: impl Into<Cow<'a, str>>) -> Self {
+        self.span.status = Some(Status {
+            message: message.into(),
+            code,
+            ..Default::default()
+        });
+        self
+    }
+
+    /// Validates the span, which must not end before it starts.
+    pub fn build(self) -> Result<Span<'a>, crate::Error> {
+        let span = self.span;
+        if span.end_time_unix_nano < span.start_time_unix_nano {
+            return Err(crate::Error::Build(format!(
+                "span {:?} ends before it starts",
+                span.name
+            )));
+        }
+        if span.name.is_empty() {
+            return Err(crate::Error::Build("span without a name".to_string()));
+        }
+        span.validate()?;
+        Ok(span)
+    }
+}
+
+/// Builds an [`ExportLogsServiceRequest`], grouping log records like
+/// [`TraceRequestBuilder`] groups spans.
+#[derive(Default)]
+pub struct LogsRequestBuilder<'a> {
+    groups: Groups<'a, LogRecordBuilder<'a>>,
+}
+
+impl<'a> LogsRequestBuilder<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Starts a resource with the given attributes.
+    pub fn resource(mut self, attributes: impl IntoIterator<Item = KeyValue<'a>>) -> Self {
+        self.groups.resource(attributes);
+        self
+    }
+
+    /// Starts an instrumentation scope in the current resource.
+    pub fn scope(
+        mut self,
+        name: impl Into<Cow<'a, str>>,
+        version: impl Into<Cow<'a, str>>,
+    ) -> Self {
+        self.groups.scope(name.into(), version.into());
+        self
+    }
+
+    pub fn log_record(mut self, log_record: LogRecordBuilder<'a>) -> Self {
+        self.groups.push(log_record);
+        self
+    }
+
+    /// Builds every log record and validates the request.
+    pub fn build(self) -> Result<ExportLogsServiceRequest<'a>, crate::Error> {
+        let mut resource_logs = vec![];
+        for (resource, scopes) in self.groups.resources {
+            let mut scope_logs = vec![];
+            for (scope, log_records) in scopes {
+                scope_logs.push(ScopeLogs {
+                    scope: Some(scope),
+                    log_records: log_records
+                        .into_iter()
+                        .map(LogRecordBuilder::build)
+                        .collect::<Result<_, _>>()?,
+                    schema_url: Cow::Borrowed(""),
+                    ..Default::default()
+                });
+            }
+            resource_logs.push(ResourceLogs {
+                resource: Some(resource),
+                scope_logs,
+                schema_url: Cow::Borrowed(""),
+                ..Default::default()
+            });
+        }
+
+        let request = ExportLogsServiceRequest {
+            resource_logs,
+            ..Default::default()
+        };
+        request.validate()?;
+        Ok(request)
+    }
+}
+
+/// Builds an INFO [`LogRecord`] observed and timestamped when the builder is
+/// created.
+pub struct LogRecordBuilder<'a> {
+    log_record: LogRecord<'a>,
+}
+
+impl<'a> LogRecordBuilder<'a> {
+    pub fn new(body: impl Into<AnyValue<'a>>) -> Self {
+        let now = unix_nanos(SystemTime::now());
+        let log_record = LogRecord {
+            time_unix_nano: now,
+            observed_time_unix_nano: now,
+            severity_number: SeverityNumber::SEVERITY_NUMBER_INFO,
+            severity_text: Cow::Borrowed("INFO"),
+            body: Some(body.into()),
+            ..Default::default()
+        };
+        LogRecordBuilder { log_record }
+    }
+
+    pub fn severity(mut self, number: SeverityNumber, text: impl Into<Cow<'a, str>>) -> Self {
+        self.log_record.severity_number = number;
+        self.log_record.severity_text = text.into();
+        self
+    }
+
+    pub fn time(mut self, time: SystemTime) -> Self {
+        self.log_record.time_unix_nano = unix_nanos(time);
+        self
+    }
+
+    pub fn observed_time(mut self, time: SystemTime) -> Self {
+        self.log_record.observed_time_unix_nano = unix_nanos(time);
+        self
+    }
+
+    pub fn event_name(mut self, name: impl Into<Cow<'a, str>>) -> Self {
+        self.log_record.event_name = name.into();
+        self
+    }
+
+    pub fn attribute(
+        mut self,
+        key: impl Into<Cow<'a, str>>,
+        value: impl Into<AnyValue<'a>>,
+    ) -> Self {
+        self.log_record.set_attr(key, value);
+        self
+    }
+
+    pub fn trace_context(mut self, trace_id: [u8; 16], span_id: [u8; 8]) -> Self {
+        self.log_record.trace_id = Cow::Owned(trace_id.to_vec());
+        self.log_record.span_id = Cow::Owned(span_id.to_vec());
+        self
+    }
+
+    /// Correlates the log record with `span`.
+    pub fn span(mut self, span: &SpanBuilder<'_>) -> Self {
+        self.log_record.trace_id = Cow::Owned(span.span.trace_id.to_vec());
+        self.log_record.span_id = Cow::Owned(span.span.span_id.to_vec());
+        self
+    }
+
+    pub fn build(self) -> Result<LogRecord<'a>, crate::Error> {
+        self.log_record.validate()?;
+        Ok(self.log_record)
+    }
+}
+
+/// Builds an [`ExportMetricsServiceRequest`], grouping metrics like
+/// [`TraceRequestBuilder`] groups spans.
+#[derive(Default)]
+pub struct MetricsRequestBuilder<'a> {
+    groups: Groups<'a, MetricBuilder<'a>>,
+}
+
+impl<'a> MetricsRequestBuilder<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Starts a resource with the given attributes.
+    pub fn resource(mut self, attributes: impl IntoIterator<Item = KeyValue<'a>>) -> Self {
+        self.groups.resource(attributes);
+        self
+    }
+
+    /// Starts an instrumentation scope in the current resource.
+    pub fn scope(
+        mut self,
+        name: impl Into<Cow<'a, str>>,
+        version: impl Into<Cow<'a, str>>,
+    ) -> Self {
+        self.groups.scope(name.into(), version.into());
+        self
+    }
+
+    pub fn metric(mut self, metric: MetricBuilder<'a>) -> Self {
+        self.groups.push(metric);
+        self
+    }
+
+    /// Builds every metric and validates the request.
+    pub fn build(self) -> Result<ExportMetricsServiceRequest<'a>, crate::Error> {
+        let mut resource_metrics = vec![];
+        for (resource, scopes) in self.groups.resources {
+            let mut scope_metrics = vec![];
+            for (scope, metrics) in scopes {
+                scope_metrics.push(ScopeMetrics {
+                    scope: Some(scope),
+                    metrics: metrics
+                        .into_iter()
+                        .map(MetricBuilder::build)
+                        .collect::<Result<_, _>>()?,
+                    schema_url: Cow::Borrowed(""),
+                    ..Default::default()
+                });
+            }
+            resource_metrics.push(ResourceMetrics {
+                resource: Some(resource),
+                scope_metrics,
+                schema_url: Cow::Borrowed(""),
+                ..Default::default()
+            });
+        }
+
+        let request = ExportMetricsServiceRequest {
+            resource_metrics,
+            ..Default::default()
+        };
+        request.validate()?;
+        Ok(request)
+    }
+}
+
+/// Builds a [`Metric`] of one kind and its data points.
+///
+/// Points are timestamped when the builder is created, and all but gauge
+/// points start then too; sums and histograms are cumulative. Adding a point
+/// of another kind is reported by [`build`](Self::build).
+pub struct MetricBuilder<'a> {
+    metric: Metric<'a>,
+    start_time_unix_nano: u64,
+    time_unix_nano: u64,
+    error: Option<String>,
+}
+
+impl<'a> MetricBuilder<'a> {
+    fn new(name: Cow<'a, str>, data: MetricOneOfdata<'a>) -> Self {
+        let now = unix_nanos(SystemTime::now());
+        let start_time_unix_nano = match data {
+            MetricOneOfdata::gauge(_) => 0,
+            _ => now,
+        };
+        MetricBuilder {
+            metric: Metric {
+                name,
+                data,
+                ..Default::default()
+            },
+            start_time_unix_nano,
+            time_unix_nano: now,
+            error: None,
+        }
+    }
+
+    pub fn gauge(name: impl Into<Cow<'a, str>>) -> Self {
+        Self::new(name.into(), MetricOneOfdata::gauge(Gauge::default()))
+    }
+
+    /// A non-monotonic sum, see [`monotonic`](Self::monotonic).
+    pub fn sum(name: impl Into<Cow<'a, str>>) -> Self {
+        let sum = Sum {
+            aggregation_temporality: AggregationTemporality::AGGREGATION_TEMPORALITY_CUMULATIVE,
+            ..Default::default()
+        };
+        Self::new(name.into(), MetricOneOfdata::sum(sum))
+    }
+
+    pub fn histogram(name: impl Into<Cow<'a, str>>) -> Self {
+        let histogram = Histogram {
+            aggregation_temporality: AggregationTemporality::AGGREGATION_TEMPORALITY_CUMULATIVE,
+            ..Default::default()
+        };
+        Self::new(name.into(), MetricOneOfdata::histogram(histogram))
+    }
+
+    pub fn exponential_histogram(name: impl Into<Cow<'a, str>>) -> Self {
+        let histogram = ExponentialHistogram {
+            aggregation_temporality: AggregationTemporality::AGGREGATION_TEMPORALITY_CUMULATIVE,
+            ..Default::default()
+        };
+        Self::new(
+            name.into(),
+            MetricOneOfdata::exponential_histogram(histogram),
+        )
+    }
+
+    pub fn summary(name: impl Into<Cow<'a, str>>) -> Self {
+        Self::new(name.into(), MetricOneOfdata::summary(Summary::default()))
+    }
+
+    pub fn description(mut self, description: impl Into<Cow<'a, str>>) -> Self {
+        self.metric.description = description.into();
+        self
+    }
+
+    pub fn unit(mut self, unit: impl Into<Cow<'a, str>>) -> Self {
+        self.metric.unit = unit.into();
+        self
+    }
+
+    fn mismatch(&mut self, method: &str) {
+        if self.error.is_none() {
+            self.error = Some(format!(
+                "`{method}` on {} metric {:?}",
+                kind_name(&self.metric.data),
+                self.metric.name
+            ));
+        }
+    }
+
+    /// Only applies to sums.
+    pub fn monotonic(mut self, monotonic: bool) -> Self {
+        match &mut self.metric.data {
+            MetricOneOfdata::sum(sum) => sum.is_monotonic = monotonic,
+            _ => self.mismatch("monotonic"),
+        }
+        self
+    }
+
+    /// Only applies to sums and histograms.
+    pub fn temporality(mut self, temporality: AggregationTemporality) -> Self {
+        match &mut self.metric.data {
+            MetricOneOfdata::sum(sum) => sum.aggregation_temporality = temporality,
+            MetricOneOfdata::histogram(histogram) => {
+                histogram.aggregation_temporality = temporality
+            }
+            MetricOneOfdata::exponential_histogram(histogram) => {
+                histogram.aggregation_temporality = temporality
+            }
+            _ => self.mismatch("temporality"),
+        }
+        self
+    }
+
+    /// The start time of the points added after this.
+    pub fn start_time(mut self, time: SystemTime) -> Self {
+        self.start_time_unix_nano = unix_nanos(time);
+        self
+    }
+
+    /// The time of the points added after this.
+    pub fn time(mut self, time: SystemTime) -> Self {
+        self.time_unix_nano = unix_nanos(time);
+        self
+    }
+
+    fn number_point(
+        mut self,
+        method: &str,
+        value: NumberDataPointOneOfvalue,
+        attributes: impl IntoIterator<Item = KeyValue<'a>>,
+    ) -> Self {
+        let data_point = NumberDataPoint {
+            attributes: attributes.into_iter().collect(),
+            start_time_unix_nano: self.start_time_unix_nano,
+            time_unix_nano: self.time_unix_nano,
+            exemplars: vec![],
+            flags: 0,
+            value,
+            ..Default::default()
+        };
+        match &mut self.metric.data {
+            MetricOneOfdata::gauge(gauge) => gauge.data_points.push(data_point),
+            MetricOneOfdata::sum(sum) => sum.data_points.push(data_point),
+            _ => self.mismatch(method),
+        }
+        self
+    }
+
+    /// Adds a gauge or sum point.
+    pub fn int_point(self, value: i64, attributes: impl IntoIterator<Item = KeyValue<'a>>) -> Self {
+        self.number_point(
+            "int_point",
+            NumberDataPointOneOfvalue::as_int(value),
+            attributes,
+        )
+    }
+
+    /// Adds a gauge or sum point.
+    pub fn double_point(
+        self,
+        value: f64,
+        attributes: impl IntoIterator<Item = KeyValue<'a>>,
+    ) -> Self {
+        self.number_point(
+            "double_point",
+            NumberDataPointOneOfvalue::as_double(value),
+            attributes,
+        )
+    }
+
+    /// Adds a histogram point, counting the values in `bucket_counts`, which
+    /// must have one more bucket than there are `explicit_bounds`.
+    pub fn histogram_point(
+        mut self,
+        explicit_bounds: Vec<f64>,
+        bucket_counts: Vec<u64>,
+        sum: f64,
+        attributes: impl IntoIterator<Item = KeyValue<'a>>,
+    ) -> Self {
+        let MetricOneOfdata::histogram(histogram) = &mut self.metric.data else {
+            self.mismatch("histogram_point");
+            return self;
+        };
+        histogram.data_points.push(HistogramDataPoint {
+            attributes: attributes.into_iter().collect(),
+            start_time_unix_nano: self.start_time_unix_nano,
+            time_unix_nano: self.time_unix_nano,
+            count: bucket_counts.iter().sum(),
+            sum,
+            bucket_counts: Cow::Owned(bucket_counts),
+            explicit_bounds: Cow::Owned(explicit_bounds),
+            exemplars: vec![],
+            flags: 0,
+            min: 0.0,
+            max: 0.0,
+            ..Default::default()
+        });
+        self
+    }
+
+    /// Adds an exponential histogram point of positive values, the first of
+    /// `bucket_counts` being bucket `offset` at `scale`.
+    pub fn exponential_histogram_point(
+        mut self,
+        scale: i32,
+        offset: i32,
+        bucket_counts: Vec<u64>,
+        sum: f64,
+        attributes: impl IntoIterator<Item = KeyValue<'a>>,
+    ) -> Self {
+        let MetricOneOfdata::exponential_histogram(histogram) = &mut self.metric.data else {
+            self.mismatch("exponential_histogram_point");
+            return self;
+        };
+        histogram.data_points.push(ExponentialHistogramDataPoint {
+            attributes: attributes.into_iter().collect(),
+            start_time_unix_nano: self.start_time_unix_nano,
+            time_unix_nano: self.time_unix_nano,
+            count: bucket_counts.iter().sum(),
+            sum,
+            scale,
+            zero_count: 0,
+            positive: Some(ExponentialHistogramDataPointBuckets {
+                offset,
+                bucket_counts,
+                ..Default::default()
+            }),
+            negative: None,
+            flags: 0,
+            exemplars: vec![],
+            min: 0.0,
+            max: 0.0,
+            zero_threshold: 0.0,
+            ..Default::default()
+        });
+        self
+    }
+
+    /// Adds a summary point with `(quantile, value)` pairs.
+    pub fn summary_point(
+        mut self,
+        count: u64,
+        sum: f64,
+        quantiles: impl IntoIterator<Item = (f64, f64)>,
+        attributes: impl IntoIterator<Item = KeyValue<'a>>,
+    ) -> Self {
+        let MetricOneOfdata::summary(summary) = &mut self.metric.data else {
+            self.mismatch("summary_point");
+            return self;
+        };
+        summary.data_points.push(SummaryDataPoint {
+            attributes: attributes.into_iter().collect(),
+            start_time_unix_nano: self.start_time_unix_nano,
+            time_unix_nano: self.time_unix_nano,
+            count,
+            sum,
+            quantile_values: quantiles
+                .into_iter()
+                .map(|(quantile, value)| SummaryDataPointValueAtQuantile {
+                    quantile,
+                    value,
+                    ..Default::default()
+                })
+                .collect(),
+            flags: 0,
+            ..Default::default()
+        });
+        self
+    }
+
+    /// Validates the metric, including the shape of histogram buckets and
+    /// summary quantiles.
+    pub fn build(self) -> Result<Metric<'a>, crate::Error> {
+        if let Some(error) = self.error {
+            return Err(crate::Error::Build(error));
+        }
+        let metric = self.metric;
+        let invalid =
+            |what: &str| crate::Error::Build(format!("{what} in metric {:?}", metric.name));
+        match &metric.data {
+            MetricOneOfdata::histogram(histogram) => {
+                for data_point in &histogram.data_points {
+                    let bounds = &data_point.explicit_bounds;
+                    if data_point.bucket_counts.len() != bounds.len() + 1 {
+                        return Err(invalid("bucket counts not matching the bounds"));
+                    }
+                    if bounds.windows(2).any(|pair| pair[0] >= pair[1]) {
+                        return Err(invalid("bounds not strictly increasing"));
+                    }
+                }
+            }
+            MetricOneOfdata::exponential_histogram(histogram) => {
+                for data_point in &histogram.data_points {
+                    if !(-10..=20).contains(&data_point.scale) {
+                        return Err(invalid("scale out of -10..=20"));
+                    }
+                }
+            }
+            MetricOneOfdata::summary(summary) => {
+                let quantiles = summary
+                    .data_points
+                    .iter()
+                    .flat_map(|data_point| &data_point.quantile_values);
+                for value_at_quantile in quantiles {
+                    if !(0.0..=1.0).contains(&value_at_quantile.quantile) {
+                        return Err(invalid("quantile out of 0..=1"));
+                    }
+                }
+            }
+            _ => {}
+        }
+        metric.validate()?;
+        Ok(metric)
+    }
+}
+
+fn kind_name(data: &MetricOneOfdata) -> &'static str {
+    match data {
+        MetricOneOfdata::gauge(_) => "a gauge",
+        MetricOneOfdata::sum(_) => "a sum",
+        MetricOneOfdata::histogram(_) => "a histogram",
+        MetricOneOfdata::exponential_histogram(_) => "an exponential histogram",
+        MetricOneOfdata::summary(_) => "a summary",
+        MetricOneOfdata::None => "an empty",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::opentelemetry::logs::ExportLogsServiceRequest;
+
+    #[test]
+    fn trace_request() {
+        let root = SpanBuilder::new("root")
+            .kind(SpanKind::SPAN_KIND_SERVER)
+            .start_time(UNIX_EPOCH + Duration::from_secs(10))
+            .duration(Duration::from_millis(5))
+            .attribute("http.route", "/users");
+        let child = SpanBuilder::new("child").child_of(&root);
+        let request = TraceRequestBuilder::new()
+            .span(root)
+            .resource([("service.name", "api").into()])
+            .scope("http", "1.0")
+            .span(child)
+            .build()
+            .unwrap();
+
+        assert_eq!(request.resource_spans.len(), 2);
+        let root = &request.resource_spans[0].scope_spans[0].spans[0];
+        assert_eq!(root.start_time_unix_nano, 10_000_000_000);
+        assert_eq!(root.end_time_unix_nano, 10_005_000_000);
+        assert_eq!(root.get_str("http.route"), Some("/users"));
+        assert!(root.parent_span_id.is_empty());
+
+        let resource_spans = &request.resource_spans[1];
+        let resource = resource_spans.resource.as_ref().unwrap();
+        assert_eq!(resource.get_str("service.name"), Some("api"));
+        let scope_spans = &resource_spans.scope_spans[0];
+        assert_eq!(scope_spans.scope.as_ref().unwrap().name, "http");
+        let child = &scope_spans.spans[0];
+        assert_eq!(child.trace_id, root.trace_id);
+        assert_eq!(child.parent_span_id, root.span_id);
+        assert_ne!(child.span_id, root.span_id);
+        assert_eq!(child.trace_id.len(), 16);
+        assert_eq!(child.span_id.len(), 8);
+    }
+
+    #[test]
+    fn invalid_span() {
+        let span = SpanBuilder::new("late")
+            .start_time(UNIX_EPOCH + Duration::from_secs(2))
+            .end_time(UNIX_EPOCH + Duration::from_secs(1));
+        assert!(matches!(
+            TraceRequestBuilder::new().span(span).build(),
+            Err(crate::Error::Build(_))
+        ));
+        assert!(SpanBuilder::new("").build().is_err());
+    }
+
+    #[test]
+    fn span_times_saturate() {
+        let span = SpanBuilder::new("long")
+            .start_time(UNIX_EPOCH + Duration::from_secs(1))
+            .duration(Duration::MAX)
+            .start_time(UNIX_EPOCH + Duration::from_secs(2))
+            .build()
+            .unwrap();
+        assert_eq!(span.start_time_unix_nano, 2_000_000_000);
+        assert_eq!(span.end_time_unix_nano, u64::MAX);
+    }
+
+    #[test]
+    fn logs_request() {
+        let span = SpanBuilder::new("span");
+        let request: ExportLogsServiceRequest = LogsRequestBuilder::new()
+            .scope("app", "")
+            .log_record(
+                LogRecordBuilder::new("disk full")
+                    .severity(SeverityNumber::SEVERITY_NUMBER_ERROR, "ERROR")
+                    .attribute("free", 0i64)
+                    .span(&span),
+            )
+            .log_record(LogRecordBuilder::new("hello"))
+            .build()
+            .unwrap();
+
+        let log_records = &request.resource_logs[0].scope_logs[0].log_records;
+        assert_eq!(log_records.len(), 2);
+        assert_eq!(log_records[0].body, Some(AnyValue::from("disk full")));
+        assert_eq!(log_records[0].span_id, span.build().unwrap().span_id);
+        assert_eq!(
+            log_records[1].severity_number,
+            SeverityNumber::SEVERITY_NUMBER_INFO
+        );
+        assert!(log_records[1].time_unix_nano > 0);
+        assert!(log_records[1].trace_id.is_empty());
+    }
+
+    #[test]
+    fn metrics_request() {
+        let request = MetricsRequestBuilder::new()
+            .metric(MetricBuilder::gauge("temperature").double_point(21.5, []))
+            .metric(
+                MetricBuilder::sum("requests")
+                    .monotonic(true)
+                    .int_point(3, [("code", 200i64).into()]),
+            )
+            .metric(MetricBuilder::histogram("latency").histogram_point(
+                vec![1.0, 2.0],
+                vec![1, 2, 3],
+                9.0,
+                [],
+            ))
+            .metric(
+                MetricBuilder::exponential_histogram("size").exponential_histogram_point(
+                    2,
+                    -1,
+                    vec![1, 1],
+                    4.0,
+                    [],
+                ),
+            )
+            .metric(MetricBuilder::summary("duration").summary_point(
+                4,
+                10.0,
+                [(0.5, 2.0), (1.0, 4.0)],
+                [],
+            ))
+            .build()
+            .unwrap();
+
+        let metrics = &request.resource_metrics[0].scope_metrics[0].metrics;
+        assert_eq!(metrics.len(), 5);
+        let MetricOneOfdata::gauge(gauge) = &metrics[0].data else {
+            panic!("expected a gauge");
+        };
+        assert_eq!(gauge.data_points[0].start_time_unix_nano, 0);
+        let MetricOneOfdata::sum(sum) = &metrics[1].data else {
+            panic!("expected a sum");
+        };
+        assert!(sum.is_monotonic);
+        assert_eq!(
+            sum.aggregation_temporality,
+            AggregationTemporality::AGGREGATION_TEMPORALITY_CUMULATIVE
+        );
+        assert_eq!(sum.data_points[0].get_i64("code"), Some(200));
+        assert!(sum.data_points[0].start_time_unix_nano > 0);
+        let MetricOneOfdata::histogram(histogram) = &metrics[2].data else {
+            panic!("expected a histogram");
+        };
+        assert_eq!(histogram.data_points[0].count, 6);
+        let MetricOneOfdata::exponential_histogram(histogram) = &metrics[3].data else {
+            panic!("expected an exponential histogram");
+        };
+        assert_eq!(histogram.data_points[0].count, 2);
+    }
+
+    #[test]
+    fn invalid_metrics() {
+        let build = |metric: MetricBuilder<'static>| metric.build().unwrap_err().to_string();
+
+        assert_eq!(
+            build(MetricBuilder::gauge("g").histogram_point(vec![], vec![1], 1.0, [])),
+            "cannot build: `histogram_point` on a gauge metric \"g\""
+        );
+        assert_eq!(
+            build(MetricBuilder::summary("s").monotonic(true)),
+            "cannot build: `monotonic` on a summary metric \"s\""
+        );
+        assert_eq!(
+            build(MetricBuilder::histogram("h").histogram_point(vec![1.0], vec![1], 1.0, [])),
+            "cannot build: bucket counts not matching the bounds in metric \"h\""
+        );
+        assert_eq!(
+            build(MetricBuilder::histogram("h").histogram_point(
+                vec![2.0, 1.0],
+                vec![1, 1, 1],
+                1.0,
+                []
+            )),
+            "cannot build: bounds not strictly increasing in metric \"h\""
+        );
+        assert_eq!(
+            build(MetricBuilder::summary("s").summary_point(1, 1.0, [(2.0, 1.0)], [])),
+            "cannot build: quantile out of 0..=1 in metric \"s\""
+        );
+        assert!(MetricBuilder::gauge("").build().is_err());
+    }
+}
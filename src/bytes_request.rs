@@ -0,0 +1,184 @@
+// Decoding from `Bytes` without copying: the request borrows from the buffer
+// the `Bytes` point to, which does not move when they do and stays alive as
+// long as they do. The request is stored with a `'static` lifetime but only
+// ever handed out with one bounded by a borrow of its `BytesRequest`.
+
+use bytes::Bytes;
+
+use crate::opentelemetry::logs::ExportLogsServiceRequest;
+use crate::opentelemetry::metrics::ExportMetricsServiceRequest;
+use crate::opentelemetry::profiles::ExportProfilesServiceRequest;
+use crate::opentelemetry::trace::ExportTraceServiceRequest;
+use crate::IntoOwned;
+
+/// An export request decoded from [`Bytes`], borrowing its strings and byte
+/// fields from them instead of copying.
+///
+/// The request is reached through `get`. Moving or cloning a `BytesRequest`
+/// is cheap and keeps sharing the buffer; use `into_owned` to copy the
+/// request out when the buffer should be released.
+#[derive(Debug, Clone)]
+pub struct BytesRequest<M> {
+    // Declared first so it is dropped before the buffer it borrows from
+    request: M,
+    bytes: Bytes,
+}
+
+impl<M> BytesRequest<M> {
+    /// The buffer the request was decoded from.
+    pub fn bytes(&self) -> &Bytes {
+        &self.bytes
+    }
+}
+
+// SAFETY: callers must not let anything borrowed from the returned slice
+// outlive `bytes`, nor hand it out with a lifetime that is not bounded by a
+// borrow of the `BytesRequest` owning `bytes`
+unsafe fn extend(bytes: &Bytes) -> &'static [u8] {
+    std::slice::from_raw_parts(bytes.as_ptr(), bytes.len())
+}
+
+impl TryFrom<Bytes> for BytesRequest<ExportMetricsServiceRequest<'static>> {
+    type Error = crate::Error;
+
+    fn try_from(bytes: Bytes) -> Result<Self, Self::Error> {
+        // SAFETY: the request is stored next to `bytes` and only lent out by `get`
+        let request = ExportMetricsServiceRequest::try_from(unsafe { extend(&bytes) })?;
+        Ok(BytesRequest { request, bytes })
+    }
+}
+
+impl BytesRequest<ExportMetricsServiceRequest<'static>> {
+    pub fn get(&self) -> &ExportMetricsServiceRequest<'_> {
+        &self.request
+    }
+
+    /// Copies the request out of the buffer.
+    pub fn into_owned(self) -> ExportMetricsServiceRequest<'static> {
+        let BytesRequest { request, bytes } = self;
+        let request = request.into_owned();
+        drop(bytes);
+        request
+    }
+}
+
+impl TryFrom<Bytes> for BytesRequest<ExportLogsServiceRequest<'static>> {
+    type Error = crate::Error;
+
+    fn try_from(bytes: Bytes) -> Result<Self, Self::Error> {
+        // SAFETY: the request is stored next to `bytes` and only lent out by `get`
+        let request = ExportLogsServiceRequest::try_from(unsafe { extend(&bytes) })?;
+        Ok(BytesRequest { request, bytes })
+    }
+}
+
+impl BytesRequest<ExportLogsServiceRequest<'static>> {
+    pub fn get(&self) -> &ExportLogsServiceRequest<'_> {
+        &self.request
+    }
+
+    /// Copies the request out of the buffer.
+    pub fn into_owned(self) -> ExportLogsServiceRequest<'static> {
+        let BytesRequest { request, bytes } = self;
+        let request = request.into_owned();
+        drop(bytes);
+        request
+    }
+}
+
+impl TryFrom<Bytes> for BytesRequest<ExportTraceServiceRequest<'static>> {
+    type Error = crate::Error;
+
+    fn try_from(bytes: Bytes) -> Result<Self, Self::Error> {
+        // SAFETY: the request is stored next to `bytes` and only lent out by `get`
+        let request = ExportTraceServiceRequest::try_from(unsafe { extend(&bytes) })?;
+        Ok(BytesRequest { request, bytes })
+    }
+}
+
+impl BytesRequest<ExportTraceServiceRequest<'static>> {
+    pub fn get(&self) -> &ExportTraceServiceRequest<'_> {
+        &self.request
+    }
+
+    /// Copies the request out of the buffer.
+    pub fn into_owned(self) -> ExportTraceServiceRequest<'static> {
+        let BytesRequest { request, bytes } = self;
+        let request = request.into_owned();
+        drop(bytes);
+        request
+    }
+}
+
+impl TryFrom<Bytes> for BytesRequest<ExportProfilesServiceRequest<'static>> {
+    type Error = crate::Error;
+
+    fn try_from(bytes: Bytes) -> Result<Self, Self::Error> {
+        // SAFETY: the request is stored next to `bytes` and only lent out by `get`
+        let request = ExportProfilesServiceRequest::try_from(unsafe { extend(&bytes) })?;
+        Ok(BytesRequest { request, bytes })
+    }
+}
+
+impl BytesRequest<ExportProfilesServiceRequest<'static>> {
+    pub fn get(&self) -> &ExportProfilesServiceRequest<'_> {
+        &self.request
+    }
+
+    /// Copies the request out of the buffer.
+    pub fn into_owned(self) -> ExportProfilesServiceRequest<'static> {
+        let BytesRequest { request, bytes } = self;
+        let request = request.into_owned();
+        drop(bytes);
+        request
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::borrow::Cow;
+
+    use super::*;
+    use crate::opentelemetry::trace::{ResourceSpans, ScopeSpans, Span};
+
+    #[test]
+    fn borrows_from_bytes() {
+        let request = ExportTraceServiceRequest {
+            resource_spans: vec![ResourceSpans {
+                scope_spans: vec![ScopeSpans {
+                    spans: vec![Span {
+                        name: Cow::from("span"),
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        let bytes = Bytes::from(request.encode_to_vec());
+
+        let decoded = BytesRequest::<ExportTraceServiceRequest>::try_from(bytes.clone()).unwrap();
+        drop(bytes);
+        let name = &decoded.get().resource_spans[0].scope_spans[0].spans[0].name;
+        let Cow::Borrowed(name) = name else {
+            panic!("expected the name to be borrowed");
+        };
+        assert!(decoded.bytes().as_ptr_range().contains(&name.as_ptr()));
+
+        let cloned = decoded.clone();
+        drop(decoded);
+        assert_eq!(cloned.get(), &request);
+        let owned = cloned.into_owned();
+        assert!(matches!(
+            owned.resource_spans[0].scope_spans[0].spans[0].name,
+            Cow::Owned(_)
+        ));
+        assert_eq!(owned, request);
+
+        assert!(
+            BytesRequest::<ExportTraceServiceRequest>::try_from(Bytes::from_static(b"\xff"))
+                .is_err()
+        );
+    }
+}
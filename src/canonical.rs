@@ -0,0 +1,428 @@
+// Canonical encoding sorts every attribute list by key, including the ones
+// nested in `kvlistValue`s, so that equal content encodes to equal bytes.
+// Duplicate keys are ordered by their encoded value, and the key lists of
+// entity references are sorted too. The output is deterministic for a given
+// version of the bindings only: it may change when the protos are upgraded.
+
+use quick_protobuf::MessageWrite;
+
+use crate::opentelemetry::common::{
+    AnyValue, AnyValueOneOfvalue, EntityRef, InstrumentationScope, KeyValue, KeyValueList, Resource,
+};
+use crate::opentelemetry::logs::{LogRecord, ResourceLogs, ScopeLogs};
+use crate::opentelemetry::metrics::{
+    Exemplar, ExponentialHistogramDataPoint, HistogramDataPoint, Metric, MetricOneOfdata,
+    NumberDataPoint, ResourceMetrics, ScopeMetrics, SummaryDataPoint,
+};
+use crate::opentelemetry::trace::{ResourceSpans, ScopeSpans, Span, SpanEvent, SpanLink};
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+const FNV_PRIME: u64 = 0x100000001b3;
+
+trait Canonicalize {
+    fn canonicalize(&mut self);
+}
+
+// 64-bit FNV-1a, which unlike the std hashers is stable across releases
+fn hash(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(FNV_OFFSET_BASIS, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(FNV_PRIME)
+    })
+}
+
+fn canonical_encoding<M: Canonicalize + Clone + MessageWrite>(message: &M) -> Vec<u8> {
+    let mut message = message.clone();
+    message.canonicalize();
+    crate::encode::encode_to_vec(&message)
+}
+
+/// Hashes a set of attributes independently of their order, e.g. to
+/// identify a metric series.
+pub fn attributes_content_hash(attributes: &[KeyValue]) -> u64 {
+    let list = KeyValueList {
+        values: attributes.to_vec(),
+        ..Default::default()
+    };
+    hash(&canonical_encoding(&list))
+}
+
+fn sort_attributes(attributes: &mut [KeyValue]) {
+    for attribute in attributes.iter_mut() {
+        attribute.canonicalize();
+    }
+    attributes.sort_by(|a, b| {
+        a.key.cmp(&b.key).then_with(|| {
+            let value =
+                |attribute: &KeyValue| attribute.value.as_ref().map(crate::encode::encode_to_vec);
+            value(a).cmp(&value(b))
+        })
+    });
+}
+
+impl<T: Canonicalize> Canonicalize for Option<T> {
+    fn canonicalize(&mut self) {
+        if let Some(value) = self {
+            value.canonicalize();
+        }
+    }
+}
+
+impl<T: Canonicalize> Canonicalize for Vec<T> {
+    fn canonicalize(&mut self) {
+        for value in self {
+            value.canonicalize();
+        }
+    }
+}
+
+impl Canonicalize for KeyValue<'_> {
+    fn canonicalize(&mut self) {
+        self.value.canonicalize();
+    }
+}
+
+impl Canonicalize for KeyValueList<'_> {
+    fn canonicalize(&mut self) {
+        sort_attributes(&mut self.values);
+    }
+}
+
+impl Canonicalize for AnyValue<'_> {
+    fn canonicalize(&mut self) {
+        match &mut self.value {
+            AnyValueOneOfvalue::kvlist_value(kvlist) => kvlist.canonicalize(),
+            AnyValueOneOfvalue::array_value(array) => array.values.canonicalize(),
+            _ => {}
+        }
+    }
+}
+
+impl Canonicalize for Resource<'_> {
+    fn canonicalize(&mut self) {
+        sort_attributes(&mut self.attributes);
+        self.entity_refs.canonicalize();
+    }
+}
+
+impl Canonicalize for EntityRef<'_> {
+    fn canonicalize(&mut self) {
+        self.id_keys.sort();
+        self.description_keys.sort();
+    }
+}
+
+impl Canonicalize for InstrumentationScope<'_> {
+    fn canonicalize(&mut self) {
+        sort_attributes(&mut self.attributes);
+    }
+}
+
+impl Canonicalize for ResourceLogs<'_> {
+    fn canonicalize(&mut self) {
+        self.resource.canonicalize();
+        self.scope_logs.canonicalize();
+    }
+}
+
+impl Canonicalize for ScopeLogs<'_> {
+    fn canonicalize(&mut self) {
+        self.scope.canonicalize();
+        self.log_records.canonicalize();
+    }
+}
+
+impl Canonicalize for LogRecord<'_> {
+    fn canonicalize(&mut self) {
+        self.body.canonicalize();
+        sort_attributes(&mut self.attributes);
+    }
+}
+
+impl Canonicalize for ResourceMetrics<'_> {
+    fn canonicalize(&mut self) {
+        self.resource.canonicalize();
+        self.scope_metrics.canonicalize();
+    }
+}
+
+impl Canonicalize for ScopeMetrics<'_> {
+    fn canonicalize(&mut self) {
+        self.scope.canonicalize();
+        self.metrics.canonicalize();
+    }
+}
+
+impl Canonicalize for Metric<'_> {
+    fn canonicalize(&mut self) {
+        sort_attributes(&mut self.metadata);
+        match &mut self.data {
+            MetricOneOfdata::gauge(gauge) => gauge.data_points.canonicalize(),
+            MetricOneOfdata::sum(sum) => sum.data_points.canonicalize(),
+            MetricOneOfdata::histogram(histogram) => histogram.data_points.canonicalize(),
+            MetricOneOfdata::exponential_histogram(histogram) => {
+                histogram.data_points.canonicalize()
+            }
+            MetricOneOfdata::summary(summary) => summary.data_points.canonicalize(),
+            MetricOneOfdata::None => {}
+        }
+    }
+}
+
+impl Canonicalize for NumberDataPoint<'_> {
+    fn canonicalize(&mut self) {
+        sort_attributes(&mut self.attributes);
+        self.exemplars.canonicalize();
+    }
+}
+
+impl Canonicalize for HistogramDataPoint<'_> {
+    fn canonicalize(&mut self) {
+        sort_attributes(&mut self.attributes);
+        self.exemplars.canonicalize();
+    }
+}
+
+impl Canonicalize for ExponentialHistogramDataPoint<'_> {
+    fn canonicalize(&mut self) {
+        sort_attributes(&mut self.attributes);
+        self.exemplars.canonicalize();
+    }
+}
+
+impl Canonicalize for SummaryDataPoint<'_> {
+    fn canonicalize(&mut self) {
+        sort_attributes(&mut self.attributes);
+    }
+}
+
+impl Canonicalize for Exemplar<'_> {
+    fn canonicalize(&mut self) {
+        sort_attributes(&mut self.filtered_attributes);
+    }
+}
+
+impl Canonicalize for ResourceSpans<'_> {
+    fn canonicalize(&mut self) {
+        self.resource.canonicalize();
+        self.scope_spans.canonicalize();
+    }
+}
+
+impl Canonicalize for ScopeSpans<'_> {
+    fn canonicalize(&mut self) {
+        self.scope.canonicalize();
+        self.spans.canonicalize();
+    }
+}
+
+impl Canonicalize for Span<'_> {
+    fn canonicalize(&mut self) {
+        sort_attributes(&mut self.attributes);
+        self.events.canonicalize();
+        self.links.canonicalize();
+    }
+}
+
+impl Canonicalize for SpanEvent<'_> {
+    fn canonicalize(&mut self) {
+        sort_attributes(&mut self.attributes);
+    }
+}
+
+impl Canonicalize for SpanLink<'_> {
+    fn canonicalize(&mut self) {
+        sort_attributes(&mut self.attributes);
+    }
+}
+
+impl ResourceLogs<'_> {
+    /// Encodes the resource logs with every attribute list sorted by key.
+    pub fn canonical_encoding(&self) -> Vec<u8> {
+        canonical_encoding(self)
+    }
+
+    /// Hash of the canonical encoding, independent of attribute order.
+    pub fn content_hash(&self) -> u64 {
+        hash(&self.canonical_encoding())
+    }
+}
+
+impl LogRecord<'_> {
+    /// Encodes the log record with every attribute list sorted by key.
+    pub fn canonical_encoding(&self) -> Vec<u8> {
+        canonical_encoding(self)
+    }
+
+    /// Hash of the canonical encoding, independent of attribute order.
+    pub fn content_hash(&self) -> u64 {
+        hash(&self.canonical_encoding())
+    }
+}
+
+impl ResourceMetrics<'_> {
+    /// Encodes the resource metrics with every attribute list sorted by key.
+    pub fn canonical_encoding(&self) -> Vec<u8> {
+        canonical_encoding(self)
+    }
+
+    /// Hash of the canonical encoding, independent of attribute order.
+    pub fn content_hash(&self) -> u64 {
+        hash(&self.canonical_encoding())
+    }
+}
+
+impl ResourceSpans<'_> {
+    /// Encodes the resource spans with every attribute list sorted by key.
+    pub fn canonical_encoding(&self) -> Vec<u8> {
+        canonical_encoding(self)
+    }
+
+    /// Hash of the canonical encoding, independent of attribute order.
+    pub fn content_hash(&self) -> u64 {
+        hash(&self.canonical_encoding())
+    }
+}
+
+impl Span<'_> {
+    /// Encodes the span with every attribute list sorted by key.
+    pub fn canonical_encoding(&self) -> Vec<u8> {
+        canonical_encoding(self)
+    }
+
+    /// Hash of the canonical encoding, independent of attribute order.
+    pub fn content_hash(&self) -> u64 {
+        hash(&self.canonical_encoding())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{attributes_content_hash, hash};
+    use crate::opentelemetry::common::{
+        AnyValue, AnyValueOneOfvalue, EntityRef, KeyValue, KeyValueList, Resource,
+    };
+    use crate::opentelemetry::metrics::{Metric, ResourceMetrics, ScopeMetrics};
+    use crate::opentelemetry::trace::{ResourceSpans, ScopeSpans, Span};
+    use std::borrow::Cow;
+
+    fn attribute(key: &'static str, value: AnyValueOneOfvalue<'static>) -> KeyValue<'static> {
+        KeyValue {
+            key: Cow::from(key),
+            value: Some(AnyValue {
+                value,
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+
+    fn int(key: &'static str, value: i64) -> KeyValue<'static> {
+        attribute(key, AnyValueOneOfvalue::int_value(value))
+    }
+
+    #[test]
+    fn fnv_hash_is_stable() {
+        assert_eq!(hash(b""), 0xcbf29ce484222325);
+        assert_eq!(hash(b"a"), 0xaf63dc4c8601ec8c);
+    }
+
+    #[test]
+    fn attributes_content_hash_ignores_order() {
+        let a = vec![int("a", 1), int("b", 2)];
+        let b = vec![int("b", 2), int("a", 1)];
+        let c = vec![int("a", 2), int("b", 1)];
+
+        assert_eq!(attributes_content_hash(&a), attributes_content_hash(&b));
+        assert_ne!(attributes_content_hash(&a), attributes_content_hash(&c));
+
+        // Duplicate keys are ordered by value
+        let a = vec![int("a", 1), int("a", 2)];
+        let b = vec![int("a", 2), int("a", 1)];
+        assert_eq!(attributes_content_hash(&a), attributes_content_hash(&b));
+    }
+
+    #[test]
+    fn content_hash_sorts_entity_ref_keys() {
+        let resource = |id_keys: [&'static str; 2]| ResourceSpans {
+            resource: Some(Resource {
+                entity_refs: vec![EntityRef {
+                    type_pb: Cow::from("service"),
+                    id_keys: id_keys.map(Cow::from).to_vec(),
+                    description_keys: vec![Cow::from("b"), Cow::from("a")],
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        let a = resource(["service.name", "service.namespace"]);
+        let b = resource(["service.namespace", "service.name"]);
+        assert_ne!(a, b);
+        assert_eq!(a.canonical_encoding(), b.canonical_encoding());
+        assert_eq!(a.content_hash(), b.content_hash());
+    }
+
+    #[test]
+    fn content_hash_sorts_nested_attributes() {
+        let span = |attributes: Vec<KeyValue<'static>>| Span {
+            name: Cow::from("span"),
+            attributes,
+            ..Default::default()
+        };
+        let map = |values| {
+            attribute(
+                "map",
+                AnyValueOneOfvalue::kvlist_value(KeyValueList {
+                    values,
+                    ..Default::default()
+                }),
+            )
+        };
+
+        let a = span(vec![int("x", 1), map(vec![int("a", 1), int("b", 2)])]);
+        let b = span(vec![map(vec![int("b", 2), int("a", 1)]), int("x", 1)]);
+        assert_ne!(a, b);
+        assert_eq!(a.canonical_encoding(), b.canonical_encoding());
+        assert_eq!(a.content_hash(), b.content_hash());
+
+        let resource_spans = |span| ResourceSpans {
+            scope_spans: vec![ScopeSpans {
+                spans: vec![span],
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        let c = span(vec![int("x", 2), map(vec![int("a", 1), int("b", 2)])]);
+        assert_eq!(
+            resource_spans(a.clone()).content_hash(),
+            resource_spans(b).content_hash()
+        );
+        assert_ne!(
+            resource_spans(a).content_hash(),
+            resource_spans(c).content_hash()
+        );
+    }
+
+    #[test]
+    fn content_hash_sorts_metric_metadata() {
+        let metric = |metadata| ResourceMetrics {
+            scope_metrics: vec![ScopeMetrics {
+                metrics: vec![Metric {
+                    name: Cow::from("metric"),
+                    metadata,
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        let a = metric(vec![int("a", 1), int("b", 2)]);
+        let b = metric(vec![int("b", 2), int("a", 1)]);
+        let c = metric(vec![int("a", 2), int("b", 1)]);
+        assert_eq!(a.canonical_encoding(), b.canonical_encoding());
+        assert_eq!(a.content_hash(), b.content_hash());
+        assert_ne!(a.content_hash(), c.content_hash());
+    }
+}
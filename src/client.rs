@@ -0,0 +1,919 @@
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::io::Write;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use bytes::{Bytes, BytesMut};
+use http_body_util::{BodyExt, Collected, Full, LengthLimitError, Limited};
+use hyper::body::Incoming;
+use hyper::client::conn::{http1, http2};
+use hyper::header::{self, HeaderMap, HeaderValue};
+use hyper::{Request, Response, StatusCode};
+use hyper_util::rt::{TokioExecutor, TokioIo};
+use quick_protobuf::{BytesReader, MessageRead, MessageWrite};
+use tokio::net::TcpStream;
+use url::{Host, Url};
+
+use crate::grpc::{write_compressed_grpc_frame, GrpcCode, GrpcStatus, GRPC_CONTENT_TYPE};
+use crate::http::{decode_rpc_status, Signal};
+use crate::opentelemetry::logs::{ExportLogsServiceRequest, ExportLogsServiceResponse};
+use crate::opentelemetry::metrics::{ExportMetricsServiceRequest, ExportMetricsServiceResponse};
+use crate::opentelemetry::trace::{ExportTraceServiceRequest, ExportTraceServiceResponse};
+use crate::{write_grpc_frame, GrpcFrames, PROTOBUF_CONTENT_TYPE};
+
+// Cap on a response message, compressed or not, as gRPC's default limit on
+// received messages
+const MAX_RESPONSE_BYTES: usize = 4 * 1024 * 1024;
+
+// Idle HTTP/1.1 connections kept open for later requests
+const MAX_IDLE_CONNECTIONS: usize = 8;
+
+/// The OTLP transport an [`Exporter`] uses.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Protocol {
+    /// Protobuf over HTTP/1.1, receivers usually listen on port 4318.
+    #[default]
+    HttpProtobuf,
+    /// gRPC over cleartext HTTP/2, receivers usually listen on port 4317.
+    Grpc,
+}
+
+/// Configuration of an [`Exporter`].
+#[derive(Debug, Clone)]
+pub struct ExporterConfig {
+    /// Base URL of the receiver, the signal path (`/v1/traces`, ...) or
+    /// gRPC method is appended to it. Only `http` is supported, see
+    /// [`Exporter`].
+    pub endpoint: String,
+    pub protocol: Protocol,
+    /// Extra headers sent with every request, e.g. for authentication.
+    pub headers: Vec<(String, String)>,
+    /// Whether to gzip the request bodies.
+    pub compression: bool,
+    /// Time limit of each attempt.
+    pub timeout: Duration,
+    /// Number of retries after the first attempt.
+    pub max_retries: u32,
+    /// Delay before the first retry, doubled for each following one.
+    pub initial_backoff: Duration,
+    /// Longest delay between two attempts, `Retry-After` included.
+    pub max_backoff: Duration,
+}
+
+impl Default for ExporterConfig {
+    fn default() -> Self {
+        ExporterConfig {
+            endpoint: "http://localhost:4318".to_string(),
+            protocol: Protocol::HttpProtobuf,
+            headers: vec![],
+            compression: true,
+            timeout: Duration::from_secs(10),
+            max_retries: 5,
+            initial_backoff: Duration::from_secs(1),
+            max_backoff: Duration::from_secs(30),
+        }
+    }
+}
+
+/// The part of an export the receiver rejected.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartialSuccess {
+    /// Number of spans, log records or data points that were rejected.
+    pub rejected: i64,
+    pub error_message: String,
+}
+
+/// An OTLP exporter, sending protobuf-encoded requests over HTTP or gRPC.
+///
+/// Requests failing with 429, 502, 503 or 504, with one of the gRPC codes
+/// the OTLP spec marks as retryable, or with a transport error, are retried
+/// with exponential backoff and jitter, or after the delay in `Retry-After`
+/// when the receiver sends one, as seconds or an HTTP-date. Neither waits
+/// longer than `max_backoff`.
+///
+/// Connections are kept open and reused by later exports, including those
+/// of clones of the exporter. TLS is out of scope: endpoints must be
+/// `http`, so a receiver that only accepts `https` needs a TLS-terminating
+/// proxy in front of it.
+#[derive(Debug, Clone)]
+pub struct Exporter {
+    config: ExporterConfig,
+    endpoint: Url,
+    headers: Vec<(header::HeaderName, HeaderValue)>,
+    connections: Arc<Connections>,
+}
+
+// HTTP/1.1 connections carry one request at a time, so the idle ones are
+// kept in a list; a single HTTP/2 connection multiplexes every request.
+#[derive(Debug, Default)]
+struct Connections {
+    http1: Mutex<Vec<http1::SendRequest<Full<Bytes>>>>,
+    http2: Mutex<Option<http2::SendRequest<Full<Bytes>>>>,
+}
+
+impl Exporter {
+    pub fn new(config: ExporterConfig) -> Result<Self, crate::Error> {
+        let mut endpoint = Url::parse(&config.endpoint)?;
+        if endpoint.scheme() != "http" || endpoint.host_str().is_none() {
+            return Err(crate::Error::InvalidEndpoint(config.endpoint.clone()));
+        }
+        // Keep any path prefix, e.g. a collector behind a reverse proxy
+        if !endpoint.path().ends_with('/') {
+            endpoint.set_path(&format!("{}/", endpoint.path()));
+        }
+
+        let headers = config
+            .headers
+            .iter()
+            .map(|(name, value)| {
+                Ok((
+                    header::HeaderName::try_from(name.as_str())
+                        .map_err(|e| crate::Error::Other(e.to_string()))?,
+                    HeaderValue::try_from(value.as_str())
+                        .map_err(|e| crate::Error::Other(e.to_string()))?,
+                ))
+            })
+            .collect::<Result<_, crate::Error>>()?;
+
+        Ok(Exporter {
+            config,
+            endpoint,
+            headers,
+            connections: Arc::default(),
+        })
+    }
+
+    pub async fn export_traces(
+        &self,
+        request: &ExportTraceServiceRequest<'_>,
+    ) -> Result<Option<PartialSuccess>, crate::Error> {
+        let body = self.export(Signal::Traces, request).await?;
+        let response = decode::<ExportTraceServiceResponse>(&body)?;
+        Ok(response
+            .partial_success
+            .map(|partial_success| PartialSuccess {
+                rejected: partial_success.rejected_spans,
+                error_message: partial_success.error_message.into_owned(),
+            }))
+    }
+
+    pub async fn export_logs(
+        &self,
+        request: &ExportLogsServiceRequest<'_>,
+    ) -> Result<Option<PartialSuccess>, crate::Error> {
+        let body = self.export(Signal::Logs, request).await?;
+        let response = decode::<ExportLogsServiceResponse>(&body)?;
+        Ok(response
+            .partial_success
+            .map(|partial_success| PartialSuccess {
+                rejected: partial_success.rejected_log_records,
+                error_message: partial_success.error_message.into_owned(),
+            }))
+    }
+
+    pub async fn export_metrics(
+        &self,
+        request: &ExportMetricsServiceRequest<'_>,
+    ) -> Result<Option<PartialSuccess>, crate::Error> {
+        let body = self.export(Signal::Metrics, request).await?;
+        let response = decode::<ExportMetricsServiceResponse>(&body)?;
+        Ok(response
+            .partial_success
+            .map(|partial_success| PartialSuccess {
+                rejected: partial_success.rejected_data_points,
+                error_message: partial_success.error_message.into_owned(),
+            }))
+    }
+
+    async fn export<M: MessageWrite>(
+        &self,
+        signal: Signal,
+        request: &M,
+    ) -> Result<Bytes, crate::Error> {
+        let path = match (self.config.protocol, signal) {
+            (Protocol::HttpProtobuf, Signal::Traces) => "v1/traces",
+            (Protocol::HttpProtobuf, Signal::Logs) => "v1/logs",
+            (Protocol::HttpProtobuf, Signal::Metrics) => "v1/metrics",
+            (Protocol::Grpc, Signal::Traces) => {
+                "opentelemetry.proto.collector.trace.v1.TraceService/Export"
+            }
+            (Protocol::Grpc, Signal::Logs) => {
+                "opentelemetry.proto.collector.logs.v1.LogsService/Export"
+            }
+            (Protocol::Grpc, Signal::Metrics) => {
+                "opentelemetry.proto.collector.metrics.v1.MetricsService/Export"
+            }
+        };
+        let url = self.endpoint.join(path)?;
+        let mut body = crate::encode::encode_to_vec(request);
+        if self.config.compression {
+            let mut encoder =
+                flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+            body = encoder
+                .write_all(&body)
+                .and_then(|()| encoder.finish())
+                .expect("writing to a Vec cannot fail");
+        }
+        let body = match self.config.protocol {
+            Protocol::HttpProtobuf => Bytes::from(body),
+            Protocol::Grpc => {
+                if u32::try_from(body.len()).is_err() {
+                    return Err(crate::Error::MessageTooLarge {
+                        size: body.len(),
+                        limit: u32::MAX as usize,
+                    });
+                }
+                let mut frame = BytesMut::new();
+                if self.config.compression {
+                    write_compressed_grpc_frame(&body, &mut frame);
+                } else {
+                    write_grpc_frame(&body, &mut frame);
+                }
+                frame.freeze()
+            }
+        };
+
+        let mut attempt = 0;
+        loop {
+            let send = async {
+                match self.config.protocol {
+                    Protocol::HttpProtobuf => self.send(&url, body.clone()).await,
+                    Protocol::Grpc => self.send_grpc(&url, body.clone()).await,
+                }
+            };
+            let result = tokio::time::timeout(self.config.timeout, send)
+                .await
+                .unwrap_or_else(|_| Err(Attempt::Retry(None, "request timed out".to_string())));
+            let (retry_after, message) = match result {
+                Ok(body) => return Ok(body),
+                Err(Attempt::Fail(e)) => return Err(e),
+                Err(Attempt::Retry(retry_after, message)) => (retry_after, message),
+            };
+
+            if attempt == self.config.max_retries {
+                return Err(crate::Error::ExportFailed(message));
+            }
+            let delay = retry_after
+                .map(|delay| delay.min(self.config.max_backoff))
+                .unwrap_or_else(|| self.backoff(attempt));
+            tokio::time::sleep(delay).await;
+            attempt += 1;
+        }
+    }
+
+    async fn send(&self, url: &Url, body: Bytes) -> Result<Bytes, Attempt> {
+        // Keeps the brackets of IPv6 addresses, as the Host header needs
+        let host = url.host_str().expect("checked in Exporter::new");
+        let authority = match url.port() {
+            Some(port) => format!("{host}:{port}"),
+            None => host.to_string(),
+        };
+        let mut request = Request::post(&url[url::Position::BeforePath..])
+            .header(header::HOST, authority)
+            .header(header::CONTENT_TYPE, PROTOBUF_CONTENT_TYPE)
+            .body(Full::new(body))
+            .map_err(|e| Attempt::Fail(crate::Error::Other(e.to_string())))?;
+        if self.config.compression {
+            request
+                .headers_mut()
+                .insert(header::CONTENT_ENCODING, HeaderValue::from_static("gzip"));
+        }
+        for (name, value) in &self.headers {
+            request.headers_mut().insert(name, value.clone());
+        }
+
+        let idle = self.connections.http1.lock().unwrap().pop();
+        let (sender, response) = match idle {
+            Some(mut sender) => match sender.try_send_request(request).await {
+                Ok(response) => (sender, response),
+                // The receiver closed the idle connection before the request
+                // went out
+                Err(mut e) => match e.take_message() {
+                    Some(request) => send_http1(url, request).await?,
+                    None => return Err(Attempt::Retry(None, e.into_error().to_string())),
+                },
+            },
+            None => send_http1(url, request).await?,
+        };
+        let status = response.status();
+        let retry_after = response
+            .headers()
+            .get(header::RETRY_AFTER)
+            .and_then(|value| parse_retry_after(value.to_str().ok()?, SystemTime::now()));
+        let body = collect(response.into_body()).await?.to_bytes();
+        let mut idle = self.connections.http1.lock().unwrap();
+        if !sender.is_closed() && idle.len() < MAX_IDLE_CONNECTIONS {
+            idle.push(sender);
+        }
+        drop(idle);
+
+        if status.is_success() {
+            return Ok(body);
+        }
+        let message = match decode_rpc_status(&body) {
+            Ok(rpc_status) if !rpc_status.message.is_empty() => {
+                format!("{status}: {}", rpc_status.message)
+            }
+            _ => status.to_string(),
+        };
+        match status {
+            StatusCode::TOO_MANY_REQUESTS
+            | StatusCode::BAD_GATEWAY
+            | StatusCode::SERVICE_UNAVAILABLE
+            | StatusCode::GATEWAY_TIMEOUT => Err(Attempt::Retry(retry_after, message)),
+            _ => Err(Attempt::Fail(crate::Error::ExportFailed(message))),
+        }
+    }
+
+    async fn send_grpc(&self, url: &Url, body: Bytes) -> Result<Bytes, Attempt> {
+        let timeout = format!("{}m", self.config.timeout.as_millis().clamp(1, 99_999_999));
+        let mut request = Request::post(url.as_str())
+            .header(header::CONTENT_TYPE, GRPC_CONTENT_TYPE)
+            .header(header::TE, "trailers")
+            .header("grpc-accept-encoding", "gzip")
+            .header("grpc-timeout", timeout)
+            .body(Full::new(body))
+            .map_err(|e| Attempt::Fail(crate::Error::Other(e.to_string())))?;
+        if self.config.compression {
+            request
+                .headers_mut()
+                .insert("grpc-encoding", HeaderValue::from_static("gzip"));
+        }
+        for (name, value) in &self.headers {
+            request.headers_mut().insert(name, value.clone());
+        }
+
+        let open = self.connections.http2.lock().unwrap().clone();
+        let response = match open.filter(|sender| !sender.is_closed()) {
+            Some(mut sender) => match sender.try_send_request(request).await {
+                Ok(response) => response,
+                Err(mut e) => match e.take_message() {
+                    Some(request) => self.send_http2(url, request).await?,
+                    None => return Err(Attempt::Retry(None, e.into_error().to_string())),
+                },
+            },
+            None => self.send_http2(url, request).await?,
+        };
+        let (parts, body) = response.into_parts();
+        if parts.status != StatusCode::OK {
+            let message = format!("HTTP status {}", parts.status);
+            return match parts.status {
+                StatusCode::TOO_MANY_REQUESTS
+                | StatusCode::BAD_GATEWAY
+                | StatusCode::SERVICE_UNAVAILABLE
+                | StatusCode::GATEWAY_TIMEOUT => Err(Attempt::Retry(None, message)),
+                _ => Err(Attempt::Fail(crate::Error::ExportFailed(message))),
+            };
+        }
+        let body = collect(body).await?;
+        // A trailers-only response carries the status in its headers
+        let trailers = body.trailers().cloned().unwrap_or_default();
+        let status = grpc_status(&trailers).or_else(|| grpc_status(&parts.headers));
+        let status = status
+            .unwrap_or_else(|| GrpcStatus::new(GrpcCode::Internal, "response without grpc-status"));
+
+        if status.code != GrpcCode::Ok {
+            let message = format!("{:?}: {}", status.code, status.message);
+            // ResourceExhausted is only retryable with a RetryInfo, which
+            // is not read here
+            return match status.code {
+                GrpcCode::Cancelled
+                | GrpcCode::DeadlineExceeded
+                | GrpcCode::Aborted
+                | GrpcCode::OutOfRange
+                | GrpcCode::Unavailable
+                | GrpcCode::DataLoss => Err(Attempt::Retry(None, message)),
+                _ => Err(Attempt::Fail(crate::Error::ExportFailed(message))),
+            };
+        }
+
+        let body = body.to_bytes();
+        let encoding = parts
+            .headers
+            .get("grpc-encoding")
+            .and_then(|value| value.to_str().ok());
+        match GrpcFrames::new(&body).next() {
+            Some(frame) => frame
+                .and_then(|frame| frame.decompress(encoding, MAX_RESPONSE_BYTES))
+                .map(|message| Bytes::copy_from_slice(&message))
+                .map_err(Attempt::Fail),
+            // An empty response message may be sent as no message at all
+            None => Ok(Bytes::new()),
+        }
+    }
+
+    // Sends a request on a new HTTP/2 connection, kept for the next ones
+    async fn send_http2(
+        &self,
+        url: &Url,
+        request: Request<Full<Bytes>>,
+    ) -> Result<Response<Incoming>, Attempt> {
+        let (mut sender, connection) = http2::handshake(TokioExecutor::new(), connect(url).await?)
+            .await
+            .map_err(|e| Attempt::Retry(None, e.to_string()))?;
+        tokio::spawn(connection);
+        *self.connections.http2.lock().unwrap() = Some(sender.clone());
+        sender
+            .send_request(request)
+            .await
+            .map_err(|e| Attempt::Retry(None, e.to_string()))
+    }
+
+    // Full jitter over the upper half of the exponential delay
+    fn backoff(&self, attempt: u32) -> Duration {
+        let delay = self
+            .config
+            .initial_backoff
+            .saturating_mul(2u32.saturating_pow(attempt))
+            .min(self.config.max_backoff);
+        let jitter = (RandomState::new().build_hasher().finish() % 1000) as f64 / 1000.0;
+        delay / 2 + (delay / 2).mul_f64(jitter)
+    }
+}
+
+async fn connect(url: &Url) -> Result<TokioIo<TcpStream>, Attempt> {
+    let port = url
+        .port_or_known_default()
+        .expect("http has a default port");
+    let stream = match url.host().expect("checked in Exporter::new") {
+        Host::Domain(domain) => TcpStream::connect((domain, port)).await,
+        Host::Ipv4(ip) => TcpStream::connect((ip, port)).await,
+        Host::Ipv6(ip) => TcpStream::connect((ip, port)).await,
+    };
+    let stream = stream.map_err(|e| Attempt::Retry(None, e.to_string()))?;
+    Ok(TokioIo::new(stream))
+}
+
+// Sends a request on a new HTTP/1.1 connection
+async fn send_http1(
+    url: &Url,
+    request: Request<Full<Bytes>>,
+) -> Result<(http1::SendRequest<Full<Bytes>>, Response<Incoming>), Attempt> {
+    let (mut sender, connection) = http1::handshake(connect(url).await?)
+        .await
+        .map_err(|e| Attempt::Retry(None, e.to_string()))?;
+    tokio::spawn(connection);
+    let response = sender
+        .send_request(request)
+        .await
+        .map_err(|e| Attempt::Retry(None, e.to_string()))?;
+    Ok((sender, response))
+}
+
+enum Attempt {
+    /// Retryable failure, with the delay the receiver asked for
+    Retry(Option<Duration>, String),
+    Fail(crate::Error),
+}
+
+// A `Retry-After` value, either delay-seconds or an HTTP-date in the
+// preferred IMF-fixdate format, e.g. `Sun, 06 Nov 1994 08:49:37 GMT`
+fn parse_retry_after(value: &str, now: SystemTime) -> Option<Duration> {
+    let value = value.trim();
+    if let Ok(seconds) = value.parse() {
+        return Some(Duration::from_secs(seconds));
+    }
+
+    let fields: Vec<_> = value.split_ascii_whitespace().collect();
+    let [_weekday, day, month, year, time, "GMT"] = fields[..] else {
+        return None;
+    };
+    let month = [
+        "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+    ]
+    .iter()
+    .position(|name| *name == month)? as u64
+        + 1;
+    let (day, year): (u64, u64) = (day.parse().ok()?, year.parse().ok()?);
+    let mut time = time.split(':').map(|n| n.parse::<u64>().ok());
+    let (hour, minute, second) = (time.next()??, time.next()??, time.next()??);
+    if year < 1970 || !(1..=31).contains(&day) || hour > 23 || minute > 59 || second > 60 {
+        return None;
+    }
+
+    // Days since 1970-01-01 in the proleptic Gregorian calendar, counting
+    // years from March so that the leap day comes last
+    let (y, m) = if month <= 2 {
+        (year - 1, month + 9)
+    } else {
+        (year, month - 3)
+    };
+    let days = 365 * y + y / 4 - y / 100 + y / 400 + (153 * m + 2) / 5 + day - 1 - 719_468;
+    let date = UNIX_EPOCH + Duration::from_secs(days * 86400 + hour * 3600 + minute * 60 + second);
+    Some(date.duration_since(now).unwrap_or_default())
+}
+
+async fn collect(body: Incoming) -> Result<Collected<Bytes>, Attempt> {
+    Limited::new(body, MAX_RESPONSE_BYTES)
+        .collect()
+        .await
+        .map_err(|e| match e.downcast::<LengthLimitError>() {
+            Ok(_) => Attempt::Fail(crate::Error::ExportFailed(format!(
+                "response exceeds {MAX_RESPONSE_BYTES} bytes"
+            ))),
+            Err(e) => Attempt::Retry(None, e.to_string()),
+        })
+}
+
+fn grpc_status(headers: &HeaderMap) -> Option<GrpcStatus> {
+    let status = headers.get("grpc-status")?.to_str().ok()?;
+    let message = headers
+        .get("grpc-message")
+        .and_then(|value| value.to_str().ok());
+    Some(GrpcStatus::from_trailers(status, message))
+}
+
+fn decode<'a, M: MessageRead<'a>>(bytes: &'a [u8]) -> Result<M, crate::Error> {
+    let mut reader = BytesReader::from_bytes(bytes);
+    Ok(M::from_reader(&mut reader, bytes)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::opentelemetry::trace::ExportTracePartialSuccess;
+    use std::sync::{Arc, Mutex};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    // Answers each connection with the next canned response, recording the
+    // requests it received
+    async fn stub(responses: Vec<Vec<u8>>) -> (String, Arc<Mutex<Vec<Vec<u8>>>>) {
+        stub_at("127.0.0.1:0", responses).await
+    }
+
+    async fn stub_at(addr: &str, responses: Vec<Vec<u8>>) -> (String, Arc<Mutex<Vec<Vec<u8>>>>) {
+        let listener = TcpListener::bind(addr).await.unwrap();
+        let endpoint = format!("http://{}/otlp", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(vec![]));
+        let received = requests.clone();
+        tokio::spawn(async move {
+            for response in responses {
+                let (mut stream, _) = listener.accept().await.unwrap();
+                let mut request = vec![];
+                let mut buf = [0; 4096];
+                while !request_complete(&request) {
+                    let n = stream.read(&mut buf).await.unwrap();
+                    request.extend_from_slice(&buf[..n]);
+                }
+                received.lock().unwrap().push(request);
+                stream.write_all(&response).await.unwrap();
+            }
+        });
+        (endpoint, requests)
+    }
+
+    fn request_complete(request: &[u8]) -> bool {
+        let text = String::from_utf8_lossy(request);
+        let Some(end) = text.find("\r\n\r\n") else {
+            return false;
+        };
+        let len = text[..end]
+            .lines()
+            .find_map(|line| {
+                line.to_ascii_lowercase()
+                    .strip_prefix("content-length: ")?
+                    .parse()
+                    .ok()
+            })
+            .unwrap_or(0);
+        request.len() >= end + 4 + len
+    }
+
+    fn response(status: &str, headers: &str, body: &[u8]) -> Vec<u8> {
+        let mut response = format!(
+            "HTTP/1.1 {status}\r\nContent-Type: application/x-protobuf\r\n{headers}\
+             Content-Length: {}\r\nConnection: close\r\n\r\n",
+            body.len()
+        )
+        .into_bytes();
+        response.extend_from_slice(body);
+        response
+    }
+
+    // A canned gRPC response: headers, body and trailers
+    type GrpcResponse = (
+        &'static [(&'static str, &'static str)],
+        &'static [u8],
+        &'static [(&'static str, &'static str)],
+    );
+
+    // Answers each gRPC request with the next canned response, over HTTP/2
+    // without going through the crate's server, recording the requests
+    async fn grpc_stub(
+        responses: Vec<GrpcResponse>,
+    ) -> (String, Arc<Mutex<Vec<hyper::Request<Bytes>>>>) {
+        use http_body_util::combinators::WithTrailers;
+        use std::convert::Infallible;
+        use std::future::{ready, Ready};
+
+        type Body = WithTrailers<Full<Bytes>, Ready<Option<Result<HeaderMap, Infallible>>>>;
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let endpoint = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(vec![]));
+        let received = requests.clone();
+        let responses = Arc::new(Mutex::new(responses.into_iter()));
+        tokio::spawn(async move {
+            loop {
+                let (stream, _) = listener.accept().await.unwrap();
+                let received = received.clone();
+                let responses = responses.clone();
+                let service = hyper::service::service_fn(move |request: Request<Incoming>| {
+                    let received = received.clone();
+                    let responses = responses.clone();
+                    async move {
+                        let (parts, body) = request.into_parts();
+                        let body = body.collect().await.unwrap().to_bytes();
+                        received
+                            .lock()
+                            .unwrap()
+                            .push(Request::from_parts(parts, body));
+
+                        let (headers, body, trailers) = responses.lock().unwrap().next().unwrap();
+                        let to_map = |fields: &[(&'static str, &'static str)]| -> HeaderMap {
+                            fields
+                                .iter()
+                                .map(|(name, value)| {
+                                    (
+                                        header::HeaderName::from_static(name),
+                                        HeaderValue::from_static(value),
+                                    )
+                                })
+                                .collect()
+                        };
+                        let trailers = (!trailers.is_empty()).then(|| Ok(to_map(trailers)));
+                        let mut response: hyper::Response<Body> = hyper::Response::new(
+                            Full::new(Bytes::from_static(body)).with_trailers(ready(trailers)),
+                        );
+                        response.headers_mut().extend(to_map(headers));
+                        Ok::<_, Infallible>(response)
+                    }
+                });
+                tokio::spawn(
+                    hyper::server::conn::http2::Builder::new(TokioExecutor::new())
+                        .serve_connection(TokioIo::new(stream), service),
+                );
+            }
+        });
+        (endpoint, requests)
+    }
+
+    fn config(endpoint: String) -> ExporterConfig {
+        ExporterConfig {
+            endpoint,
+            headers: vec![("authorization".to_string(), "Bearer token".to_string())],
+            initial_backoff: Duration::from_millis(1),
+            max_backoff: Duration::from_millis(10),
+            max_retries: 2,
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn retry_then_partial_success() {
+        let partial_success = ExportTraceServiceResponse {
+            partial_success: Some(ExportTracePartialSuccess {
+                rejected_spans: 3,
+                error_message: "invalid spans".into(),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let (endpoint, requests) = stub(vec![
+            // The delay is capped at `max_backoff`
+            response("503 Service Unavailable", "Retry-After: 3600\r\n", &[]),
+            response("429 Too Many Requests", "", &[]),
+            response("200 OK", "", &partial_success.encode_to_vec()),
+        ])
+        .await;
+
+        let exporter = Exporter::new(config(endpoint)).unwrap();
+        let result = exporter
+            .export_traces(&ExportTraceServiceRequest::default())
+            .await
+            .unwrap();
+        assert_eq!(
+            result,
+            Some(PartialSuccess {
+                rejected: 3,
+                error_message: "invalid spans".to_string(),
+            })
+        );
+
+        let requests = requests.lock().unwrap();
+        assert_eq!(requests.len(), 3);
+        let request = String::from_utf8_lossy(&requests[0]).to_ascii_lowercase();
+        assert!(
+            request.starts_with("post /otlp/v1/traces http/1.1"),
+            "{request}"
+        );
+        assert!(request.contains("content-encoding: gzip"));
+        assert!(request.contains("authorization: bearer token"));
+    }
+
+    #[tokio::test]
+    async fn ipv6_endpoint() {
+        let (endpoint, requests) = stub_at("[::1]:0", vec![response("200 OK", "", &[])]).await;
+        assert!(endpoint.starts_with("http://[::1]:"), "{endpoint}");
+        let exporter = Exporter::new(config(endpoint.clone())).unwrap();
+        let result = exporter
+            .export_traces(&ExportTraceServiceRequest::default())
+            .await;
+        assert_eq!(result.unwrap(), None);
+
+        let requests = requests.lock().unwrap();
+        let request = String::from_utf8_lossy(&requests[0]).to_ascii_lowercase();
+        let authority = endpoint
+            .trim_start_matches("http://")
+            .trim_end_matches("/otlp");
+        assert!(
+            request.contains(&format!("host: {authority}\r\n")),
+            "{request}"
+        );
+    }
+
+    #[tokio::test]
+    async fn reuse_connections() {
+        // Answers every request on the connections it accepts
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let endpoint = format!("http://{}", listener.local_addr().unwrap());
+        let accepted = Arc::new(Mutex::new(0));
+        let counter = accepted.clone();
+        tokio::spawn(async move {
+            loop {
+                let (stream, _) = listener.accept().await.unwrap();
+                *counter.lock().unwrap() += 1;
+                let service = hyper::service::service_fn(|_: Request<Incoming>| async {
+                    Ok::<_, std::convert::Infallible>(hyper::Response::new(Full::new(Bytes::new())))
+                });
+                tokio::spawn(
+                    hyper::server::conn::http1::Builder::new()
+                        .serve_connection(TokioIo::new(stream), service),
+                );
+            }
+        });
+
+        let exporter = Exporter::new(config(endpoint)).unwrap();
+        let request = ExportLogsServiceRequest::default();
+        for _ in 0..3 {
+            assert_eq!(exporter.export_logs(&request).await.unwrap(), None);
+        }
+        assert_eq!(exporter.clone().export_logs(&request).await.unwrap(), None);
+        assert_eq!(*accepted.lock().unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn give_up() {
+        let unavailable = response("503 Service Unavailable", "", &[]);
+        let (endpoint, requests) = stub(vec![unavailable; 3]).await;
+        let exporter = Exporter::new(config(endpoint)).unwrap();
+        let result = exporter
+            .export_logs(&ExportLogsServiceRequest::default())
+            .await;
+        assert!(matches!(result, Err(crate::Error::ExportFailed(_))));
+        assert_eq!(requests.lock().unwrap().len(), 3);
+
+        // Client errors are not retried
+        let (endpoint, requests) = stub(vec![response("400 Bad Request", "", &[])]).await;
+        let exporter = Exporter::new(config(endpoint)).unwrap();
+        let result = exporter
+            .export_metrics(&ExportMetricsServiceRequest::default())
+            .await;
+        assert!(matches!(result, Err(crate::Error::ExportFailed(_))));
+        assert_eq!(requests.lock().unwrap().len(), 1);
+    }
+
+    // Hand-captured responses of the shapes the collector sends, served by a
+    // stub rather than a collector
+    #[tokio::test]
+    async fn grpc_responses() {
+        const CONTENT_TYPE: &[(&str, &str)] = &[("content-type", "application/grpc")];
+        let (endpoint, requests) = grpc_stub(vec![
+            // Trailers-only responses put the status in the headers
+            (
+                &[
+                    ("content-type", "application/grpc"),
+                    ("grpc-status", "14"),
+                    ("grpc-message", "data refused due to high memory usage"),
+                ],
+                &[],
+                &[],
+            ),
+            // ExportTraceServiceResponse with a partial success, then OK
+            (
+                CONTENT_TYPE,
+                &[
+                    0x00, 0x00, 0x00, 0x00, 0x0d, 0x0a, 0x0b, 0x08, 0x01, 0x12, 0x07, 0x69, 0x6e,
+                    0x76, 0x61, 0x6c, 0x69, 0x64,
+                ],
+                &[("grpc-status", "0"), ("grpc-message", "")],
+            ),
+            // Empty response message
+            (
+                CONTENT_TYPE,
+                &[0x00, 0x00, 0x00, 0x00, 0x00],
+                &[("grpc-status", "0")],
+            ),
+            (
+                CONTENT_TYPE,
+                &[],
+                &[
+                    ("grpc-status", "3"),
+                    ("grpc-message", "invalid%20span%20id%3A%20%25"),
+                ],
+            ),
+        ])
+        .await;
+
+        let exporter = Exporter::new(ExporterConfig {
+            protocol: Protocol::Grpc,
+            compression: false,
+            ..config(endpoint)
+        })
+        .unwrap();
+        let request = ExportTraceServiceRequest::default();
+        assert_eq!(
+            exporter.export_traces(&request).await.unwrap(),
+            Some(PartialSuccess {
+                rejected: 1,
+                error_message: "invalid".to_string(),
+            })
+        );
+        assert_eq!(exporter.export_traces(&request).await.unwrap(), None);
+        let result = exporter.export_traces(&request).await;
+        assert!(
+            matches!(&result, Err(crate::Error::ExportFailed(message)) if message == "InvalidArgument: invalid span id: %"),
+            "{result:?}"
+        );
+
+        let requests = requests.lock().unwrap();
+        assert_eq!(requests.len(), 4);
+        let request = &requests[0];
+        assert_eq!(
+            request.uri().path(),
+            "/opentelemetry.proto.collector.trace.v1.TraceService/Export"
+        );
+        let headers = request.headers();
+        assert_eq!(headers["content-type"], "application/grpc");
+        assert_eq!(headers["te"], "trailers");
+        assert_eq!(headers["authorization"], "Bearer token");
+        assert_eq!(&request.body()[..], &[0, 0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn backoff() {
+        let exporter = Exporter::new(ExporterConfig {
+            initial_backoff: Duration::from_secs(1),
+            max_backoff: Duration::from_secs(4),
+            ..Default::default()
+        })
+        .unwrap();
+        for (attempt, max) in [(0, 1), (1, 2), (2, 4), (10, 4)] {
+            let delay = exporter.backoff(attempt);
+            let max = Duration::from_secs(max);
+            assert!(delay >= max / 2 && delay <= max, "{delay:?}");
+        }
+
+        let exporter = Exporter::new(ExporterConfig {
+            initial_backoff: Duration::MAX,
+            max_backoff: Duration::MAX,
+            ..Default::default()
+        })
+        .unwrap();
+        assert!(exporter.backoff(3) >= Duration::MAX / 2);
+
+        assert!(Exporter::new(ExporterConfig {
+            endpoint: "https://localhost".to_string(),
+            ..Default::default()
+        })
+        .is_err());
+    }
+
+    #[test]
+    fn retry_after() {
+        // 2015-10-21T07:28:00Z
+        let now = UNIX_EPOCH + Duration::from_secs(1_445_412_480);
+        let parse = |value| parse_retry_after(value, now);
+        assert_eq!(parse(" 120 "), Some(Duration::from_secs(120)));
+        assert_eq!(
+            parse("Wed, 21 Oct 2015 07:30:00 GMT"),
+            Some(Duration::from_secs(120))
+        );
+        assert_eq!(
+            parse("Thu, 29 Feb 2024 00:00:00 GMT"),
+            Some(Duration::from_secs(1_709_164_800 - 1_445_412_480))
+        );
+        // Dates in the past mean now
+        assert_eq!(parse("Sun, 06 Nov 1994 08:49:37 GMT"), Some(Duration::ZERO));
+        for value in [
+            "-1",
+            "soon",
+            "Wed, 21 Oct 2015 07:30:00 PST",
+            "Wed, 21 Foo 2015 07:30:00 GMT",
+        ] {
+            assert_eq!(parse(value), None, "{value}");
+        }
+    }
+}
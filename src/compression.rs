@@ -0,0 +1,263 @@
+use std::borrow::Cow;
+#[cfg(any(feature = "gzip", feature = "deflate", feature = "zstd"))]
+use std::io::Read;
+
+use crate::opentelemetry::logs::{ExportLogsServiceRequest, Validate as _};
+use crate::opentelemetry::metrics::{ExportMetricsServiceRequest, Validate as _};
+use crate::opentelemetry::trace::{ExportTraceServiceRequest, Validate as _};
+use crate::{DecodeOptions, IntoOwned};
+
+/// Decodes `body` according to a `Content-Encoding` or `grpc-encoding`
+/// value, failing once the decoded body grows past `limit` bytes.
+///
+/// Codings listed together, e.g. `gzip, zstd`, are undone last to first.
+/// Only the codecs whose cargo feature is enabled are supported.
+pub fn decompress<'a>(
+    body: &'a [u8],
+    encoding: Option<&str>,
+    limit: usize,
+) -> Result<Cow<'a, [u8]>, crate::Error> {
+    let mut body = Cow::Borrowed(body);
+    for coding in encoding.into_iter().flat_map(codings).rev() {
+        body = Cow::Owned(decode(&body, coding, limit)?);
+    }
+
+    if body.len() > limit {
+        return Err(crate::Error::MessageTooLarge {
+            size: body.len(),
+            limit,
+        });
+    }
+    Ok(body)
+}
+
+// The codings of a `Content-Encoding` or `grpc-encoding` value in the order
+// they were applied, `identity` aside
+pub(crate) fn codings(encoding: &str) -> impl DoubleEndedIterator<Item = &str> {
+    encoding
+        .split(',')
+        .map(str::trim)
+        .filter(|coding| !(coding.is_empty() || coding.eq_ignore_ascii_case("identity")))
+}
+
+#[cfg_attr(
+    not(any(feature = "gzip", feature = "deflate", feature = "zstd")),
+    allow(unused_variables)
+)]
+fn decode(body: &[u8], coding: &str, limit: usize) -> Result<Vec<u8>, crate::Error> {
+    match coding.to_ascii_lowercase().as_str() {
+        #[cfg(feature = "gzip")]
+        "gzip" | "x-gzip" => read_limited(flate2::read::MultiGzDecoder::new(body), limit),
+        // HTTP deflate is zlib-wrapped, not raw deflate
+        #[cfg(feature = "deflate")]
+        "deflate" => read_limited(flate2::read::ZlibDecoder::new(body), limit),
+        #[cfg(feature = "zstd")]
+        "zstd" => {
+            let decoder = zstd::stream::read::Decoder::new(body)
+                .map_err(|e| crate::Error::Decompression(e.to_string()))?;
+            read_limited(decoder, limit)
+        }
+        _ => Err(crate::Error::UnsupportedEncoding(coding.to_string())),
+    }
+}
+
+// Reads one byte past the limit to tell a body of exactly `limit` bytes from
+// a larger one without decompressing the rest
+#[cfg(any(feature = "gzip", feature = "deflate", feature = "zstd"))]
+fn read_limited<R: Read>(decoder: R, limit: usize) -> Result<Vec<u8>, crate::Error> {
+    let mut out = Vec::new();
+    decoder
+        .take((limit as u64).saturating_add(1))
+        .read_to_end(&mut out)
+        .map_err(|e| crate::Error::Decompression(e.to_string()))?;
+    if out.len() > limit {
+        return Err(crate::Error::DecompressedTooLarge { limit });
+    }
+    Ok(out)
+}
+
+/// A request decoded from a request body, valid or not.
+#[derive(Debug)]
+pub struct DecodedRequest<T> {
+    pub request: T,
+    /// One error for each resource that failed validation.
+    pub errors: Vec<crate::Error>,
+}
+
+impl ExportMetricsServiceRequest<'static> {
+    /// Decompresses and decodes a request body, then validates each resource.
+    ///
+    /// The decompressed body is capped at `options.max_total_bytes`.
+    pub fn decode_body(
+        body: &[u8],
+        content_encoding: Option<&str>,
+        options: &DecodeOptions,
+    ) -> Result<DecodedRequest<Self>, crate::Error> {
+        let body = decompress(body, content_encoding, options.max_total_bytes)?;
+        let request = ExportMetricsServiceRequest::decode_with(&body, options)?.into_owned();
+        let errors = request
+            .resource_metrics
+            .iter()
+            .filter_map(|resource_metrics| resource_metrics.validate().err())
+            .collect();
+        Ok(DecodedRequest { request, errors })
+    }
+}
+
+impl ExportLogsServiceRequest<'static> {
+    /// Decompresses and decodes a request body, then validates each resource.
+    ///
+    /// The decompressed body is capped at `options.max_total_bytes`.
+    pub fn decode_body(
+        body: &[u8],
+        content_encoding: Option<&str>,
+        options: &DecodeOptions,
+    ) -> Result<DecodedRequest<Self>, crate::Error> {
+        let body = decompress(body, content_encoding, options.max_total_bytes)?;
+        let request = ExportLogsServiceRequest::decode_with(&body, options)?.into_owned();
+        let errors = request
+            .resource_logs
+            .iter()
+            .filter_map(|resource_logs| resource_logs.validate().err())
+            .collect();
+        Ok(DecodedRequest { request, errors })
+    }
+}
+
+impl ExportTraceServiceRequest<'static> {
+    /// Decompresses and decodes a request body, then validates each resource.
+    ///
+    /// The decompressed body is capped at `options.max_total_bytes`.
+    pub fn decode_body(
+        body: &[u8],
+        content_encoding: Option<&str>,
+        options: &DecodeOptions,
+    ) -> Result<DecodedRequest<Self>, crate::Error> {
+        let body = decompress(body, content_encoding, options.max_total_bytes)?;
+        let request = ExportTraceServiceRequest::decode_with(&body, options)?.into_owned();
+        let errors = request
+            .resource_spans
+            .iter()
+            .filter_map(|resource_spans| resource_spans.validate().err())
+            .collect();
+        Ok(DecodedRequest { request, errors })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::opentelemetry::trace::{ResourceSpans, ScopeSpans, Span};
+
+    fn request() -> ExportTraceServiceRequest<'static> {
+        let resource_spans = |schema_url: &'static str| ResourceSpans {
+            scope_spans: vec![ScopeSpans {
+                spans: vec![Span {
+                    name: "span".into(),
+                    ..Default::default()
+                }],
+                schema_url: schema_url.into(),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        ExportTraceServiceRequest {
+            // The second resource has an invalid schema URL
+            resource_spans: vec![resource_spans(""), resource_spans("not a url")],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn decode_identity_body() {
+        let body = request().encode_to_vec();
+        let options = DecodeOptions::default();
+        for encoding in [None, Some("identity"), Some("")] {
+            let decoded =
+                ExportTraceServiceRequest::decode_body(&body, encoding, &options).unwrap();
+            assert_eq!(decoded.request, request());
+            assert_eq!(decoded.errors.len(), 1);
+            assert!(matches!(
+                decoded.errors[0],
+                crate::Error::UrlParse(url::ParseError::RelativeUrlWithoutBase)
+            ));
+        }
+
+        let options = DecodeOptions {
+            max_total_bytes: body.len() - 1,
+            ..Default::default()
+        };
+        assert!(matches!(
+            ExportTraceServiceRequest::decode_body(&body, None, &options),
+            Err(crate::Error::MessageTooLarge { .. })
+        ));
+        assert!(matches!(
+            decompress(&body, Some("br"), usize::MAX),
+            Err(crate::Error::UnsupportedEncoding(encoding)) if encoding == "br"
+        ));
+    }
+
+    #[cfg(any(feature = "gzip", feature = "deflate"))]
+    fn compress<W: std::io::Write>(mut encoder: W, body: &[u8]) -> W {
+        encoder.write_all(body).unwrap();
+        encoder
+    }
+
+    #[cfg(feature = "gzip")]
+    #[test]
+    fn decode_gzip_body() {
+        use flate2::{write::GzEncoder, Compression};
+
+        let body = request().encode_to_vec();
+        let gzip = compress(GzEncoder::new(vec![], Compression::default()), &body)
+            .finish()
+            .unwrap();
+        let decoded =
+            ExportTraceServiceRequest::decode_body(&gzip, Some("gzip"), &DecodeOptions::default())
+                .unwrap();
+        assert_eq!(decoded.request, request());
+
+        // A small body that inflates past the cap is cut off
+        let zeros = compress(GzEncoder::new(vec![], Compression::best()), &[0; 1 << 20])
+            .finish()
+            .unwrap();
+        assert!(zeros.len() < 4096);
+        assert!(matches!(
+            decompress(&zeros, Some("gzip"), 4096),
+            Err(crate::Error::DecompressedTooLarge { limit: 4096 })
+        ));
+        assert!(matches!(
+            decompress(&body, Some("gzip"), 4096),
+            Err(crate::Error::Decompression(_))
+        ));
+    }
+
+    #[cfg(feature = "deflate")]
+    #[test]
+    fn decode_deflate_body() {
+        use flate2::{write::ZlibEncoder, Compression};
+
+        let body = request().encode_to_vec();
+        let deflate = compress(ZlibEncoder::new(vec![], Compression::default()), &body)
+            .finish()
+            .unwrap();
+        assert_eq!(
+            decompress(&deflate, Some("Deflate"), usize::MAX).unwrap(),
+            body
+        );
+    }
+
+    #[cfg(feature = "zstd")]
+    #[test]
+    fn decode_zstd_body() {
+        let body = request().encode_to_vec();
+        let zstd = zstd::encode_all(&body[..], 0).unwrap();
+        assert_eq!(decompress(&zstd, Some("zstd"), usize::MAX).unwrap(), body);
+
+        let zeros = zstd::encode_all(&[0; 1 << 20][..], 0).unwrap();
+        assert!(matches!(
+            decompress(&zeros, Some("zstd"), 4096),
+            Err(crate::Error::DecompressedTooLarge { limit: 4096 })
+        ));
+    }
+}
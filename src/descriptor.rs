@@ -0,0 +1,389 @@
+// Wire-level description of the OTLP messages, listing every field with
+// what is needed to look into it without decoding: nested messages, packed
+// repeated scalars and repeated strings or bytes are told apart from plain
+// scalar, string or bytes fields.
+
+pub(crate) struct MessageDescriptor {
+    pub(crate) fields: &'static [FieldDescriptor],
+}
+
+pub(crate) struct FieldDescriptor {
+    pub(crate) number: u32,
+    pub(crate) kind: FieldKind,
+}
+
+pub(crate) enum FieldKind {
+    Message(&'static MessageDescriptor),
+    PackedFixed(usize),
+    PackedVarint,
+    Scalar,
+    RepeatedBytes,
+}
+
+// No message descriptor lists more fields than this
+pub(crate) const MAX_FIELDS: usize = 24;
+
+impl MessageDescriptor {
+    const fn new(fields: &'static [FieldDescriptor]) -> Self {
+        assert!(
+            fields.len() <= MAX_FIELDS,
+            "too many fields, raise MAX_FIELDS"
+        );
+        MessageDescriptor { fields }
+    }
+
+    pub(crate) fn field(&self, number: u32) -> Option<(usize, &FieldDescriptor)> {
+        self.fields
+            .iter()
+            .enumerate()
+            .find(|(_, field)| field.number == number)
+    }
+}
+
+const fn message(number: u32, descriptor: &'static MessageDescriptor) -> FieldDescriptor {
+    FieldDescriptor {
+        number,
+        kind: FieldKind::Message(descriptor),
+    }
+}
+
+const fn packed_fixed(number: u32, size: usize) -> FieldDescriptor {
+    FieldDescriptor {
+        number,
+        kind: FieldKind::PackedFixed(size),
+    }
+}
+
+const fn packed_varint(number: u32) -> FieldDescriptor {
+    FieldDescriptor {
+        number,
+        kind: FieldKind::PackedVarint,
+    }
+}
+
+const fn scalar(number: u32) -> FieldDescriptor {
+    FieldDescriptor {
+        number,
+        kind: FieldKind::Scalar,
+    }
+}
+
+const fn repeated_bytes(number: u32) -> FieldDescriptor {
+    FieldDescriptor {
+        number,
+        kind: FieldKind::RepeatedBytes,
+    }
+}
+
+// common.proto
+
+pub(crate) static ANY_VALUE: MessageDescriptor = MessageDescriptor::new(&[
+    scalar(1),
+    scalar(2),
+    scalar(3),
+    scalar(4),
+    message(5, &ARRAY_VALUE),
+    message(6, &KEY_VALUE_LIST),
+    scalar(7),
+]);
+
+pub(crate) static ARRAY_VALUE: MessageDescriptor =
+    MessageDescriptor::new(&[message(1, &ANY_VALUE)]);
+
+pub(crate) static KEY_VALUE_LIST: MessageDescriptor =
+    MessageDescriptor::new(&[message(1, &KEY_VALUE)]);
+
+pub(crate) static KEY_VALUE: MessageDescriptor =
+    MessageDescriptor::new(&[scalar(1), message(2, &ANY_VALUE)]);
+
+pub(crate) static INSTRUMENTATION_SCOPE: MessageDescriptor =
+    MessageDescriptor::new(&[scalar(1), scalar(2), message(3, &KEY_VALUE), scalar(4)]);
+
+pub(crate) static RESOURCE: MessageDescriptor =
+    MessageDescriptor::new(&[message(1, &KEY_VALUE), scalar(2), message(3, &ENTITY_REF)]);
+
+pub(crate) static ENTITY_REF: MessageDescriptor =
+    MessageDescriptor::new(&[scalar(1), scalar(2), repeated_bytes(3), repeated_bytes(4)]);
+
+// logs.proto
+
+pub(crate) static EXPORT_LOGS_SERVICE_REQUEST: MessageDescriptor =
+    MessageDescriptor::new(&[message(1, &RESOURCE_LOGS)]);
+
+// 1000 is the deprecated `instrumentation_library_logs`, see legacy.rs
+pub(crate) static RESOURCE_LOGS: MessageDescriptor = MessageDescriptor::new(&[
+    message(1, &RESOURCE),
+    message(2, &SCOPE_LOGS),
+    scalar(3),
+    message(1000, &SCOPE_LOGS),
+]);
+
+pub(crate) static SCOPE_LOGS: MessageDescriptor = MessageDescriptor::new(&[
+    message(1, &INSTRUMENTATION_SCOPE),
+    message(2, &LOG_RECORD),
+    scalar(3),
+]);
+
+pub(crate) static LOG_RECORD: MessageDescriptor = MessageDescriptor::new(&[
+    scalar(1),
+    scalar(2),
+    scalar(3),
+    message(5, &ANY_VALUE),
+    message(6, &KEY_VALUE),
+    scalar(7),
+    scalar(8),
+    scalar(9),
+    scalar(10),
+    scalar(11),
+    scalar(12),
+]);
+
+// metrics.proto
+
+pub(crate) static EXPORT_METRICS_SERVICE_REQUEST: MessageDescriptor =
+    MessageDescriptor::new(&[message(1, &RESOURCE_METRICS)]);
+
+// 1000 is the deprecated `instrumentation_library_metrics`, see legacy.rs
+pub(crate) static RESOURCE_METRICS: MessageDescriptor = MessageDescriptor::new(&[
+    message(1, &RESOURCE),
+    message(2, &SCOPE_METRICS),
+    scalar(3),
+    message(1000, &SCOPE_METRICS),
+]);
+
+pub(crate) static SCOPE_METRICS: MessageDescriptor = MessageDescriptor::new(&[
+    message(1, &INSTRUMENTATION_SCOPE),
+    message(2, &METRIC),
+    scalar(3),
+]);
+
+pub(crate) static METRIC: MessageDescriptor = MessageDescriptor::new(&[
+    scalar(1),
+    scalar(2),
+    scalar(3),
+    message(5, &GAUGE),
+    message(7, &SUM),
+    message(9, &HISTOGRAM),
+    message(10, &EXPONENTIAL_HISTOGRAM),
+    message(11, &SUMMARY),
+    message(12, &KEY_VALUE),
+]);
+
+pub(crate) static GAUGE: MessageDescriptor =
+    MessageDescriptor::new(&[message(1, &NUMBER_DATA_POINT)]);
+
+pub(crate) static SUM: MessageDescriptor =
+    MessageDescriptor::new(&[message(1, &NUMBER_DATA_POINT), scalar(2), scalar(3)]);
+
+pub(crate) static HISTOGRAM: MessageDescriptor =
+    MessageDescriptor::new(&[message(1, &HISTOGRAM_DATA_POINT), scalar(2)]);
+
+pub(crate) static EXPONENTIAL_HISTOGRAM: MessageDescriptor =
+    MessageDescriptor::new(&[message(1, &EXPONENTIAL_HISTOGRAM_DATA_POINT), scalar(2)]);
+
+pub(crate) static SUMMARY: MessageDescriptor =
+    MessageDescriptor::new(&[message(1, &SUMMARY_DATA_POINT)]);
+
+pub(crate) static NUMBER_DATA_POINT: MessageDescriptor = MessageDescriptor::new(&[
+    scalar(2),
+    scalar(3),
+    scalar(4),
+    message(5, &EXEMPLAR),
+    scalar(6),
+    message(7, &KEY_VALUE),
+    scalar(8),
+]);
+
+pub(crate) static HISTOGRAM_DATA_POINT: MessageDescriptor = MessageDescriptor::new(&[
+    scalar(2),
+    scalar(3),
+    scalar(4),
+    scalar(5),
+    packed_fixed(6, 8),
+    packed_fixed(7, 8),
+    message(8, &EXEMPLAR),
+    message(9, &KEY_VALUE),
+    scalar(10),
+    scalar(11),
+    scalar(12),
+]);
+
+pub(crate) static EXPONENTIAL_HISTOGRAM_DATA_POINT: MessageDescriptor = MessageDescriptor::new(&[
+    message(1, &KEY_VALUE),
+    scalar(2),
+    scalar(3),
+    scalar(4),
+    scalar(5),
+    scalar(6),
+    scalar(7),
+    message(8, &BUCKETS),
+    message(9, &BUCKETS),
+    scalar(10),
+    message(11, &EXEMPLAR),
+    scalar(12),
+    scalar(13),
+    scalar(14),
+]);
+
+pub(crate) static BUCKETS: MessageDescriptor =
+    MessageDescriptor::new(&[scalar(1), packed_varint(2)]);
+
+pub(crate) static SUMMARY_DATA_POINT: MessageDescriptor = MessageDescriptor::new(&[
+    scalar(2),
+    scalar(3),
+    scalar(4),
+    scalar(5),
+    message(6, &VALUE_AT_QUANTILE),
+    message(7, &KEY_VALUE),
+    scalar(8),
+]);
+
+pub(crate) static VALUE_AT_QUANTILE: MessageDescriptor =
+    MessageDescriptor::new(&[scalar(1), scalar(2)]);
+
+pub(crate) static EXEMPLAR: MessageDescriptor = MessageDescriptor::new(&[
+    scalar(2),
+    scalar(3),
+    scalar(4),
+    scalar(5),
+    scalar(6),
+    message(7, &KEY_VALUE),
+]);
+
+// trace.proto
+
+pub(crate) static EXPORT_TRACE_SERVICE_REQUEST: MessageDescriptor =
+    MessageDescriptor::new(&[message(1, &RESOURCE_SPANS)]);
+
+// 1000 is the deprecated `instrumentation_library_spans`, see legacy.rs
+pub(crate) static RESOURCE_SPANS: MessageDescriptor = MessageDescriptor::new(&[
+    message(1, &RESOURCE),
+    message(2, &SCOPE_SPANS),
+    scalar(3),
+    message(1000, &SCOPE_SPANS),
+]);
+
+pub(crate) static SCOPE_SPANS: MessageDescriptor = MessageDescriptor::new(&[
+    message(1, &INSTRUMENTATION_SCOPE),
+    message(2, &SPAN),
+    scalar(3),
+]);
+
+pub(crate) static SPAN: MessageDescriptor = MessageDescriptor::new(&[
+    scalar(1),
+    scalar(2),
+    scalar(3),
+    scalar(4),
+    scalar(5),
+    scalar(6),
+    scalar(7),
+    scalar(8),
+    message(9, &KEY_VALUE),
+    scalar(10),
+    message(11, &SPAN_EVENT),
+    scalar(12),
+    message(13, &SPAN_LINK),
+    scalar(14),
+    message(15, &STATUS),
+    scalar(16),
+]);
+
+pub(crate) static SPAN_EVENT: MessageDescriptor =
+    MessageDescriptor::new(&[scalar(1), scalar(2), message(3, &KEY_VALUE), scalar(4)]);
+
+pub(crate) static SPAN_LINK: MessageDescriptor = MessageDescriptor::new(&[
+    scalar(1),
+    scalar(2),
+    scalar(3),
+    message(4, &KEY_VALUE),
+    scalar(5),
+    scalar(6),
+]);
+
+// 1 is the deprecated `deprecated_code`, see legacy.rs
+pub(crate) static STATUS: MessageDescriptor =
+    MessageDescriptor::new(&[scalar(1), scalar(2), scalar(3)]);
+
+// profiles.proto
+
+pub(crate) static EXPORT_PROFILES_SERVICE_REQUEST: MessageDescriptor =
+    MessageDescriptor::new(&[message(1, &RESOURCE_PROFILES)]);
+
+pub(crate) static RESOURCE_PROFILES: MessageDescriptor = MessageDescriptor::new(&[
+    message(1, &RESOURCE),
+    message(2, &SCOPE_PROFILES),
+    scalar(3),
+]);
+
+pub(crate) static SCOPE_PROFILES: MessageDescriptor = MessageDescriptor::new(&[
+    message(1, &INSTRUMENTATION_SCOPE),
+    message(2, &PROFILE),
+    scalar(3),
+]);
+
+pub(crate) static PROFILE: MessageDescriptor = MessageDescriptor::new(&[
+    message(1, &VALUE_TYPE),
+    message(2, &SAMPLE),
+    message(3, &MAPPING),
+    message(4, &LOCATION),
+    packed_varint(5),
+    message(6, &FUNCTION),
+    message(7, &KEY_VALUE),
+    message(8, &ATTRIBUTE_UNIT),
+    message(9, &PROFILE_LINK),
+    repeated_bytes(10),
+    scalar(11),
+    scalar(12),
+    message(13, &VALUE_TYPE),
+    scalar(14),
+    packed_varint(15),
+    scalar(16),
+    scalar(17),
+    packed_varint(18),
+    scalar(19),
+    scalar(20),
+    scalar(21),
+]);
+
+pub(crate) static ATTRIBUTE_UNIT: MessageDescriptor =
+    MessageDescriptor::new(&[scalar(1), scalar(2)]);
+
+pub(crate) static PROFILE_LINK: MessageDescriptor = MessageDescriptor::new(&[scalar(1), scalar(2)]);
+
+pub(crate) static VALUE_TYPE: MessageDescriptor =
+    MessageDescriptor::new(&[scalar(1), scalar(2), scalar(3)]);
+
+pub(crate) static SAMPLE: MessageDescriptor = MessageDescriptor::new(&[
+    scalar(1),
+    scalar(2),
+    packed_varint(3),
+    packed_varint(4),
+    scalar(5),
+    packed_varint(6),
+]);
+
+pub(crate) static MAPPING: MessageDescriptor = MessageDescriptor::new(&[
+    scalar(1),
+    scalar(2),
+    scalar(3),
+    scalar(4),
+    packed_varint(5),
+    scalar(6),
+    scalar(7),
+    scalar(8),
+    scalar(9),
+]);
+
+pub(crate) static LOCATION: MessageDescriptor = MessageDescriptor::new(&[
+    scalar(1),
+    scalar(2),
+    message(3, &LINE),
+    scalar(4),
+    packed_varint(5),
+]);
+
+pub(crate) static LINE: MessageDescriptor =
+    MessageDescriptor::new(&[scalar(1), scalar(2), scalar(3)]);
+
+pub(crate) static FUNCTION: MessageDescriptor =
+    MessageDescriptor::new(&[scalar(1), scalar(2), scalar(3), scalar(4)]);
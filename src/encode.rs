@@ -0,0 +1,246 @@
+use bytes::{BufMut, BytesMut};
+use quick_protobuf::{MessageWrite, Writer};
+
+use crate::opentelemetry::logs::{ExportLogsServiceRequest, ExportLogsServiceResponse, LogsData};
+use crate::opentelemetry::metrics::{
+    ExportMetricsServiceRequest, ExportMetricsServiceResponse, MetricsData,
+};
+use crate::opentelemetry::trace::{
+    ExportTraceServiceRequest, ExportTraceServiceResponse, TracesData,
+};
+
+pub(crate) fn encode_to_vec<M: MessageWrite>(message: &M) -> Vec<u8> {
+    let mut out = Vec::with_capacity(message.get_size());
+    message
+        .write_message(&mut Writer::new(&mut out))
+        .expect("writing to a Vec cannot fail");
+    out
+}
+
+fn encode_into<M: MessageWrite>(message: &M, buf: &mut BytesMut) {
+    buf.reserve(message.get_size());
+    message
+        .write_message(&mut Writer::new(buf.writer()))
+        .expect("writing to a BytesMut cannot fail");
+}
+
+impl ExportMetricsServiceRequest<'_> {
+    /// Encodes the request, without a length prefix.
+    pub fn encode_to_vec(&self) -> Vec<u8> {
+        encode_to_vec(self)
+    }
+
+    /// Appends the encoded request, without a length prefix, to `buf`.
+    pub fn encode_into(&self, buf: &mut BytesMut) {
+        encode_into(self, buf)
+    }
+
+    /// Size of the encoded request, in bytes.
+    pub fn encoded_len(&self) -> usize {
+        self.get_size()
+    }
+}
+
+impl ExportMetricsServiceResponse<'_> {
+    /// Encodes the response, without a length prefix.
+    pub fn encode_to_vec(&self) -> Vec<u8> {
+        encode_to_vec(self)
+    }
+
+    /// Appends the encoded response, without a length prefix, to `buf`.
+    pub fn encode_into(&self, buf: &mut BytesMut) {
+        encode_into(self, buf)
+    }
+
+    /// Size of the encoded response, in bytes.
+    pub fn encoded_len(&self) -> usize {
+        self.get_size()
+    }
+}
+
+impl MetricsData<'_> {
+    /// Encodes the metrics data, without a length prefix.
+    pub fn encode_to_vec(&self) -> Vec<u8> {
+        encode_to_vec(self)
+    }
+
+    /// Appends the encoded metrics data, without a length prefix, to `buf`.
+    pub fn encode_into(&self, buf: &mut BytesMut) {
+        encode_into(self, buf)
+    }
+
+    /// Size of the encoded metrics data, in bytes.
+    pub fn encoded_len(&self) -> usize {
+        self.get_size()
+    }
+}
+
+impl ExportLogsServiceRequest<'_> {
+    /// Encodes the request, without a length prefix.
+    pub fn encode_to_vec(&self) -> Vec<u8> {
+        encode_to_vec(self)
+    }
+
+    /// Appends the encoded request, without a length prefix, to `buf`.
+    pub fn encode_into(&self, buf: &mut BytesMut) {
+        encode_into(self, buf)
+    }
+
+    /// Size of the encoded request, in bytes.
+    pub fn encoded_len(&self) -> usize {
+        self.get_size()
+    }
+}
+
+impl ExportLogsServiceResponse<'_> {
+    /// Encodes the response, without a length prefix.
+    pub fn encode_to_vec(&self) -> Vec<u8> {
+        encode_to_vec(self)
+    }
+
+    /// Appends the encoded response, without a length prefix, to `buf`.
+    pub fn encode_into(&self, buf: &mut BytesMut) {
+        encode_into(self, buf)
+    }
+
+    /// Size of the encoded response, in bytes.
+    pub fn encoded_len(&self) -> usize {
+        self.get_size()
+    }
+}
+
+impl LogsData<'_> {
+    /// Encodes the logs data, without a length prefix.
+    pub fn encode_to_vec(&self) -> Vec<u8> {
+        encode_to_vec(self)
+    }
+
+    /// Appends the encoded logs data, without a length prefix, to `buf`.
+    pub fn encode_into(&self, buf: &mut BytesMut) {
+        encode_into(self, buf)
+    }
+
+    /// Size of the encoded logs data, in bytes.
+    pub fn encoded_len(&self) -> usize {
+        self.get_size()
+    }
+}
+
+impl ExportTraceServiceRequest<'_> {
+    /// Encodes the request, without a length prefix.
+    pub fn encode_to_vec(&self) -> Vec<u8> {
+        encode_to_vec(self)
+    }
+
+    /// Appends the encoded request, without a length prefix, to `buf`.
+    pub fn encode_into(&self, buf: &mut BytesMut) {
+        encode_into(self, buf)
+    }
+
+    /// Size of the encoded request, in bytes.
+    pub fn encoded_len(&self) -> usize {
+        self.get_size()
+    }
+}
+
+impl ExportTraceServiceResponse<'_> {
+    /// Encodes the response, without a length prefix.
+    pub fn encode_to_vec(&self) -> Vec<u8> {
+        encode_to_vec(self)
+    }
+
+    /// Appends the encoded response, without a length prefix, to `buf`.
+    pub fn encode_into(&self, buf: &mut BytesMut) {
+        encode_into(self, buf)
+    }
+
+    /// Size of the encoded response, in bytes.
+    pub fn encoded_len(&self) -> usize {
+        self.get_size()
+    }
+}
+
+impl TracesData<'_> {
+    /// Encodes the traces data, without a length prefix.
+    pub fn encode_to_vec(&self) -> Vec<u8> {
+        encode_to_vec(self)
+    }
+
+    /// Appends the encoded traces data, without a length prefix, to `buf`.
+    pub fn encode_into(&self, buf: &mut BytesMut) {
+        encode_into(self, buf)
+    }
+
+    /// Size of the encoded traces data, in bytes.
+    pub fn encoded_len(&self) -> usize {
+        self.get_size()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::opentelemetry::common::{AnyValue, AnyValueOneOfvalue, KeyValue};
+    use crate::opentelemetry::logs::{ExportLogsPartialSuccess, ExportLogsServiceResponse};
+    use crate::opentelemetry::trace::{ExportTraceServiceRequest, ResourceSpans, ScopeSpans, Span};
+    use bytes::BytesMut;
+    use std::borrow::Cow;
+
+    fn trace_request() -> ExportTraceServiceRequest<'static> {
+        ExportTraceServiceRequest {
+            resource_spans: vec![ResourceSpans {
+                scope_spans: vec![ScopeSpans {
+                    spans: vec![Span {
+                        trace_id: Cow::from(&[1; 16][..]),
+                        span_id: Cow::from(&[2; 8][..]),
+                        name: Cow::from("span"),
+                        attributes: vec![KeyValue {
+                            key: Cow::from("key"),
+                            value: Some(AnyValue {
+                                value: AnyValueOneOfvalue::int_value(300),
+                                ..Default::default()
+                            }),
+                            ..Default::default()
+                        }],
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn encode_to_vec_allocates_exact_size() {
+        let request = trace_request();
+        let out = request.encode_to_vec();
+
+        assert_eq!(out.len(), request.encoded_len());
+        assert_eq!(out.capacity(), request.encoded_len());
+        assert_eq!(
+            ExportTraceServiceRequest::try_from(&out[..])
+                .expect("Parsing of trace protobuf failed"),
+            request
+        );
+    }
+
+    #[test]
+    fn encode_into_appends_to_buffer() {
+        let response = ExportLogsServiceResponse {
+            partial_success: Some(ExportLogsPartialSuccess {
+                rejected_log_records: 2,
+                error_message: Cow::from("rejected"),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        let mut buf = BytesMut::from(&b"head"[..]);
+        response.encode_into(&mut buf);
+
+        assert_eq!(&buf[..4], b"head");
+        assert_eq!(&buf[4..], &response.encode_to_vec()[..]);
+        assert_eq!(buf.len(), 4 + response.encoded_len());
+    }
+}
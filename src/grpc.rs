@@ -0,0 +1,432 @@
+use std::borrow::Cow;
+use std::fmt;
+
+use bytes::{BufMut, BytesMut};
+use quick_protobuf::{MessageWrite, Writer};
+
+use crate::opentelemetry::logs::ExportLogsServiceResponse;
+use crate::opentelemetry::metrics::ExportMetricsServiceResponse;
+use crate::opentelemetry::profiles::ExportProfilesServiceResponse;
+use crate::opentelemetry::trace::ExportTraceServiceResponse;
+
+pub const GRPC_CONTENT_TYPE: &str = "application/grpc";
+
+/// Size of the prefix in front of every gRPC message: a compressed flag and
+/// a big-endian `u32` length.
+pub const GRPC_FRAME_HEADER_LEN: usize = 5;
+
+/// A message read from a gRPC length-prefixed frame.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GrpcFrame<'a> {
+    /// Whether the message is compressed with the `grpc-encoding` of the
+    /// stream.
+    pub compressed: bool,
+    pub message: &'a [u8],
+}
+
+impl<'a> GrpcFrame<'a> {
+    /// Reads the frame at the start of `bytes`, returning it with the bytes
+    /// that follow it, or `None` if `bytes` does not hold a complete frame yet.
+    pub fn read(bytes: &'a [u8]) -> Result<Option<(Self, &'a [u8])>, crate::Error> {
+        if bytes.len() < GRPC_FRAME_HEADER_LEN {
+            return Ok(None);
+        }
+
+        let compressed = match bytes[0] {
+            0 => false,
+            1 => true,
+            flag => {
+                return Err(crate::Error::InvalidGrpcFrame(format!(
+                    "invalid compressed flag {flag}"
+                )))
+            }
+        };
+        let len = u32::from_be_bytes([bytes[1], bytes[2], bytes[3], bytes[4]]) as usize;
+        let rest = &bytes[GRPC_FRAME_HEADER_LEN..];
+        if rest.len() < len {
+            return Ok(None);
+        }
+
+        let (message, rest) = rest.split_at(len);
+        Ok(Some((
+            GrpcFrame {
+                compressed,
+                message,
+            },
+            rest,
+        )))
+    }
+
+    /// The message, decompressed according to the `grpc-encoding` header,
+    /// failing once it grows past `limit` bytes.
+    pub fn decompress(
+        &self,
+        encoding: Option<&str>,
+        limit: usize,
+    ) -> Result<Cow<'a, [u8]>, crate::Error> {
+        if !self.compressed {
+            return crate::decompress(self.message, None, limit);
+        }
+
+        match encoding {
+            // The flag must not be set when the stream is not compressed
+            None | Some("identity") => Err(crate::Error::InvalidGrpcFrame(
+                "compressed flag set without a grpc-encoding".to_string(),
+            )),
+            Some(encoding) => crate::decompress(self.message, Some(encoding), limit),
+        }
+    }
+}
+
+/// Iterator over the frames of a complete gRPC message stream, e.g. a
+/// request body.
+///
+/// Unlike [`GrpcFrame::read`], a truncated trailing frame is an error.
+#[derive(Debug, Clone)]
+pub struct GrpcFrames<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> GrpcFrames<'a> {
+    pub fn new(bytes: &'a [u8]) -> Self {
+        GrpcFrames { bytes }
+    }
+}
+
+impl<'a> Iterator for GrpcFrames<'a> {
+    type Item = Result<GrpcFrame<'a>, crate::Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.bytes.is_empty() {
+            return None;
+        }
+
+        match GrpcFrame::read(self.bytes) {
+            Ok(Some((frame, rest))) => {
+                self.bytes = rest;
+                Some(Ok(frame))
+            }
+            Ok(None) => {
+                let len = self.bytes.len();
+                self.bytes = &[];
+                Some(Err(crate::Error::InvalidGrpcFrame(format!(
+                    "truncated frame of {len} bytes"
+                ))))
+            }
+            Err(e) => {
+                self.bytes = &[];
+                Some(Err(e))
+            }
+        }
+    }
+}
+
+/// Appends `message`, uncompressed, to `buf` as a gRPC length-prefixed frame.
+///
+/// # Panics
+///
+/// If `message` is longer than the 4 GiB a frame header can describe.
+pub fn write_grpc_frame(message: &[u8], buf: &mut BytesMut) {
+    write_frame(false, message, buf)
+}
+
+/// Appends `message`, already compressed with the `grpc-encoding` of the
+/// stream, to `buf` as a frame with the compressed flag set.
+///
+/// # Panics
+///
+/// If `message` is longer than the 4 GiB a frame header can describe.
+pub fn write_compressed_grpc_frame(message: &[u8], buf: &mut BytesMut) {
+    write_frame(true, message, buf)
+}
+
+fn write_frame(compressed: bool, message: &[u8], buf: &mut BytesMut) {
+    let len = u32::try_from(message.len()).expect("gRPC messages are at most 4 GiB");
+    buf.reserve(GRPC_FRAME_HEADER_LEN + message.len());
+    buf.put_u8(compressed as u8);
+    buf.put_u32(len);
+    buf.put_slice(message);
+}
+
+fn write_grpc_message<M: MessageWrite>(message: &M, buf: &mut BytesMut) {
+    let len = message.get_size();
+    buf.reserve(GRPC_FRAME_HEADER_LEN + len);
+    buf.put_u8(0);
+    buf.put_u32(u32::try_from(len).expect("gRPC messages are at most 4 GiB"));
+    message
+        .write_message(&mut Writer::new(buf.writer()))
+        .expect("writing to a BytesMut cannot fail");
+}
+
+/// gRPC status codes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GrpcCode {
+    Ok = 0,
+    Cancelled = 1,
+    Unknown = 2,
+    InvalidArgument = 3,
+    DeadlineExceeded = 4,
+    NotFound = 5,
+    AlreadyExists = 6,
+    PermissionDenied = 7,
+    ResourceExhausted = 8,
+    FailedPrecondition = 9,
+    Aborted = 10,
+    OutOfRange = 11,
+    Unimplemented = 12,
+    Internal = 13,
+    Unavailable = 14,
+    DataLoss = 15,
+    Unauthenticated = 16,
+}
+
+impl GrpcCode {
+    /// Unknown values map to [`GrpcCode::Unknown`].
+    pub fn from_i32(code: i32) -> Self {
+        match code {
+            0 => GrpcCode::Ok,
+            1 => GrpcCode::Cancelled,
+            3 => GrpcCode::InvalidArgument,
+            4 => GrpcCode::DeadlineExceeded,
+            5 => GrpcCode::NotFound,
+            6 => GrpcCode::AlreadyExists,
+            7 => GrpcCode::PermissionDenied,
+            8 => GrpcCode::ResourceExhausted,
+            9 => GrpcCode::FailedPrecondition,
+            10 => GrpcCode::Aborted,
+            11 => GrpcCode::OutOfRange,
+            12 => GrpcCode::Unimplemented,
+            13 => GrpcCode::Internal,
+            14 => GrpcCode::Unavailable,
+            15 => GrpcCode::DataLoss,
+            16 => GrpcCode::Unauthenticated,
+            _ => GrpcCode::Unknown,
+        }
+    }
+
+    /// Parses a code as it appears in the `grpc-status` trailer. Unknown
+    /// values map to [`GrpcCode::Unknown`].
+    pub fn from_trailer(value: &str) -> Self {
+        value
+            .trim()
+            .parse()
+            .map_or(GrpcCode::Unknown, GrpcCode::from_i32)
+    }
+}
+
+impl fmt::Display for GrpcCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", *self as u8)
+    }
+}
+
+/// The status of a gRPC call, sent in the `grpc-status` and `grpc-message`
+/// trailers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GrpcStatus {
+    pub code: GrpcCode,
+    pub message: String,
+}
+
+impl GrpcStatus {
+    pub fn new(code: GrpcCode, message: impl Into<String>) -> Self {
+        GrpcStatus {
+            code,
+            message: message.into(),
+        }
+    }
+
+    pub fn ok() -> Self {
+        GrpcStatus::new(GrpcCode::Ok, "")
+    }
+
+    /// Reads the status from the values of the `grpc-status` and
+    /// `grpc-message` trailers.
+    pub fn from_trailers(status: &str, message: Option<&str>) -> Self {
+        GrpcStatus {
+            code: GrpcCode::from_trailer(status),
+            message: message.map(percent_decode).unwrap_or_default(),
+        }
+    }
+
+    /// Trailer names and values, `grpc-message` only when there is one.
+    ///
+    /// The message is percent-encoded as the gRPC spec requires.
+    pub fn trailers(&self) -> Vec<(&'static str, String)> {
+        let mut trailers = vec![("grpc-status", self.code.to_string())];
+        if !self.message.is_empty() {
+            trailers.push(("grpc-message", percent_encode(&self.message)));
+        }
+        trailers
+    }
+}
+
+impl From<&crate::Error> for GrpcStatus {
+    fn from(error: &crate::Error) -> Self {
+        let code = match error {
+            crate::Error::UnsupportedEncoding(_) => GrpcCode::Unimplemented,
+            crate::Error::MessageTooLarge { .. } | crate::Error::DecompressedTooLarge { .. } => {
+                GrpcCode::ResourceExhausted
+            }
+            crate::Error::InvalidGrpcFrame(_) => GrpcCode::Internal,
+            _ => GrpcCode::InvalidArgument,
+        };
+        GrpcStatus::new(code, error.to_string())
+    }
+}
+
+// Everything but printable ASCII and '%' is encoded
+fn percent_encode(message: &str) -> String {
+    let mut out = String::with_capacity(message.len());
+    for byte in message.bytes() {
+        if (0x20..=0x7e).contains(&byte) && byte != b'%' {
+            out.push(byte as char);
+        } else {
+            out.push_str(&format!("%{byte:02X}"));
+        }
+    }
+    out
+}
+
+// Malformed escapes are kept as they are, per the gRPC spec
+fn percent_decode(message: &str) -> String {
+    let bytes = message.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let escaped = bytes
+            .get(i + 1..i + 3)
+            .filter(|_| bytes[i] == b'%')
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match escaped {
+            Some(byte) => {
+                out.push(byte);
+                i += 3;
+            }
+            None => {
+                out.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+impl ExportMetricsServiceResponse<'_> {
+    /// Appends the response to `buf` as an uncompressed gRPC frame.
+    pub fn encode_grpc(&self, buf: &mut BytesMut) {
+        write_grpc_message(self, buf)
+    }
+}
+
+impl ExportLogsServiceResponse<'_> {
+    /// Appends the response to `buf` as an uncompressed gRPC frame.
+    pub fn encode_grpc(&self, buf: &mut BytesMut) {
+        write_grpc_message(self, buf)
+    }
+}
+
+impl ExportTraceServiceResponse<'_> {
+    /// Appends the response to `buf` as an uncompressed gRPC frame.
+    pub fn encode_grpc(&self, buf: &mut BytesMut) {
+        write_grpc_message(self, buf)
+    }
+}
+
+impl ExportProfilesServiceResponse<'_> {
+    /// Appends the response to `buf` as an uncompressed gRPC frame.
+    pub fn encode_grpc(&self, buf: &mut BytesMut) {
+        write_grpc_message(self, buf)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::opentelemetry::trace::{
+        ExportTracePartialSuccess, ExportTraceServiceRequest, ResourceSpans,
+    };
+
+    #[test]
+    fn read_frames() {
+        let request = ExportTraceServiceRequest {
+            resource_spans: vec![ResourceSpans::default()],
+            ..Default::default()
+        };
+        let mut buf = BytesMut::new();
+        write_grpc_frame(&request.encode_to_vec(), &mut buf);
+        write_grpc_frame(&[], &mut buf);
+
+        let frames = GrpcFrames::new(&buf)
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(frames.len(), 2);
+        assert!(!frames[0].compressed);
+        let message = frames[0].decompress(None, usize::MAX).unwrap();
+        assert_eq!(
+            ExportTraceServiceRequest::try_from(&message[..]).unwrap(),
+            request
+        );
+        assert!(frames[1].message.is_empty());
+
+        // A partial frame is left for the next read, but fails a full body
+        let partial = &buf[..buf.len() - 1];
+        let (_, rest) = GrpcFrame::read(partial).unwrap().unwrap();
+        assert_eq!(GrpcFrame::read(rest).unwrap(), None);
+        assert!(GrpcFrames::new(partial).nth(1).unwrap().is_err());
+
+        assert!(GrpcFrame::read(&[2, 0, 0, 0, 0]).is_err());
+    }
+
+    #[test]
+    fn compressed_flag_requires_encoding() {
+        let frame = GrpcFrame::read(&[1, 0, 0, 0, 1, 0]).unwrap().unwrap().0;
+        assert!(frame.compressed);
+        assert!(matches!(
+            frame.decompress(Some("identity"), usize::MAX),
+            Err(crate::Error::InvalidGrpcFrame(_))
+        ));
+        assert!(matches!(
+            frame.decompress(Some("snappy"), usize::MAX),
+            Err(crate::Error::UnsupportedEncoding(_))
+        ));
+    }
+
+    #[test]
+    fn response_with_trailers() {
+        let response = ExportTraceServiceResponse {
+            partial_success: Some(ExportTracePartialSuccess {
+                rejected_spans: 2,
+                error_message: "dropped".into(),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let mut buf = BytesMut::new();
+        response.encode_grpc(&mut buf);
+        let (frame, rest) = GrpcFrame::read(&buf).unwrap().unwrap();
+        assert!(rest.is_empty());
+        assert_eq!(frame.message, response.encode_to_vec());
+
+        assert_eq!(
+            GrpcStatus::ok().trailers(),
+            vec![("grpc-status", "0".into())]
+        );
+        let status = GrpcStatus::new(GrpcCode::InvalidArgument, "bad span: 100% ✗");
+        let trailers = status.trailers();
+        assert_eq!(trailers[0], ("grpc-status", "3".into()));
+        assert_eq!(
+            trailers[1],
+            ("grpc-message", "bad span: 100%25 %E2%9C%97".into())
+        );
+        assert_eq!(
+            GrpcStatus::from_trailers(&trailers[0].1, Some(&trailers[1].1)),
+            status
+        );
+        assert_eq!(
+            GrpcStatus::from_trailers("99", None).code,
+            GrpcCode::Unknown
+        );
+    }
+}
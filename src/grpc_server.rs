@@ -0,0 +1,552 @@
+use std::convert::Infallible;
+use std::future::{ready, Future, Ready};
+use std::io;
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use bytes::{Bytes, BytesMut};
+use http_body_util::combinators::WithTrailers;
+use http_body_util::{BodyExt, Full, LengthLimitError, Limited};
+use hyper::body::Incoming;
+use hyper::header::{HeaderMap, HeaderName, HeaderValue, CONTENT_TYPE};
+use hyper::service::service_fn;
+use hyper_util::rt::{TokioExecutor, TokioIo, TokioTimer};
+use hyper_util::server::graceful::GracefulShutdown;
+use tokio::net::TcpListener;
+use tokio::task::JoinSet;
+
+use crate::compression::codings;
+use crate::grpc::{GrpcCode, GrpcStatus, GRPC_CONTENT_TYPE};
+use crate::http::{export_signal, Encoding, OtlpHttpResponse, Signal};
+use crate::server::{accept, shutdown_within};
+use crate::{write_grpc_frame, DecodeOptions, ExportHandler, GrpcFrames, ServerConfig};
+
+type GrpcBody = WithTrailers<Full<Bytes>, Ready<Option<Result<HeaderMap, Infallible>>>>;
+
+/// A minimal OTLP/gRPC receiver for logs, metrics and traces, serving the
+/// `Export` method of the collector's `TraceService`, `LogsService` and
+/// `MetricsService` over cleartext HTTP/2.
+///
+/// As with [`crate::Server`], requests are decoded on the blocking thread
+/// pool, resources that fail validation are reported as a partial success
+/// and the rest is passed to the [`ExportHandler`], whose errors are
+/// returned as the gRPC status.
+///
+/// The tests only exercise it with fixed, hand-captured frames, not against
+/// a running collector or gRPC library.
+pub struct GrpcServer {
+    listener: TcpListener,
+    config: ServerConfig,
+}
+
+impl GrpcServer {
+    /// Binds to `config.addr`; OTLP/gRPC receivers usually listen on port
+    /// 4317 rather than the default 4318.
+    pub async fn bind(config: ServerConfig) -> io::Result<Self> {
+        let listener = TcpListener::bind(config.addr).await?;
+        Ok(GrpcServer { listener, config })
+    }
+
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    /// Serves requests until `shutdown` completes, then stops accepting
+    /// connections and waits for the in-flight requests to finish, for at
+    /// most `shutdown_timeout`.
+    ///
+    /// Failing to accept a connection is logged and does not stop the
+    /// server.
+    pub async fn serve<H: ExportHandler>(
+        self,
+        handler: H,
+        shutdown: impl Future<Output = ()>,
+    ) -> io::Result<()> {
+        let handler = Arc::new(handler);
+        let config = Arc::new(self.config);
+        let graceful = GracefulShutdown::new();
+        let mut connections = JoinSet::new();
+        let mut builder = hyper::server::conn::http2::Builder::new(TokioExecutor::new());
+        builder
+            .timer(TokioTimer::new())
+            .keep_alive_interval(config.http2_keep_alive_interval)
+            .keep_alive_timeout(config.http2_keep_alive_timeout);
+        tokio::pin!(shutdown);
+
+        loop {
+            let stream = tokio::select! {
+                stream = accept(&self.listener) => stream,
+                () = &mut shutdown => break,
+            };
+            while connections.try_join_next().is_some() {}
+
+            let handler = handler.clone();
+            let config = config.clone();
+            let service = service_fn(move |request| {
+                let handler = handler.clone();
+                let config = config.clone();
+                async move { Ok::<_, Infallible>(handle(request, &*handler, &config).await) }
+            });
+            let connection = builder.serve_connection(TokioIo::new(stream), service);
+            let connection = graceful.watch(connection);
+            connections.spawn(async move {
+                let _ = connection.await;
+            });
+        }
+
+        shutdown_within(graceful, connections, config.shutdown_timeout).await;
+        Ok(())
+    }
+}
+
+async fn handle<H: ExportHandler>(
+    request: hyper::Request<Incoming>,
+    handler: &H,
+    config: &ServerConfig,
+) -> hyper::Response<GrpcBody> {
+    // gRPC clients expect HTTP 415 for anything that is not gRPC
+    let content_type = request.headers().get(CONTENT_TYPE);
+    let is_grpc = content_type
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| {
+            value == GRPC_CONTENT_TYPE || value.starts_with("application/grpc+proto")
+        });
+    if !is_grpc {
+        return hyper::Response::builder()
+            .status(415)
+            .body(Full::default().with_trailers(ready(None)))
+            .expect("status is valid");
+    }
+
+    let response = tokio::time::timeout(config.request_timeout, export(request, handler, config))
+        .await
+        .unwrap_or_else(|_| {
+            Err(GrpcStatus::new(
+                GrpcCode::DeadlineExceeded,
+                "request timed out",
+            ))
+        });
+    match response {
+        Ok(message) => {
+            let mut body = BytesMut::new();
+            write_grpc_frame(&message, &mut body);
+            hyper::Response::builder()
+                .header(CONTENT_TYPE, GRPC_CONTENT_TYPE)
+                .body(
+                    Full::new(body.freeze())
+                        .with_trailers(ready(Some(Ok(status_headers(&GrpcStatus::ok()))))),
+                )
+                .expect("content type is valid")
+        }
+        // Trailers-only response
+        Err(status) => {
+            let mut response = hyper::Response::new(Full::default().with_trailers(ready(None)));
+            response
+                .headers_mut()
+                .insert(CONTENT_TYPE, HeaderValue::from_static(GRPC_CONTENT_TYPE));
+            response.headers_mut().extend(status_headers(&status));
+            response
+        }
+    }
+}
+
+// Returns the encoded response message
+async fn export<H: ExportHandler>(
+    request: hyper::Request<Incoming>,
+    handler: &H,
+    config: &ServerConfig,
+) -> Result<Vec<u8>, GrpcStatus> {
+    let (parts, body) = request.into_parts();
+    let signal = match parts.uri.path() {
+        "/opentelemetry.proto.collector.trace.v1.TraceService/Export" => Signal::Traces,
+        "/opentelemetry.proto.collector.logs.v1.LogsService/Export" => Signal::Logs,
+        "/opentelemetry.proto.collector.metrics.v1.MetricsService/Export" => Signal::Metrics,
+        path => {
+            return Err(GrpcStatus::new(
+                GrpcCode::Unimplemented,
+                format!("unknown method {path}"),
+            ))
+        }
+    };
+    let encoding = parts
+        .headers
+        .get("grpc-encoding")
+        .and_then(|value| value.to_str().ok())
+        .filter(|encoding| codings(encoding).next().is_some())
+        .map(str::to_owned);
+    if let (Some(encoding), false) = (&encoding, config.compression) {
+        return Err(GrpcStatus::new(
+            GrpcCode::Unimplemented,
+            format!("compression is disabled, got {encoding}"),
+        ));
+    }
+
+    let body = Limited::new(body, config.max_body_bytes)
+        .collect()
+        .await
+        .map_err(|e| {
+            if e.is::<LengthLimitError>() {
+                GrpcStatus::new(
+                    GrpcCode::ResourceExhausted,
+                    format!("body exceeds {} bytes", config.max_body_bytes),
+                )
+            } else {
+                GrpcStatus::new(
+                    GrpcCode::Internal,
+                    format!("failed to read the request: {e}"),
+                )
+            }
+        })?
+        .to_bytes();
+
+    // Decompressing, decoding and validating are CPU-bound
+    let options = config.decode_options.clone();
+    let mut response =
+        tokio::task::spawn_blocking(move || decode(signal, &body, encoding.as_deref(), &options))
+            .await
+            .map_err(|e| GrpcStatus::new(GrpcCode::Internal, e.to_string()))??;
+    if let Some(request) = response.request.take() {
+        handler.export(request).await?;
+    }
+    Ok(response.body)
+}
+
+fn decode(
+    signal: Signal,
+    body: &[u8],
+    encoding: Option<&str>,
+    options: &DecodeOptions,
+) -> Result<OtlpHttpResponse, GrpcStatus> {
+    let mut frames = GrpcFrames::new(body);
+    let frame = match (frames.next(), frames.next()) {
+        (Some(frame), None) => frame.map_err(|e| GrpcStatus::from(&e))?,
+        // Export is unary, empty requests included
+        _ => {
+            return Err(GrpcStatus::new(
+                GrpcCode::Internal,
+                "expected exactly one request message",
+            ))
+        }
+    };
+    let message = frame
+        .decompress(encoding, options.max_total_bytes)
+        .map_err(|e| GrpcStatus::from(&e))?;
+
+    export_signal(signal, &message, Encoding::Protobuf, options).map_err(|e| GrpcStatus::from(&e))
+}
+
+fn status_headers(status: &GrpcStatus) -> HeaderMap {
+    status
+        .trailers()
+        .into_iter()
+        .map(|(name, value)| {
+            let value = HeaderValue::try_from(value).expect("values are percent-encoded");
+            (HeaderName::from_static(name), value)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::opentelemetry::common::InstrumentationScope;
+    use crate::opentelemetry::common::{AnyValue, AnyValueOneOfvalue, KeyValue, Resource};
+    use crate::opentelemetry::trace::{
+        ExportTraceServiceRequest, ResourceSpans, ScopeSpans, Span, SpanKind,
+    };
+    use crate::ExportRequest;
+    use tokio::sync::{mpsc, oneshot};
+
+    struct Channel(mpsc::UnboundedSender<ExportRequest>);
+
+    impl ExportHandler for Channel {
+        async fn export(&self, request: ExportRequest) -> Result<(), GrpcStatus> {
+            match request {
+                ExportRequest::Logs(_) => {
+                    Err(GrpcStatus::new(GrpcCode::PermissionDenied, "no logs, 100%"))
+                }
+                request => {
+                    self.0.send(request).unwrap();
+                    Ok(())
+                }
+            }
+        }
+    }
+
+    async fn serve() -> (
+        SocketAddr,
+        mpsc::UnboundedReceiver<ExportRequest>,
+        oneshot::Sender<()>,
+        tokio::task::JoinHandle<io::Result<()>>,
+    ) {
+        let config = ServerConfig {
+            addr: SocketAddr::from(([127, 0, 0, 1], 0)),
+            ..Default::default()
+        };
+        let server = GrpcServer::bind(config).await.unwrap();
+        let addr = server.local_addr().unwrap();
+        let (requests, received) = mpsc::unbounded_channel();
+        let (stop, stopped) = oneshot::channel::<()>();
+        let serve = tokio::spawn(server.serve(Channel(requests), async {
+            let _ = stopped.await;
+        }));
+        (addr, received, stop, serve)
+    }
+
+    // Sends a request with the headers of a grpc-go client, returning the
+    // response with its body and trailers. Only the headers and the fixed
+    // frames below come from captured traffic: no collector or gRPC library
+    // is involved.
+    async fn call(
+        addr: SocketAddr,
+        path: &str,
+        body: &'static [u8],
+    ) -> (hyper::http::response::Parts, Bytes, HeaderMap) {
+        let stream = tokio::net::TcpStream::connect(addr).await.unwrap();
+        let (mut sender, connection) =
+            hyper::client::conn::http2::handshake(TokioExecutor::new(), TokioIo::new(stream))
+                .await
+                .unwrap();
+        tokio::spawn(connection);
+
+        let request = hyper::Request::post(format!("http://{addr}{path}"))
+            .header("content-type", "application/grpc")
+            .header("te", "trailers")
+            .header("grpc-accept-encoding", "gzip")
+            .header("grpc-timeout", "5000m")
+            .header("user-agent", "OTel-Collector/0.114.0 grpc-go/1.67.1")
+            .body(Full::new(Bytes::from_static(body)))
+            .unwrap();
+        let (parts, body) = sender.send_request(request).await.unwrap().into_parts();
+        let body = body.collect().await.unwrap();
+        let trailers = body.trailers().cloned().unwrap_or_default();
+        (parts, body.to_bytes(), trailers)
+    }
+
+    #[tokio::test]
+    async fn export_fixed_bytes() {
+        let (addr, mut received, stop, serve) = serve().await;
+
+        // A gRPC frame holding one span of a `demo` service
+        let (parts, body, trailers) = call(
+            addr,
+            "/opentelemetry.proto.collector.trace.v1.TraceService/Export",
+            &[
+                0x00, 0x00, 0x00, 0x00, 0x5f, 0x0a, 0x5d, 0x0a, 0x18, 0x0a, 0x16, 0x0a, 0x0c, 0x73,
+                0x65, 0x72, 0x76, 0x69, 0x63, 0x65, 0x2e, 0x6e, 0x61, 0x6d, 0x65, 0x12, 0x06, 0x0a,
+                0x04, 0x64, 0x65, 0x6d, 0x6f, 0x12, 0x41, 0x0a, 0x06, 0x0a, 0x04, 0x64, 0x65, 0x6d,
+                0x6f, 0x12, 0x37, 0x0a, 0x10, 0x5b, 0x8e, 0xff, 0xf7, 0x98, 0x03, 0x81, 0x03, 0xd2,
+                0x69, 0xb6, 0x33, 0x81, 0x3f, 0xc6, 0x0c, 0x12, 0x08, 0xee, 0xe1, 0x9b, 0x7e, 0xc3,
+                0xc1, 0xb1, 0x74, 0x2a, 0x05, 0x47, 0x45, 0x54, 0x20, 0x2f, 0x30, 0x02, 0x39, 0x00,
+                0x48, 0x59, 0xe3, 0xfa, 0xeb, 0x6f, 0x15, 0x41, 0x00, 0x12, 0xf4, 0x1e, 0xfb, 0xeb,
+                0x6f, 0x15,
+            ],
+        )
+        .await;
+        assert_eq!(parts.status, 200);
+        assert_eq!(parts.headers["content-type"], "application/grpc");
+        // An empty ExportTraceServiceResponse
+        assert_eq!(&body[..], &[0, 0, 0, 0, 0]);
+        assert_eq!(trailers["grpc-status"], "0");
+        assert!(trailers.get("grpc-message").is_none());
+
+        let span = Span {
+            trace_id: vec![
+                0x5b, 0x8e, 0xff, 0xf7, 0x98, 0x03, 0x81, 0x03, 0xd2, 0x69, 0xb6, 0x33, 0x81, 0x3f,
+                0xc6, 0x0c,
+            ]
+            .into(),
+            span_id: vec![0xee, 0xe1, 0x9b, 0x7e, 0xc3, 0xc1, 0xb1, 0x74].into(),
+            name: "GET /".into(),
+            kind: SpanKind::SPAN_KIND_SERVER,
+            start_time_unix_nano: 1544712660000000000,
+            end_time_unix_nano: 1544712661000000000,
+            ..Default::default()
+        };
+        let demo = || "demo".into();
+        assert_eq!(
+            received.recv().await,
+            Some(ExportRequest::Traces(ExportTraceServiceRequest {
+                resource_spans: vec![ResourceSpans {
+                    resource: Some(Resource {
+                        attributes: vec![KeyValue {
+                            key: "service.name".into(),
+                            value: Some(AnyValue {
+                                value: AnyValueOneOfvalue::string_value(demo()),
+                                ..Default::default()
+                            }),
+                            ..Default::default()
+                        }],
+                        ..Default::default()
+                    }),
+                    scope_spans: vec![ScopeSpans {
+                        scope: Some(InstrumentationScope {
+                            name: demo(),
+                            ..Default::default()
+                        }),
+                        spans: vec![span],
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }))
+        );
+
+        // Errors are trailers-only responses, with a percent-encoded message
+        let (parts, body, trailers) = call(
+            addr,
+            "/opentelemetry.proto.collector.logs.v1.LogsService/Export",
+            &[0x00, 0x00, 0x00, 0x00, 0x00],
+        )
+        .await;
+        assert_eq!(parts.status, 200);
+        assert_eq!(parts.headers["grpc-status"], "7");
+        assert_eq!(parts.headers["grpc-message"], "no logs, 100%25");
+        assert!(body.is_empty());
+        assert!(trailers.is_empty());
+
+        let (parts, _, _) = call(
+            addr,
+            "/opentelemetry.proto.collector.profiles.v1development.ProfilesService/Export",
+            &[0x00, 0x00, 0x00, 0x00, 0x00],
+        )
+        .await;
+        assert_eq!(parts.headers["grpc-status"], "12");
+
+        stop.send(()).unwrap();
+        serve.await.unwrap().unwrap();
+    }
+
+    struct Stuck;
+
+    impl ExportHandler for Stuck {
+        async fn export(&self, _: ExportRequest) -> Result<(), GrpcStatus> {
+            std::future::pending().await
+        }
+    }
+
+    #[tokio::test]
+    async fn deadlines() {
+        use std::time::Duration;
+
+        let config = ServerConfig {
+            addr: SocketAddr::from(([127, 0, 0, 1], 0)),
+            request_timeout: Duration::from_millis(200),
+            ..Default::default()
+        };
+        let server = GrpcServer::bind(config).await.unwrap();
+        let addr = server.local_addr().unwrap();
+        let (stop, stopped) = oneshot::channel::<()>();
+        let serve = tokio::spawn(server.serve(Stuck, async {
+            let _ = stopped.await;
+        }));
+        let path = "/opentelemetry.proto.collector.trace.v1.TraceService/Export";
+        let (parts, _, _) = call(addr, path, &[0x00, 0x00, 0x00, 0x00, 0x00]).await;
+        assert_eq!(parts.headers["grpc-status"], "4");
+        stop.send(()).unwrap();
+        serve.await.unwrap().unwrap();
+
+        // Requests still in flight are dropped once the shutdown deadline
+        // passes
+        let config = ServerConfig {
+            addr: SocketAddr::from(([127, 0, 0, 1], 0)),
+            shutdown_timeout: Duration::from_millis(100),
+            ..Default::default()
+        };
+        let server = GrpcServer::bind(config).await.unwrap();
+        let addr = server.local_addr().unwrap();
+        let (stop, stopped) = oneshot::channel::<()>();
+        let serve = tokio::spawn(server.serve(Stuck, async {
+            let _ = stopped.await;
+        }));
+        let pending = tokio::spawn(async move {
+            let stream = tokio::net::TcpStream::connect(addr).await.unwrap();
+            let (mut sender, connection) =
+                hyper::client::conn::http2::handshake(TokioExecutor::new(), TokioIo::new(stream))
+                    .await
+                    .unwrap();
+            tokio::spawn(connection);
+            let request = hyper::Request::post(format!("http://{addr}{path}"))
+                .header("content-type", "application/grpc")
+                .body(Full::new(Bytes::from_static(&[
+                    0x00, 0x00, 0x00, 0x00, 0x00,
+                ])))
+                .unwrap();
+            sender.send_request(request).await
+        });
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        stop.send(()).unwrap();
+        tokio::time::timeout(Duration::from_secs(5), serve)
+            .await
+            .unwrap()
+            .unwrap()
+            .unwrap();
+        assert!(pending.await.unwrap().is_err());
+    }
+
+    #[cfg(feature = "client")]
+    #[tokio::test]
+    async fn export_over_grpc() {
+        use crate::opentelemetry::logs::ExportLogsServiceRequest;
+        use crate::{Exporter, ExporterConfig, PartialSuccess, Protocol};
+        use std::time::Duration;
+
+        let (addr, mut received, stop, serve) = serve().await;
+
+        let resource_spans = |schema_url: &'static str| ResourceSpans {
+            scope_spans: vec![ScopeSpans {
+                spans: vec![Span {
+                    name: "span".into(),
+                    ..Default::default()
+                }],
+                schema_url: schema_url.into(),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        // The second resource has an invalid schema URL
+        let request = ExportTraceServiceRequest {
+            resource_spans: vec![resource_spans(""), resource_spans("not a url")],
+            ..Default::default()
+        };
+
+        for compression in [true, false] {
+            let exporter = Exporter::new(ExporterConfig {
+                endpoint: format!("http://{addr}"),
+                protocol: Protocol::Grpc,
+                compression,
+                max_retries: 0,
+                timeout: Duration::from_secs(5),
+                ..Default::default()
+            })
+            .unwrap();
+            let partial_success = exporter.export_traces(&request).await.unwrap();
+            assert_eq!(
+                partial_success,
+                Some(PartialSuccess {
+                    rejected: 1,
+                    error_message: url::ParseError::RelativeUrlWithoutBase.to_string(),
+                })
+            );
+            assert_eq!(
+                received.recv().await,
+                Some(ExportRequest::Traces(ExportTraceServiceRequest {
+                    resource_spans: vec![resource_spans("")],
+                    ..Default::default()
+                }))
+            );
+
+            let result = exporter
+                .export_logs(&ExportLogsServiceRequest::default())
+                .await;
+            assert!(
+                matches!(&result, Err(crate::Error::ExportFailed(message)) if message == "PermissionDenied: no logs, 100%"),
+                "{result:?}"
+            );
+        }
+
+        stop.send(()).unwrap();
+        serve.await.unwrap().unwrap();
+    }
+}
@@ -0,0 +1,581 @@
+use quick_protobuf::{BytesReader, MessageWrite, Writer};
+
+use crate::grpc::{GrpcCode, GrpcStatus};
+use crate::opentelemetry::logs::{
+    ExportLogsPartialSuccess, ExportLogsServiceRequest, ExportLogsServiceResponse, ResourceLogs,
+    Validate as _,
+};
+use crate::opentelemetry::metrics::{
+    ExportMetricsPartialSuccess, ExportMetricsServiceRequest, ExportMetricsServiceResponse,
+    MetricOneOfdata, ResourceMetrics, Validate as _,
+};
+use crate::opentelemetry::trace::{
+    ExportTracePartialSuccess, ExportTraceServiceRequest, ExportTraceServiceResponse,
+    ResourceSpans, Validate as _,
+};
+use crate::{DecodeOptions, IntoOwned};
+
+pub const PROTOBUF_CONTENT_TYPE: &str = "application/x-protobuf";
+pub const JSON_CONTENT_TYPE: &str = "application/json";
+
+/// A request accepted by [`handle_otlp_http`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExportRequest {
+    Traces(ExportTraceServiceRequest<'static>),
+    Logs(ExportLogsServiceRequest<'static>),
+    Metrics(ExportMetricsServiceRequest<'static>),
+}
+
+/// An OTLP/HTTP response, to be written out by whichever HTTP stack
+/// received the request.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OtlpHttpResponse {
+    pub status: u16,
+    pub content_type: &'static str,
+    pub body: Vec<u8>,
+    /// The valid part of the request, set when the status is 200.
+    pub request: Option<ExportRequest>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Encoding {
+    Protobuf,
+    #[cfg(feature = "json")]
+    Json,
+}
+
+impl Encoding {
+    /// Parses a `Content-Type` value, ignoring its parameters.
+    pub(crate) fn from_content_type(value: &str) -> Option<Self> {
+        let value = value.split(';').next().unwrap_or_default().trim();
+        if value.eq_ignore_ascii_case(PROTOBUF_CONTENT_TYPE) {
+            return Some(Encoding::Protobuf);
+        }
+        #[cfg(feature = "json")]
+        if value.eq_ignore_ascii_case(JSON_CONTENT_TYPE) {
+            return Some(Encoding::Json);
+        }
+        None
+    }
+
+    fn content_type(self) -> &'static str {
+        match self {
+            Encoding::Protobuf => PROTOBUF_CONTENT_TYPE,
+            #[cfg(feature = "json")]
+            Encoding::Json => JSON_CONTENT_TYPE,
+        }
+    }
+}
+
+/// Handles an OTLP/HTTP export request with the default [`DecodeOptions`].
+///
+/// See [`handle_otlp_http_with`].
+pub fn handle_otlp_http(
+    method: &str,
+    path: &str,
+    headers: &[(&str, &str)],
+    body: &[u8],
+) -> OtlpHttpResponse {
+    handle_otlp_http_with(method, path, headers, body, &DecodeOptions::default())
+}
+
+/// Handles an OTLP/HTTP export request to `/v1/traces`, `/v1/logs` or
+/// `/v1/metrics`.
+///
+/// The body is decompressed according to `Content-Encoding` and decoded
+/// according to `Content-Type`, upgrading the deprecated fields of pre-1.0
+/// protobuf payloads as `decode_legacy_with` does. Resources that fail validation are dropped
+/// and reported as a partial success; the rest is returned in
+/// [`OtlpHttpResponse::request`]. Errors are answered with a
+/// `google.rpc.Status` body: 400 for malformed requests, 413 for bodies over
+/// `options.max_total_bytes` and 415 for unsupported content types and
+/// encodings.
+pub fn handle_otlp_http_with(
+    method: &str,
+    path: &str,
+    headers: &[(&str, &str)],
+    body: &[u8],
+    options: &DecodeOptions,
+) -> OtlpHttpResponse {
+    let header = |name: &str| {
+        headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| *value)
+    };
+
+    let content_type = header("content-type").unwrap_or_default();
+    let Some(encoding) = Encoding::from_content_type(content_type) else {
+        let status = GrpcStatus::new(
+            GrpcCode::InvalidArgument,
+            format!("unsupported content type {content_type:?}"),
+        );
+        return error_response(415, &status, Encoding::Protobuf);
+    };
+
+    let path = path.split('?').next().unwrap_or_default();
+    let signal = match path {
+        "/v1/traces" => Signal::Traces,
+        "/v1/logs" => Signal::Logs,
+        "/v1/metrics" => Signal::Metrics,
+        _ => {
+            let status = GrpcStatus::new(GrpcCode::NotFound, format!("no OTLP signal at {path}"));
+            return error_response(404, &status, encoding);
+        }
+    };
+    if !method.eq_ignore_ascii_case("POST") {
+        let status = GrpcStatus::new(GrpcCode::Unimplemented, format!("method {method}"));
+        return error_response(405, &status, encoding);
+    }
+
+    let body = match crate::decompress(body, header("content-encoding"), options.max_total_bytes) {
+        Ok(body) => body,
+        Err(e) => return decode_error_response(&e, encoding),
+    };
+    export_signal(signal, &body, encoding, options)
+        .unwrap_or_else(|e| decode_error_response(&e, encoding))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Signal {
+    Traces,
+    Logs,
+    Metrics,
+}
+
+/// Decodes an uncompressed request for `signal` and drops the resources
+/// that fail validation, answering with a 200 and the partial success.
+pub(crate) fn export_signal(
+    signal: Signal,
+    body: &[u8],
+    encoding: Encoding,
+    options: &DecodeOptions,
+) -> Result<OtlpHttpResponse, crate::Error> {
+    match signal {
+        Signal::Traces => export::<ExportTraceServiceRequest>(body, encoding, options),
+        Signal::Logs => export::<ExportLogsServiceRequest>(body, encoding, options),
+        Signal::Metrics => export::<ExportMetricsServiceRequest>(body, encoding, options),
+    }
+}
+
+fn decode_error_response(error: &crate::Error, encoding: Encoding) -> OtlpHttpResponse {
+    let status = match error {
+        crate::Error::MessageTooLarge { .. } | crate::Error::DecompressedTooLarge { .. } => 413,
+        crate::Error::UnsupportedEncoding(_) => 415,
+        _ => 400,
+    };
+    error_response(status, &GrpcStatus::from(error), encoding)
+}
+
+pub(crate) fn error_response(
+    status: u16,
+    rpc_status: &GrpcStatus,
+    encoding: Encoding,
+) -> OtlpHttpResponse {
+    let body = match encoding {
+        Encoding::Protobuf => encode_rpc_status(rpc_status),
+        #[cfg(feature = "json")]
+        Encoding::Json => serde_json::json!({
+            "code": rpc_status.code as i32,
+            "message": rpc_status.message,
+        })
+        .to_string()
+        .into_bytes(),
+    };
+
+    OtlpHttpResponse {
+        status,
+        content_type: encoding.content_type(),
+        body,
+        request: None,
+    }
+}
+
+// google.rpc.Status, without details
+fn encode_rpc_status(rpc_status: &GrpcStatus) -> Vec<u8> {
+    let mut body = vec![];
+    let mut w = Writer::new(&mut body);
+    let code = rpc_status.code as i32;
+    if code != 0 {
+        w.write_with_tag(8, |w| w.write_int32(code))
+            .expect("writing to a Vec cannot fail");
+    }
+    if !rpc_status.message.is_empty() {
+        w.write_with_tag(18, |w| w.write_string(&rpc_status.message))
+            .expect("writing to a Vec cannot fail");
+    }
+    body
+}
+
+/// Reads an encoded `google.rpc.Status`, ignoring its details.
+#[cfg_attr(not(feature = "client"), allow(dead_code))]
+pub(crate) fn decode_rpc_status(bytes: &[u8]) -> Result<GrpcStatus, crate::Error> {
+    let mut reader = BytesReader::from_bytes(bytes);
+    let mut status = GrpcStatus::new(GrpcCode::Ok, "");
+    while !reader.is_eof() {
+        match reader.next_tag(bytes)? {
+            8 => status.code = GrpcCode::from_i32(reader.read_int32(bytes)?),
+            18 => status.message = reader.read_string(bytes)?.to_string(),
+            tag => reader.read_unknown(bytes, tag)?,
+        }
+    }
+    Ok(status)
+}
+
+fn export<R: Export>(
+    body: &[u8],
+    encoding: Encoding,
+    options: &DecodeOptions,
+) -> Result<OtlpHttpResponse, crate::Error> {
+    let mut request = match encoding {
+        Encoding::Protobuf => R::decode(body, options)?,
+        #[cfg(feature = "json")]
+        Encoding::Json => R::decode_json(body, options)?,
+    };
+
+    let mut rejected = 0;
+    let mut error_message = String::new();
+    request
+        .resources()
+        .retain(|resource| match R::validate(resource) {
+            Ok(()) => true,
+            Err(e) => {
+                rejected += R::count(resource);
+                if error_message.is_empty() {
+                    error_message = e.to_string();
+                }
+                false
+            }
+        });
+
+    let body = match encoding {
+        Encoding::Protobuf => crate::encode::encode_to_vec(&R::response(rejected, error_message)),
+        #[cfg(feature = "json")]
+        Encoding::Json => R::response_json(rejected, error_message).into_bytes(),
+    };
+    Ok(OtlpHttpResponse {
+        status: 200,
+        content_type: encoding.content_type(),
+        body,
+        request: Some(request.into_export_request()),
+    })
+}
+
+// What `handle_otlp_http` needs from each signal
+trait Export: Sized {
+    type Resource;
+    type Response: MessageWrite;
+
+    fn decode(body: &[u8], options: &DecodeOptions) -> Result<Self, crate::Error>;
+    #[cfg(feature = "json")]
+    fn decode_json(body: &[u8], options: &DecodeOptions) -> Result<Self, crate::Error>;
+    fn resources(&mut self) -> &mut Vec<Self::Resource>;
+    fn validate(resource: &Self::Resource) -> Result<(), crate::Error>;
+    /// Number of items a rejected resource counts for in the partial success.
+    fn count(resource: &Self::Resource) -> i64;
+    fn response(rejected: i64, error_message: String) -> Self::Response;
+    #[cfg(feature = "json")]
+    fn response_json(rejected: i64, error_message: String) -> String;
+    fn into_export_request(self) -> ExportRequest;
+}
+
+impl Export for ExportTraceServiceRequest<'static> {
+    type Resource = ResourceSpans<'static>;
+    type Response = ExportTraceServiceResponse<'static>;
+
+    fn decode(body: &[u8], options: &DecodeOptions) -> Result<Self, crate::Error> {
+        Ok(ExportTraceServiceRequest::decode_legacy_with(body, options)?.into_owned())
+    }
+
+    #[cfg(feature = "json")]
+    fn decode_json(body: &[u8], options: &DecodeOptions) -> Result<Self, crate::Error> {
+        ExportTraceServiceRequest::from_json_with(body, options)
+    }
+
+    fn resources(&mut self) -> &mut Vec<Self::Resource> {
+        &mut self.resource_spans
+    }
+
+    fn validate(resource: &Self::Resource) -> Result<(), crate::Error> {
+        resource.validate()
+    }
+
+    fn count(resource: &Self::Resource) -> i64 {
+        resource
+            .scope_spans
+            .iter()
+            .map(|scope_spans| scope_spans.spans.len() as i64)
+            .sum()
+    }
+
+    fn response(rejected: i64, error_message: String) -> Self::Response {
+        ExportTraceServiceResponse {
+            partial_success: (rejected > 0).then(|| ExportTracePartialSuccess {
+                rejected_spans: rejected,
+                error_message: error_message.into(),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[cfg(feature = "json")]
+    fn response_json(rejected: i64, error_message: String) -> String {
+        Self::response(rejected, error_message).to_json()
+    }
+
+    fn into_export_request(self) -> ExportRequest {
+        ExportRequest::Traces(self)
+    }
+}
+
+impl Export for ExportLogsServiceRequest<'static> {
+    type Resource = ResourceLogs<'static>;
+    type Response = ExportLogsServiceResponse<'static>;
+
+    fn decode(body: &[u8], options: &DecodeOptions) -> Result<Self, crate::Error> {
+        Ok(ExportLogsServiceRequest::decode_legacy_with(body, options)?.into_owned())
+    }
+
+    #[cfg(feature = "json")]
+    fn decode_json(body: &[u8], options: &DecodeOptions) -> Result<Self, crate::Error> {
+        ExportLogsServiceRequest::from_json_with(body, options)
+    }
+
+    fn resources(&mut self) -> &mut Vec<Self::Resource> {
+        &mut self.resource_logs
+    }
+
+    fn validate(resource: &Self::Resource) -> Result<(), crate::Error> {
+        resource.validate()
+    }
+
+    fn count(resource: &Self::Resource) -> i64 {
+        resource
+            .scope_logs
+            .iter()
+            .map(|scope_logs| scope_logs.log_records.len() as i64)
+            .sum()
+    }
+
+    fn response(rejected: i64, error_message: String) -> Self::Response {
+        ExportLogsServiceResponse {
+            partial_success: (rejected > 0).then(|| ExportLogsPartialSuccess {
+                rejected_log_records: rejected,
+                error_message: error_message.into(),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[cfg(feature = "json")]
+    fn response_json(rejected: i64, error_message: String) -> String {
+        Self::response(rejected, error_message).to_json()
+    }
+
+    fn into_export_request(self) -> ExportRequest {
+        ExportRequest::Logs(self)
+    }
+}
+
+impl Export for ExportMetricsServiceRequest<'static> {
+    type Resource = ResourceMetrics<'static>;
+    type Response = ExportMetricsServiceResponse<'static>;
+
+    fn decode(body: &[u8], options: &DecodeOptions) -> Result<Self, crate::Error> {
+        Ok(ExportMetricsServiceRequest::decode_legacy_with(body, options)?.into_owned())
+    }
+
+    #[cfg(feature = "json")]
+    fn decode_json(body: &[u8], options: &DecodeOptions) -> Result<Self, crate::Error> {
+        ExportMetricsServiceRequest::from_json_with(body, options)
+    }
+
+    fn resources(&mut self) -> &mut Vec<Self::Resource> {
+        &mut self.resource_metrics
+    }
+
+    fn validate(resource: &Self::Resource) -> Result<(), crate::Error> {
+        resource.validate()
+    }
+
+    // Partial successes count data points, not metrics
+    fn count(resource: &Self::Resource) -> i64 {
+        resource
+            .scope_metrics
+            .iter()
+            .flat_map(|scope_metrics| &scope_metrics.metrics)
+            .map(|metric| match &metric.data {
+                MetricOneOfdata::gauge(gauge) => gauge.data_points.len(),
+                MetricOneOfdata::sum(sum) => sum.data_points.len(),
+                MetricOneOfdata::histogram(histogram) => histogram.data_points.len(),
+                MetricOneOfdata::exponential_histogram(histogram) => histogram.data_points.len(),
+                MetricOneOfdata::summary(summary) => summary.data_points.len(),
+                MetricOneOfdata::None => 0,
+            } as i64)
+            .sum()
+    }
+
+    fn response(rejected: i64, error_message: String) -> Self::Response {
+        ExportMetricsServiceResponse {
+            partial_success: (rejected > 0).then(|| ExportMetricsPartialSuccess {
+                rejected_data_points: rejected,
+                error_message: error_message.into(),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[cfg(feature = "json")]
+    fn response_json(rejected: i64, error_message: String) -> String {
+        Self::response(rejected, error_message).to_json()
+    }
+
+    fn into_export_request(self) -> ExportRequest {
+        ExportRequest::Metrics(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::opentelemetry::logs::{LogRecord, ScopeLogs};
+    use crate::opentelemetry::trace::{ScopeSpans, Span};
+    use quick_protobuf::MessageRead;
+
+    fn traces() -> ExportTraceServiceRequest<'static> {
+        let resource_spans = |schema_url: &'static str| ResourceSpans {
+            scope_spans: vec![ScopeSpans {
+                spans: vec![Span::default(); 2],
+                schema_url: schema_url.into(),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        ExportTraceServiceRequest {
+            // The second resource has an invalid schema URL
+            resource_spans: vec![resource_spans(""), resource_spans("not a url")],
+            ..Default::default()
+        }
+    }
+
+    const PROTOBUF: &[(&str, &str)] = &[("Content-Type", "application/x-protobuf")];
+
+    #[test]
+    fn partial_success() {
+        let body = traces().encode_to_vec();
+        let response = handle_otlp_http("POST", "/v1/traces", PROTOBUF, &body);
+        assert_eq!(response.status, 200);
+        assert_eq!(response.content_type, PROTOBUF_CONTENT_TYPE);
+
+        let mut accepted = traces();
+        accepted.resource_spans.truncate(1);
+        assert_eq!(response.request, Some(ExportRequest::Traces(accepted)));
+
+        let mut reader = quick_protobuf::BytesReader::from_bytes(&response.body);
+        let decoded = ExportTraceServiceResponse::from_reader(&mut reader, &response.body);
+        let partial_success = decoded.unwrap().partial_success.unwrap();
+        assert_eq!(partial_success.rejected_spans, 2);
+        assert!(!partial_success.error_message.is_empty());
+    }
+
+    #[test]
+    fn full_success() {
+        let request = ExportLogsServiceRequest {
+            resource_logs: vec![ResourceLogs {
+                scope_logs: vec![ScopeLogs {
+                    log_records: vec![LogRecord::default()],
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        let headers = [("content-type", "application/x-protobuf; charset=binary")];
+        let response = handle_otlp_http("POST", "/v1/logs", &headers, &request.encode_to_vec());
+        assert_eq!(response.status, 200);
+        assert!(response.body.is_empty());
+        assert_eq!(response.request, Some(ExportRequest::Logs(request)));
+    }
+
+    #[test]
+    fn legacy_scopes() {
+        // A pre-1.0 request, with its scope as `instrumentation_library_logs`
+        let scope_logs = ScopeLogs {
+            log_records: vec![LogRecord::default()],
+            ..Default::default()
+        };
+        let mut resource = vec![];
+        let mut w = quick_protobuf::Writer::new(&mut resource);
+        w.write_with_tag(1000 << 3 | 2, |w| w.write_message(&scope_logs))
+            .unwrap();
+        let mut body = vec![];
+        let mut w = quick_protobuf::Writer::new(&mut body);
+        w.write_with_tag(10, |w| w.write_bytes(&resource)).unwrap();
+
+        let response = handle_otlp_http("POST", "/v1/logs", PROTOBUF, &body);
+        assert_eq!(response.status, 200);
+        let Some(ExportRequest::Logs(request)) = response.request else {
+            panic!("expected logs, got {:?}", response.request);
+        };
+        assert_eq!(request.resource_logs[0].scope_logs, [scope_logs]);
+    }
+
+    #[test]
+    fn errors() {
+        let body = traces().encode_to_vec();
+
+        let response = handle_otlp_http("POST", "/v1/traces", &[], &body);
+        assert_eq!(response.status, 415);
+        let response = handle_otlp_http("POST", "/v1/spans", PROTOBUF, &body);
+        assert_eq!(response.status, 404);
+        let response = handle_otlp_http("GET", "/v1/traces", PROTOBUF, &body);
+        assert_eq!(response.status, 405);
+
+        let response = handle_otlp_http("POST", "/v1/metrics", PROTOBUF, &[0xff]);
+        assert_eq!(response.status, 400);
+        assert_eq!(response.request, None);
+        let status = decode_rpc_status(&response.body).unwrap();
+        assert_eq!(status.code, GrpcCode::InvalidArgument);
+        assert!(!status.message.is_empty());
+
+        let options = DecodeOptions {
+            max_total_bytes: 4,
+            ..Default::default()
+        };
+        let response = handle_otlp_http_with("POST", "/v1/traces", PROTOBUF, &body, &options);
+        assert_eq!(response.status, 413);
+
+        let headers = [PROTOBUF[0], ("Content-Encoding", "br")];
+        let response = handle_otlp_http("POST", "/v1/traces", &headers, &body);
+        assert_eq!(response.status, 415);
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn json() {
+        let headers = [("Content-Type", "application/json")];
+        let body = traces().to_json();
+        let response = handle_otlp_http("POST", "/v1/traces", &headers, body.as_bytes());
+        assert_eq!(response.status, 200);
+        assert_eq!(response.content_type, JSON_CONTENT_TYPE);
+        let response: serde_json::Value = serde_json::from_slice(&response.body).unwrap();
+        assert_eq!(response["partialSuccess"]["rejectedSpans"], "2");
+
+        let response = handle_otlp_http("POST", "/v1/traces", &headers, b"[");
+        assert_eq!(response.status, 400);
+        let status: serde_json::Value = serde_json::from_slice(&response.body).unwrap();
+        assert_eq!(status["code"], 3);
+
+        let options = DecodeOptions {
+            max_repeated_elements: 1,
+            ..Default::default()
+        };
+        let response =
+            handle_otlp_http_with("POST", "/v1/traces", &headers, body.as_bytes(), &options);
+        assert_eq!(response.status, 400);
+        assert_eq!(response.request, None);
+    }
+}
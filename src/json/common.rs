@@ -0,0 +1,188 @@
+use serde_json::Value;
+
+use crate::json::{from_f64, to_base64, Fields, FromJson, Object, ToJson};
+use crate::opentelemetry::common::{
+    AnyValue, AnyValueOneOfvalue, ArrayValue, EntityRef, InstrumentationScope, KeyValue,
+    KeyValueList, Resource,
+};
+
+impl FromJson for Resource<'static> {
+    fn from_json(value: &Value) -> Result<Self, crate::Error> {
+        let fields = Fields::new(value)?;
+        Ok(Resource {
+            attributes: fields.messages("attributes")?,
+            dropped_attributes_count: fields.u32("dropped_attributes_count")?,
+            entity_refs: fields.messages("entity_refs")?,
+            unknown_fields: Default::default(),
+        })
+    }
+}
+
+impl FromJson for EntityRef<'static> {
+    fn from_json(value: &Value) -> Result<Self, crate::Error> {
+        let fields = Fields::new(value)?;
+        Ok(EntityRef {
+            schema_url: fields.string("schema_url")?,
+            type_pb: fields.string("type")?,
+            id_keys: fields.strings("id_keys")?,
+            description_keys: fields.strings("description_keys")?,
+            unknown_fields: Default::default(),
+        })
+    }
+}
+
+impl FromJson for InstrumentationScope<'static> {
+    fn from_json(value: &Value) -> Result<Self, crate::Error> {
+        let fields = Fields::new(value)?;
+        Ok(InstrumentationScope {
+            name: fields.string("name")?,
+            version: fields.string("version")?,
+            attributes: fields.messages("attributes")?,
+            dropped_attributes_count: fields.u32("dropped_attributes_count")?,
+            unknown_fields: Default::default(),
+        })
+    }
+}
+
+impl FromJson for KeyValue<'static> {
+    fn from_json(value: &Value) -> Result<Self, crate::Error> {
+        let fields = Fields::new(value)?;
+        Ok(KeyValue {
+            key: fields.string("key")?,
+            value: fields.message("value")?,
+            unknown_fields: Default::default(),
+        })
+    }
+}
+
+impl FromJson for KeyValueList<'static> {
+    fn from_json(value: &Value) -> Result<Self, crate::Error> {
+        let fields = Fields::new(value)?;
+        Ok(KeyValueList {
+            values: fields.messages("values")?,
+            unknown_fields: Default::default(),
+        })
+    }
+}
+
+impl FromJson for ArrayValue<'static> {
+    fn from_json(value: &Value) -> Result<Self, crate::Error> {
+        let fields = Fields::new(value)?;
+        Ok(ArrayValue {
+            values: fields.messages("values")?,
+            unknown_fields: Default::default(),
+        })
+    }
+}
+
+impl FromJson for AnyValue<'static> {
+    fn from_json(value: &Value) -> Result<Self, crate::Error> {
+        let fields = Fields::new(value)?;
+
+        let value = if fields.get("string_value").is_some() {
+            AnyValueOneOfvalue::string_value(fields.string("string_value")?)
+        } else if fields.get("bool_value").is_some() {
+            AnyValueOneOfvalue::bool_value(fields.bool("bool_value")?)
+        } else if fields.get("int_value").is_some() {
+            AnyValueOneOfvalue::int_value(fields.i64("int_value")?)
+        } else if fields.get("double_value").is_some() {
+            AnyValueOneOfvalue::double_value(fields.f64("double_value")?)
+        } else if let Some(array) = fields.message("array_value")? {
+            AnyValueOneOfvalue::array_value(array)
+        } else if let Some(kvlist) = fields.message("kvlist_value")? {
+            AnyValueOneOfvalue::kvlist_value(kvlist)
+        } else if fields.get("bytes_value").is_some() {
+            AnyValueOneOfvalue::bytes_value(fields.base64("bytes_value")?)
+        } else {
+            AnyValueOneOfvalue::None
+        };
+
+        Ok(AnyValue {
+            value,
+            unknown_fields: Default::default(),
+        })
+    }
+}
+
+impl ToJson for Resource<'_> {
+    fn to_json(&self) -> Value {
+        Object::new()
+            .messages("attributes", &self.attributes)
+            .u32("dropped_attributes_count", self.dropped_attributes_count)
+            .messages("entity_refs", &self.entity_refs)
+            .build()
+    }
+}
+
+impl ToJson for EntityRef<'_> {
+    fn to_json(&self) -> Value {
+        Object::new()
+            .string("schema_url", &self.schema_url)
+            .string("type", &self.type_pb)
+            .strings("id_keys", &self.id_keys)
+            .strings("description_keys", &self.description_keys)
+            .build()
+    }
+}
+
+impl ToJson for InstrumentationScope<'_> {
+    fn to_json(&self) -> Value {
+        Object::new()
+            .string("name", &self.name)
+            .string("version", &self.version)
+            .messages("attributes", &self.attributes)
+            .u32("dropped_attributes_count", self.dropped_attributes_count)
+            .build()
+    }
+}
+
+impl ToJson for KeyValue<'_> {
+    fn to_json(&self) -> Value {
+        Object::new()
+            .string("key", &self.key)
+            .message("value", self.value.as_ref())
+            .build()
+    }
+}
+
+impl ToJson for KeyValueList<'_> {
+    fn to_json(&self) -> Value {
+        Object::new().messages("values", &self.values).build()
+    }
+}
+
+impl ToJson for ArrayValue<'_> {
+    fn to_json(&self) -> Value {
+        Object::new().messages("values", &self.values).build()
+    }
+}
+
+impl ToJson for AnyValue<'_> {
+    fn to_json(&self) -> Value {
+        // The set oneof field is written even when it holds its default value
+        let object = Object::new();
+        match &self.value {
+            AnyValueOneOfvalue::string_value(value) => {
+                object.value("string_value", Value::from(value.as_ref()))
+            }
+            AnyValueOneOfvalue::bool_value(value) => {
+                object.value("bool_value", Value::from(*value))
+            }
+            AnyValueOneOfvalue::int_value(value) => {
+                object.value("int_value", Value::from(value.to_string()))
+            }
+            AnyValueOneOfvalue::double_value(value) => {
+                object.value("double_value", from_f64(*value))
+            }
+            AnyValueOneOfvalue::array_value(value) => object.value("array_value", value.to_json()),
+            AnyValueOneOfvalue::kvlist_value(value) => {
+                object.value("kvlist_value", value.to_json())
+            }
+            AnyValueOneOfvalue::bytes_value(value) => {
+                object.value("bytes_value", Value::from(to_base64(value)))
+            }
+            AnyValueOneOfvalue::None => object,
+        }
+        .build()
+    }
+}
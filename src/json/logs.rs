@@ -0,0 +1,261 @@
+use serde_json::Value;
+
+use crate::descriptor;
+use crate::json::{Enumeration, Fields, FromJson, Object, ToJson};
+use crate::opentelemetry::logs::{
+    ExportLogsPartialSuccess, ExportLogsServiceRequest, ExportLogsServiceResponse, LogRecord,
+    LogsData, ResourceLogs, ScopeLogs, SeverityNumber,
+};
+use crate::DecodeOptions;
+
+impl ExportLogsServiceRequest<'static> {
+    /// Decodes a request from its OTLP/JSON encoding.
+    pub fn from_json(json: &[u8]) -> Result<Self, crate::Error> {
+        crate::json::decode(json)
+    }
+
+    /// Decodes a request from its OTLP/JSON encoding, enforcing the given
+    /// limits.
+    pub fn from_json_with(json: &[u8], options: &DecodeOptions) -> Result<Self, crate::Error> {
+        crate::json::decode_with(json, options, &descriptor::EXPORT_LOGS_SERVICE_REQUEST)
+    }
+}
+
+impl ExportLogsServiceResponse<'static> {
+    /// Decodes a response from its OTLP/JSON encoding.
+    pub fn from_json(json: &[u8]) -> Result<Self, crate::Error> {
+        crate::json::decode(json)
+    }
+}
+
+impl LogsData<'static> {
+    /// Decodes logs data from its OTLP/JSON encoding.
+    pub fn from_json(json: &[u8]) -> Result<Self, crate::Error> {
+        crate::json::decode(json)
+    }
+}
+
+impl ExportLogsServiceRequest<'_> {
+    /// Encodes the request using OTLP/JSON.
+    pub fn to_json(&self) -> String {
+        crate::json::encode(self, false)
+    }
+
+    /// Encodes the request using OTLP/JSON, pretty-printed.
+    pub fn to_json_pretty(&self) -> String {
+        crate::json::encode(self, true)
+    }
+}
+
+impl ExportLogsServiceResponse<'_> {
+    /// Encodes the response using OTLP/JSON.
+    pub fn to_json(&self) -> String {
+        crate::json::encode(self, false)
+    }
+
+    /// Encodes the response using OTLP/JSON, pretty-printed.
+    pub fn to_json_pretty(&self) -> String {
+        crate::json::encode(self, true)
+    }
+}
+
+impl LogsData<'_> {
+    /// Encodes the logs data using OTLP/JSON.
+    pub fn to_json(&self) -> String {
+        crate::json::encode(self, false)
+    }
+
+    /// Encodes the logs data using OTLP/JSON, pretty-printed.
+    pub fn to_json_pretty(&self) -> String {
+        crate::json::encode(self, true)
+    }
+}
+
+impl Enumeration for SeverityNumber {
+    fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "SEVERITY_NUMBER_UNSPECIFIED" => SeverityNumber::SEVERITY_NUMBER_UNSPECIFIED,
+            "SEVERITY_NUMBER_TRACE" => SeverityNumber::SEVERITY_NUMBER_TRACE,
+            "SEVERITY_NUMBER_TRACE2" => SeverityNumber::SEVERITY_NUMBER_TRACE2,
+            "SEVERITY_NUMBER_TRACE3" => SeverityNumber::SEVERITY_NUMBER_TRACE3,
+            "SEVERITY_NUMBER_TRACE4" => SeverityNumber::SEVERITY_NUMBER_TRACE4,
+            "SEVERITY_NUMBER_DEBUG" => SeverityNumber::SEVERITY_NUMBER_DEBUG,
+            "SEVERITY_NUMBER_DEBUG2" => SeverityNumber::SEVERITY_NUMBER_DEBUG2,
+            "SEVERITY_NUMBER_DEBUG3" => SeverityNumber::SEVERITY_NUMBER_DEBUG3,
+            "SEVERITY_NUMBER_DEBUG4" => SeverityNumber::SEVERITY_NUMBER_DEBUG4,
+            "SEVERITY_NUMBER_INFO" => SeverityNumber::SEVERITY_NUMBER_INFO,
+            "SEVERITY_NUMBER_INFO2" => SeverityNumber::SEVERITY_NUMBER_INFO2,
+            "SEVERITY_NUMBER_INFO3" => SeverityNumber::SEVERITY_NUMBER_INFO3,
+            "SEVERITY_NUMBER_INFO4" => SeverityNumber::SEVERITY_NUMBER_INFO4,
+            "SEVERITY_NUMBER_WARN" => SeverityNumber::SEVERITY_NUMBER_WARN,
+            "SEVERITY_NUMBER_WARN2" => SeverityNumber::SEVERITY_NUMBER_WARN2,
+            "SEVERITY_NUMBER_WARN3" => SeverityNumber::SEVERITY_NUMBER_WARN3,
+            "SEVERITY_NUMBER_WARN4" => SeverityNumber::SEVERITY_NUMBER_WARN4,
+            "SEVERITY_NUMBER_ERROR" => SeverityNumber::SEVERITY_NUMBER_ERROR,
+            "SEVERITY_NUMBER_ERROR2" => SeverityNumber::SEVERITY_NUMBER_ERROR2,
+            "SEVERITY_NUMBER_ERROR3" => SeverityNumber::SEVERITY_NUMBER_ERROR3,
+            "SEVERITY_NUMBER_ERROR4" => SeverityNumber::SEVERITY_NUMBER_ERROR4,
+            "SEVERITY_NUMBER_FATAL" => SeverityNumber::SEVERITY_NUMBER_FATAL,
+            "SEVERITY_NUMBER_FATAL2" => SeverityNumber::SEVERITY_NUMBER_FATAL2,
+            "SEVERITY_NUMBER_FATAL3" => SeverityNumber::SEVERITY_NUMBER_FATAL3,
+            "SEVERITY_NUMBER_FATAL4" => SeverityNumber::SEVERITY_NUMBER_FATAL4,
+            _ => return None,
+        })
+    }
+}
+
+impl FromJson for ExportLogsServiceRequest<'static> {
+    fn from_json(value: &Value) -> Result<Self, crate::Error> {
+        let fields = Fields::new(value)?;
+        Ok(ExportLogsServiceRequest {
+            resource_logs: fields.messages("resource_logs")?,
+            unknown_fields: Default::default(),
+        })
+    }
+}
+
+impl FromJson for ExportLogsServiceResponse<'static> {
+    fn from_json(value: &Value) -> Result<Self, crate::Error> {
+        let fields = Fields::new(value)?;
+        Ok(ExportLogsServiceResponse {
+            partial_success: fields.message("partial_success")?,
+            unknown_fields: Default::default(),
+        })
+    }
+}
+
+impl FromJson for ExportLogsPartialSuccess<'static> {
+    fn from_json(value: &Value) -> Result<Self, crate::Error> {
+        let fields = Fields::new(value)?;
+        Ok(ExportLogsPartialSuccess {
+            rejected_log_records: fields.i64("rejected_log_records")?,
+            error_message: fields.string("error_message")?,
+            unknown_fields: Default::default(),
+        })
+    }
+}
+
+impl FromJson for LogsData<'static> {
+    fn from_json(value: &Value) -> Result<Self, crate::Error> {
+        let fields = Fields::new(value)?;
+        Ok(LogsData {
+            resource_logs: fields.messages("resource_logs")?,
+            unknown_fields: Default::default(),
+        })
+    }
+}
+
+impl FromJson for ResourceLogs<'static> {
+    fn from_json(value: &Value) -> Result<Self, crate::Error> {
+        let fields = Fields::new(value)?;
+        Ok(ResourceLogs {
+            resource: fields.message("resource")?,
+            scope_logs: fields.messages("scope_logs")?,
+            schema_url: fields.string("schema_url")?,
+            unknown_fields: Default::default(),
+        })
+    }
+}
+
+impl FromJson for ScopeLogs<'static> {
+    fn from_json(value: &Value) -> Result<Self, crate::Error> {
+        let fields = Fields::new(value)?;
+        Ok(ScopeLogs {
+            scope: fields.message("scope")?,
+            log_records: fields.messages("log_records")?,
+            schema_url: fields.string("schema_url")?,
+            unknown_fields: Default::default(),
+        })
+    }
+}
+
+impl FromJson for LogRecord<'static> {
+    fn from_json(value: &Value) -> Result<Self, crate::Error> {
+        let fields = Fields::new(value)?;
+        Ok(LogRecord {
+            time_unix_nano: fields.u64("time_unix_nano")?,
+            observed_time_unix_nano: fields.u64("observed_time_unix_nano")?,
+            severity_number: fields.enumeration("severity_number")?,
+            severity_text: fields.string("severity_text")?,
+            body: fields.message("body")?,
+            attributes: fields.messages("attributes")?,
+            dropped_attributes_count: fields.u32("dropped_attributes_count")?,
+            flags: fields.u32("flags")?,
+            trace_id: fields.hex("trace_id")?,
+            span_id: fields.hex("span_id")?,
+            event_name: fields.string("event_name")?,
+            unknown_fields: Default::default(),
+        })
+    }
+}
+
+impl ToJson for ExportLogsServiceRequest<'_> {
+    fn to_json(&self) -> Value {
+        Object::new()
+            .messages("resource_logs", &self.resource_logs)
+            .build()
+    }
+}
+
+impl ToJson for ExportLogsServiceResponse<'_> {
+    fn to_json(&self) -> Value {
+        Object::new()
+            .message("partial_success", self.partial_success.as_ref())
+            .build()
+    }
+}
+
+impl ToJson for ExportLogsPartialSuccess<'_> {
+    fn to_json(&self) -> Value {
+        Object::new()
+            .i64("rejected_log_records", self.rejected_log_records)
+            .string("error_message", &self.error_message)
+            .build()
+    }
+}
+
+impl ToJson for LogsData<'_> {
+    fn to_json(&self) -> Value {
+        Object::new()
+            .messages("resource_logs", &self.resource_logs)
+            .build()
+    }
+}
+
+impl ToJson for ResourceLogs<'_> {
+    fn to_json(&self) -> Value {
+        Object::new()
+            .message("resource", self.resource.as_ref())
+            .messages("scope_logs", &self.scope_logs)
+            .string("schema_url", &self.schema_url)
+            .build()
+    }
+}
+
+impl ToJson for ScopeLogs<'_> {
+    fn to_json(&self) -> Value {
+        Object::new()
+            .message("scope", self.scope.as_ref())
+            .messages("log_records", &self.log_records)
+            .string("schema_url", &self.schema_url)
+            .build()
+    }
+}
+
+impl ToJson for LogRecord<'_> {
+    fn to_json(&self) -> Value {
+        Object::new()
+            .u64("time_unix_nano", self.time_unix_nano)
+            .u64("observed_time_unix_nano", self.observed_time_unix_nano)
+            .i32("severity_number", self.severity_number as i32)
+            .string("severity_text", &self.severity_text)
+            .message("body", self.body.as_ref())
+            .messages("attributes", &self.attributes)
+            .u32("dropped_attributes_count", self.dropped_attributes_count)
+            .u32("flags", self.flags)
+            .hex("trace_id", &self.trace_id)
+            .hex("span_id", &self.span_id)
+            .string("event_name", &self.event_name)
+            .build()
+    }
+}
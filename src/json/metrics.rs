@@ -0,0 +1,611 @@
+use std::borrow::Cow;
+
+use serde_json::Value;
+
+use crate::descriptor;
+use crate::json::{from_f64, Enumeration, Fields, FromJson, Object, ToJson};
+use crate::opentelemetry::metrics::{
+    AggregationTemporality, Exemplar, ExemplarOneOfvalue, ExponentialHistogram,
+    ExponentialHistogramDataPoint, ExponentialHistogramDataPointBuckets,
+    ExportMetricsPartialSuccess, ExportMetricsServiceRequest, ExportMetricsServiceResponse, Gauge,
+    Histogram, HistogramDataPoint, Metric, MetricOneOfdata, MetricsData, NumberDataPoint,
+    NumberDataPointOneOfvalue, ResourceMetrics, ScopeMetrics, Sum, Summary, SummaryDataPoint,
+    SummaryDataPointValueAtQuantile,
+};
+use crate::DecodeOptions;
+
+impl ExportMetricsServiceRequest<'static> {
+    /// Decodes a request from its OTLP/JSON encoding.
+    pub fn from_json(json: &[u8]) -> Result<Self, crate::Error> {
+        crate::json::decode(json)
+    }
+
+    /// Decodes a request from its OTLP/JSON encoding, enforcing the given
+    /// limits.
+    pub fn from_json_with(json: &[u8], options: &DecodeOptions) -> Result<Self, crate::Error> {
+        crate::json::decode_with(json, options, &descriptor::EXPORT_METRICS_SERVICE_REQUEST)
+    }
+}
+
+impl ExportMetricsServiceResponse<'static> {
+    /// Decodes a response from its OTLP/JSON encoding.
+    pub fn from_json(json: &[u8]) -> Result<Self, crate::Error> {
+        crate::json::decode(json)
+    }
+}
+
+impl MetricsData<'static> {
+    /// Decodes metrics data from its OTLP/JSON encoding.
+    pub fn from_json(json: &[u8]) -> Result<Self, crate::Error> {
+        crate::json::decode(json)
+    }
+}
+
+impl ExportMetricsServiceRequest<'_> {
+    /// Encodes the request using OTLP/JSON.
+    pub fn to_json(&self) -> String {
+        crate::json::encode(self, false)
+    }
+
+    /// Encodes the request using OTLP/JSON, pretty-printed.
+    pub fn to_json_pretty(&self) -> String {
+        crate::json::encode(self, true)
+    }
+}
+
+impl ExportMetricsServiceResponse<'_> {
+    /// Encodes the response using OTLP/JSON.
+    pub fn to_json(&self) -> String {
+        crate::json::encode(self, false)
+    }
+
+    /// Encodes the response using OTLP/JSON, pretty-printed.
+    pub fn to_json_pretty(&self) -> String {
+        crate::json::encode(self, true)
+    }
+}
+
+impl MetricsData<'_> {
+    /// Encodes the metrics data using OTLP/JSON.
+    pub fn to_json(&self) -> String {
+        crate::json::encode(self, false)
+    }
+
+    /// Encodes the metrics data using OTLP/JSON, pretty-printed.
+    pub fn to_json_pretty(&self) -> String {
+        crate::json::encode(self, true)
+    }
+}
+
+impl Enumeration for AggregationTemporality {
+    fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "AGGREGATION_TEMPORALITY_UNSPECIFIED" => {
+                AggregationTemporality::AGGREGATION_TEMPORALITY_UNSPECIFIED
+            }
+            "AGGREGATION_TEMPORALITY_DELTA" => {
+                AggregationTemporality::AGGREGATION_TEMPORALITY_DELTA
+            }
+            "AGGREGATION_TEMPORALITY_CUMULATIVE" => {
+                AggregationTemporality::AGGREGATION_TEMPORALITY_CUMULATIVE
+            }
+            _ => return None,
+        })
+    }
+}
+
+impl FromJson for ExportMetricsServiceRequest<'static> {
+    fn from_json(value: &Value) -> Result<Self, crate::Error> {
+        let fields = Fields::new(value)?;
+        Ok(ExportMetricsServiceRequest {
+            resource_metrics: fields.messages("resource_metrics")?,
+            unknown_fields: Default::default(),
+        })
+    }
+}
+
+impl FromJson for ExportMetricsServiceResponse<'static> {
+    fn from_json(value: &Value) -> Result<Self, crate::Error> {
+        let fields = Fields::new(value)?;
+        Ok(ExportMetricsServiceResponse {
+            partial_success: fields.message("partial_success")?,
+            unknown_fields: Default::default(),
+        })
+    }
+}
+
+impl FromJson for ExportMetricsPartialSuccess<'static> {
+    fn from_json(value: &Value) -> Result<Self, crate::Error> {
+        let fields = Fields::new(value)?;
+        Ok(ExportMetricsPartialSuccess {
+            rejected_data_points: fields.i64("rejected_data_points")?,
+            error_message: fields.string("error_message")?,
+            unknown_fields: Default::default(),
+        })
+    }
+}
+
+impl FromJson for MetricsData<'static> {
+    fn from_json(value: &Value) -> Result<Self, crate::Error> {
+        let fields = Fields::new(value)?;
+        Ok(MetricsData {
+            resource_metrics: fields.messages("resource_metrics")?,
+            unknown_fields: Default::default(),
+        })
+    }
+}
+
+impl FromJson for ResourceMetrics<'static> {
+    fn from_json(value: &Value) -> Result<Self, crate::Error> {
+        let fields = Fields::new(value)?;
+        Ok(ResourceMetrics {
+            resource: fields.message("resource")?,
+            scope_metrics: fields.messages("scope_metrics")?,
+            schema_url: fields.string("schema_url")?,
+            unknown_fields: Default::default(),
+        })
+    }
+}
+
+impl FromJson for ScopeMetrics<'static> {
+    fn from_json(value: &Value) -> Result<Self, crate::Error> {
+        let fields = Fields::new(value)?;
+        Ok(ScopeMetrics {
+            scope: fields.message("scope")?,
+            metrics: fields.messages("metrics")?,
+            schema_url: fields.string("schema_url")?,
+            unknown_fields: Default::default(),
+        })
+    }
+}
+
+impl FromJson for Metric<'static> {
+    fn from_json(value: &Value) -> Result<Self, crate::Error> {
+        let fields = Fields::new(value)?;
+
+        let data = if let Some(gauge) = fields.message("gauge")? {
+            MetricOneOfdata::gauge(gauge)
+        } else if let Some(sum) = fields.message("sum")? {
+            MetricOneOfdata::sum(sum)
+        } else if let Some(histogram) = fields.message("histogram")? {
+            MetricOneOfdata::histogram(histogram)
+        } else if let Some(histogram) = fields.message("exponential_histogram")? {
+            MetricOneOfdata::exponential_histogram(histogram)
+        } else if let Some(summary) = fields.message("summary")? {
+            MetricOneOfdata::summary(summary)
+        } else {
+            MetricOneOfdata::None
+        };
+
+        Ok(Metric {
+            name: fields.string("name")?,
+            description: fields.string("description")?,
+            unit: fields.string("unit")?,
+            data,
+            metadata: fields.messages("metadata")?,
+            unknown_fields: Default::default(),
+        })
+    }
+}
+
+impl FromJson for Gauge<'static> {
+    fn from_json(value: &Value) -> Result<Self, crate::Error> {
+        let fields = Fields::new(value)?;
+        Ok(Gauge {
+            data_points: fields.messages("data_points")?,
+            unknown_fields: Default::default(),
+        })
+    }
+}
+
+impl FromJson for Sum<'static> {
+    fn from_json(value: &Value) -> Result<Self, crate::Error> {
+        let fields = Fields::new(value)?;
+        Ok(Sum {
+            data_points: fields.messages("data_points")?,
+            aggregation_temporality: fields.enumeration("aggregation_temporality")?,
+            is_monotonic: fields.bool("is_monotonic")?,
+            unknown_fields: Default::default(),
+        })
+    }
+}
+
+impl FromJson for Histogram<'static> {
+    fn from_json(value: &Value) -> Result<Self, crate::Error> {
+        let fields = Fields::new(value)?;
+        Ok(Histogram {
+            data_points: fields.messages("data_points")?,
+            aggregation_temporality: fields.enumeration("aggregation_temporality")?,
+            unknown_fields: Default::default(),
+        })
+    }
+}
+
+impl FromJson for ExponentialHistogram<'static> {
+    fn from_json(value: &Value) -> Result<Self, crate::Error> {
+        let fields = Fields::new(value)?;
+        Ok(ExponentialHistogram {
+            data_points: fields.messages("data_points")?,
+            aggregation_temporality: fields.enumeration("aggregation_temporality")?,
+            unknown_fields: Default::default(),
+        })
+    }
+}
+
+impl FromJson for Summary<'static> {
+    fn from_json(value: &Value) -> Result<Self, crate::Error> {
+        let fields = Fields::new(value)?;
+        Ok(Summary {
+            data_points: fields.messages("data_points")?,
+            unknown_fields: Default::default(),
+        })
+    }
+}
+
+impl FromJson for NumberDataPoint<'static> {
+    fn from_json(value: &Value) -> Result<Self, crate::Error> {
+        let fields = Fields::new(value)?;
+
+        let value = if fields.get("as_double").is_some() {
+            NumberDataPointOneOfvalue::as_double(fields.f64("as_double")?)
+        } else if fields.get("as_int").is_some() {
+            NumberDataPointOneOfvalue::as_int(fields.i64("as_int")?)
+        } else {
+            NumberDataPointOneOfvalue::None
+        };
+
+        Ok(NumberDataPoint {
+            attributes: fields.messages("attributes")?,
+            start_time_unix_nano: fields.u64("start_time_unix_nano")?,
+            time_unix_nano: fields.u64("time_unix_nano")?,
+            exemplars: fields.messages("exemplars")?,
+            flags: fields.u32("flags")?,
+            value,
+            unknown_fields: Default::default(),
+        })
+    }
+}
+
+impl FromJson for HistogramDataPoint<'static> {
+    fn from_json(value: &Value) -> Result<Self, crate::Error> {
+        let fields = Fields::new(value)?;
+        Ok(HistogramDataPoint {
+            attributes: fields.messages("attributes")?,
+            start_time_unix_nano: fields.u64("start_time_unix_nano")?,
+            time_unix_nano: fields.u64("time_unix_nano")?,
+            count: fields.u64("count")?,
+            sum: fields.f64("sum")?,
+            bucket_counts: Cow::Owned(fields.u64s("bucket_counts")?),
+            explicit_bounds: Cow::Owned(fields.f64s("explicit_bounds")?),
+            exemplars: fields.messages("exemplars")?,
+            flags: fields.u32("flags")?,
+            min: fields.f64("min")?,
+            max: fields.f64("max")?,
+            unknown_fields: Default::default(),
+        })
+    }
+}
+
+impl FromJson for ExponentialHistogramDataPoint<'static> {
+    fn from_json(value: &Value) -> Result<Self, crate::Error> {
+        let fields = Fields::new(value)?;
+        Ok(ExponentialHistogramDataPoint {
+            attributes: fields.messages("attributes")?,
+            start_time_unix_nano: fields.u64("start_time_unix_nano")?,
+            time_unix_nano: fields.u64("time_unix_nano")?,
+            count: fields.u64("count")?,
+            sum: fields.f64("sum")?,
+            scale: fields.i32("scale")?,
+            zero_count: fields.u64("zero_count")?,
+            positive: fields.message("positive")?,
+            negative: fields.message("negative")?,
+            flags: fields.u32("flags")?,
+            exemplars: fields.messages("exemplars")?,
+            min: fields.f64("min")?,
+            max: fields.f64("max")?,
+            zero_threshold: fields.f64("zero_threshold")?,
+            unknown_fields: Default::default(),
+        })
+    }
+}
+
+impl FromJson for ExponentialHistogramDataPointBuckets {
+    fn from_json(value: &Value) -> Result<Self, crate::Error> {
+        let fields = Fields::new(value)?;
+        Ok(ExponentialHistogramDataPointBuckets {
+            offset: fields.i32("offset")?,
+            bucket_counts: fields.u64s("bucket_counts")?,
+            unknown_fields: Default::default(),
+        })
+    }
+}
+
+impl FromJson for SummaryDataPoint<'static> {
+    fn from_json(value: &Value) -> Result<Self, crate::Error> {
+        let fields = Fields::new(value)?;
+        Ok(SummaryDataPoint {
+            attributes: fields.messages("attributes")?,
+            start_time_unix_nano: fields.u64("start_time_unix_nano")?,
+            time_unix_nano: fields.u64("time_unix_nano")?,
+            count: fields.u64("count")?,
+            sum: fields.f64("sum")?,
+            quantile_values: fields.messages("quantile_values")?,
+            flags: fields.u32("flags")?,
+            unknown_fields: Default::default(),
+        })
+    }
+}
+
+impl FromJson for SummaryDataPointValueAtQuantile {
+    fn from_json(value: &Value) -> Result<Self, crate::Error> {
+        let fields = Fields::new(value)?;
+        Ok(SummaryDataPointValueAtQuantile {
+            quantile: fields.f64("quantile")?,
+            value: fields.f64("value")?,
+            unknown_fields: Default::default(),
+        })
+    }
+}
+
+impl FromJson for Exemplar<'static> {
+    fn from_json(value: &Value) -> Result<Self, crate::Error> {
+        let fields = Fields::new(value)?;
+
+        let value = if fields.get("as_double").is_some() {
+            ExemplarOneOfvalue::as_double(fields.f64("as_double")?)
+        } else if fields.get("as_int").is_some() {
+            ExemplarOneOfvalue::as_int(fields.i64("as_int")?)
+        } else {
+            ExemplarOneOfvalue::None
+        };
+
+        Ok(Exemplar {
+            filtered_attributes: fields.messages("filtered_attributes")?,
+            time_unix_nano: fields.u64("time_unix_nano")?,
+            span_id: fields.hex("span_id")?,
+            trace_id: fields.hex("trace_id")?,
+            value,
+            unknown_fields: Default::default(),
+        })
+    }
+}
+
+impl ToJson for ExportMetricsServiceRequest<'_> {
+    fn to_json(&self) -> Value {
+        Object::new()
+            .messages("resource_metrics", &self.resource_metrics)
+            .build()
+    }
+}
+
+impl ToJson for ExportMetricsServiceResponse<'_> {
+    fn to_json(&self) -> Value {
+        Object::new()
+            .message("partial_success", self.partial_success.as_ref())
+            .build()
+    }
+}
+
+impl ToJson for ExportMetricsPartialSuccess<'_> {
+    fn to_json(&self) -> Value {
+        Object::new()
+            .i64("rejected_data_points", self.rejected_data_points)
+            .string("error_message", &self.error_message)
+            .build()
+    }
+}
+
+impl ToJson for MetricsData<'_> {
+    fn to_json(&self) -> Value {
+        Object::new()
+            .messages("resource_metrics", &self.resource_metrics)
+            .build()
+    }
+}
+
+impl ToJson for ResourceMetrics<'_> {
+    fn to_json(&self) -> Value {
+        Object::new()
+            .message("resource", self.resource.as_ref())
+            .messages("scope_metrics", &self.scope_metrics)
+            .string("schema_url", &self.schema_url)
+            .build()
+    }
+}
+
+impl ToJson for ScopeMetrics<'_> {
+    fn to_json(&self) -> Value {
+        Object::new()
+            .message("scope", self.scope.as_ref())
+            .messages("metrics", &self.metrics)
+            .string("schema_url", &self.schema_url)
+            .build()
+    }
+}
+
+impl ToJson for Metric<'_> {
+    fn to_json(&self) -> Value {
+        let object = Object::new()
+            .string("name", &self.name)
+            .string("description", &self.description)
+            .string("unit", &self.unit)
+            .messages("metadata", &self.metadata);
+
+        match &self.data {
+            MetricOneOfdata::gauge(gauge) => object.message("gauge", Some(gauge)),
+            MetricOneOfdata::sum(sum) => object.message("sum", Some(sum)),
+            MetricOneOfdata::histogram(histogram) => object.message("histogram", Some(histogram)),
+            MetricOneOfdata::exponential_histogram(histogram) => {
+                object.message("exponential_histogram", Some(histogram))
+            }
+            MetricOneOfdata::summary(summary) => object.message("summary", Some(summary)),
+            MetricOneOfdata::None => object,
+        }
+        .build()
+    }
+}
+
+impl ToJson for Gauge<'_> {
+    fn to_json(&self) -> Value {
+        Object::new()
+            .messages("data_points", &self.data_points)
+            .build()
+    }
+}
+
+impl ToJson for Sum<'_> {
+    fn to_json(&self) -> Value {
+        Object::new()
+            .messages("data_points", &self.data_points)
+            .i32(
+                "aggregation_temporality",
+                self.aggregation_temporality as i32,
+            )
+            .bool("is_monotonic", self.is_monotonic)
+            .build()
+    }
+}
+
+impl ToJson for Histogram<'_> {
+    fn to_json(&self) -> Value {
+        Object::new()
+            .messages("data_points", &self.data_points)
+            .i32(
+                "aggregation_temporality",
+                self.aggregation_temporality as i32,
+            )
+            .build()
+    }
+}
+
+impl ToJson for ExponentialHistogram<'_> {
+    fn to_json(&self) -> Value {
+        Object::new()
+            .messages("data_points", &self.data_points)
+            .i32(
+                "aggregation_temporality",
+                self.aggregation_temporality as i32,
+            )
+            .build()
+    }
+}
+
+impl ToJson for Summary<'_> {
+    fn to_json(&self) -> Value {
+        Object::new()
+            .messages("data_points", &self.data_points)
+            .build()
+    }
+}
+
+impl ToJson for NumberDataPoint<'_> {
+    fn to_json(&self) -> Value {
+        let object = Object::new()
+            .messages("attributes", &self.attributes)
+            .u64("start_time_unix_nano", self.start_time_unix_nano)
+            .u64("time_unix_nano", self.time_unix_nano)
+            .messages("exemplars", &self.exemplars)
+            .u32("flags", self.flags);
+
+        match self.value {
+            NumberDataPointOneOfvalue::as_double(value) => {
+                object.value("as_double", from_f64(value))
+            }
+            NumberDataPointOneOfvalue::as_int(value) => {
+                object.value("as_int", Value::from(value.to_string()))
+            }
+            NumberDataPointOneOfvalue::None => object,
+        }
+        .build()
+    }
+}
+
+impl ToJson for HistogramDataPoint<'_> {
+    fn to_json(&self) -> Value {
+        Object::new()
+            .messages("attributes", &self.attributes)
+            .u64("start_time_unix_nano", self.start_time_unix_nano)
+            .u64("time_unix_nano", self.time_unix_nano)
+            .u64("count", self.count)
+            .f64("sum", self.sum)
+            .u64s("bucket_counts", &self.bucket_counts)
+            .f64s("explicit_bounds", &self.explicit_bounds)
+            .messages("exemplars", &self.exemplars)
+            .u32("flags", self.flags)
+            .f64("min", self.min)
+            .f64("max", self.max)
+            .build()
+    }
+}
+
+impl ToJson for ExponentialHistogramDataPoint<'_> {
+    fn to_json(&self) -> Value {
+        Object::new()
+            .messages("attributes", &self.attributes)
+            .u64("start_time_unix_nano", self.start_time_unix_nano)
+            .u64("time_unix_nano", self.time_unix_nano)
+            .u64("count", self.count)
+            .f64("sum", self.sum)
+            .i32("scale", self.scale)
+            .u64("zero_count", self.zero_count)
+            .message("positive", self.positive.as_ref())
+            .message("negative", self.negative.as_ref())
+            .u32("flags", self.flags)
+            .messages("exemplars", &self.exemplars)
+            .f64("min", self.min)
+            .f64("max", self.max)
+            .f64("zero_threshold", self.zero_threshold)
+            .build()
+    }
+}
+
+impl ToJson for ExponentialHistogramDataPointBuckets {
+    fn to_json(&self) -> Value {
+        Object::new()
+            .i32("offset", self.offset)
+            .u64s("bucket_counts", &self.bucket_counts)
+            .build()
+    }
+}
+
+impl ToJson for SummaryDataPoint<'_> {
+    fn to_json(&self) -> Value {
+        Object::new()
+            .messages("attributes", &self.attributes)
+            .u64("start_time_unix_nano", self.start_time_unix_nano)
+            .u64("time_unix_nano", self.time_unix_nano)
+            .u64("count", self.count)
+            .f64("sum", self.sum)
+            .messages("quantile_values", &self.quantile_values)
+            .u32("flags", self.flags)
+            .build()
+    }
+}
+
+impl ToJson for SummaryDataPointValueAtQuantile {
+    fn to_json(&self) -> Value {
+        Object::new()
+            .f64("quantile", self.quantile)
+            .f64("value", self.value)
+            .build()
+    }
+}
+
+impl ToJson for Exemplar<'_> {
+    fn to_json(&self) -> Value {
+        let object = Object::new()
+            .messages("filtered_attributes", &self.filtered_attributes)
+            .u64("time_unix_nano", self.time_unix_nano)
+            .hex("span_id", &self.span_id)
+            .hex("trace_id", &self.trace_id);
+
+        match self.value {
+            ExemplarOneOfvalue::as_double(value) => object.value("as_double", from_f64(value)),
+            ExemplarOneOfvalue::as_int(value) => {
+                object.value("as_int", Value::from(value.to_string()))
+            }
+            ExemplarOneOfvalue::None => object,
+        }
+        .build()
+    }
+}
@@ -0,0 +1,848 @@
+// OTLP/JSON mapping of the protobuf messages, as described in
+// https://opentelemetry.io/docs/specs/otlp/#json-protobuf-encoding
+//
+// Field names are lowerCamelCase, with the original snake_case names also
+// accepted. Trace and span ids are hex strings, 64 bit integers may be
+// given as strings and enums either as integers or by name. Unknown fields
+// are ignored.
+
+use std::borrow::Cow;
+
+use base64::Engine;
+use quick_protobuf::MessageWrite;
+use serde_json::{Map, Value};
+
+use crate::descriptor::MessageDescriptor;
+use crate::DecodeOptions;
+
+pub(crate) mod common;
+pub(crate) mod logs;
+pub(crate) mod metrics;
+pub(crate) mod trace;
+
+pub(crate) trait FromJson: Sized {
+    fn from_json(value: &Value) -> Result<Self, crate::Error>;
+}
+
+pub(crate) trait ToJson {
+    fn to_json(&self) -> Value;
+}
+
+/// An enum read from JSON either as its integer value or by its name.
+pub(crate) trait Enumeration: Default + From<i32> {
+    /// Returns `None` for names not declared in the proto.
+    fn from_name(name: &str) -> Option<Self>;
+}
+
+pub(crate) fn decode<T: FromJson>(json: &[u8]) -> Result<T, crate::Error> {
+    let value: Value = serde_json::from_slice(json)?;
+    T::from_json(&value)
+}
+
+// The limits other than the size are checked once the request is decoded,
+// the memory used until then being bounded by the size of the input
+pub(crate) fn decode_with<T: FromJson + MessageWrite>(
+    json: &[u8],
+    options: &DecodeOptions,
+    descriptor: &'static MessageDescriptor,
+) -> Result<T, crate::Error> {
+    options.check_size(json)?;
+    let message = decode(json)?;
+    options.check_message(&message, descriptor)?;
+    Ok(message)
+}
+
+pub(crate) fn encode<T: ToJson>(message: &T, pretty: bool) -> String {
+    if pretty {
+        format!("{:#}", message.to_json())
+    } else {
+        message.to_json().to_string()
+    }
+}
+
+fn invalid(name: &str, expected: &str) -> crate::Error {
+    crate::Error::InvalidJson(format!("field `{}` must be {}", name, expected))
+}
+
+// Compares a lowerCamelCase JSON key against a snake_case field name
+fn is_camel_case_of(key: &str, name: &str) -> bool {
+    let mut key = key.chars();
+    let mut upper = false;
+    for c in name.chars() {
+        if c == '_' {
+            upper = true;
+            continue;
+        }
+        let expected = if upper { c.to_ascii_uppercase() } else { c };
+        upper = false;
+        if key.next() != Some(expected) {
+            return false;
+        }
+    }
+    key.next().is_none()
+}
+
+/// The fields of a JSON object, looked up by their protobuf field name.
+pub(crate) struct Fields<'j> {
+    object: &'j Map<String, Value>,
+}
+
+impl<'j> Fields<'j> {
+    pub(crate) fn new(value: &'j Value) -> Result<Self, crate::Error> {
+        match value {
+            Value::Object(object) => Ok(Fields { object }),
+            _ => Err(crate::Error::InvalidJson(format!(
+                "expected an object, found `{}`",
+                value
+            ))),
+        }
+    }
+
+    /// Returns the field, treating `null` the same as a missing field.
+    pub(crate) fn get(&self, name: &str) -> Option<&'j Value> {
+        let value = if name.contains('_') {
+            self.object
+                .iter()
+                .find(|(key, _)| is_camel_case_of(key, name))
+                .map(|(_, value)| value)
+                .or_else(|| self.object.get(name))
+        } else {
+            self.object.get(name)
+        };
+        value.filter(|value| !value.is_null())
+    }
+
+    pub(crate) fn string(&self, name: &str) -> Result<Cow<'static, str>, crate::Error> {
+        match self.get(name) {
+            None => Ok(Cow::Borrowed("")),
+            Some(Value::String(s)) => Ok(Cow::Owned(s.clone())),
+            Some(_) => Err(invalid(name, "a string")),
+        }
+    }
+
+    pub(crate) fn hex(&self, name: &str) -> Result<Cow<'static, [u8]>, crate::Error> {
+        match self.get(name) {
+            None => Ok(Cow::Borrowed(&[])),
+            Some(Value::String(s)) => from_hex(s)
+                .map(Cow::Owned)
+                .ok_or_else(|| invalid(name, "a hex string")),
+            Some(_) => Err(invalid(name, "a hex string")),
+        }
+    }
+
+    pub(crate) fn base64(&self, name: &str) -> Result<Cow<'static, [u8]>, crate::Error> {
+        match self.get(name) {
+            None => Ok(Cow::Borrowed(&[])),
+            Some(Value::String(s)) => from_base64(s)
+                .map(Cow::Owned)
+                .ok_or_else(|| invalid(name, "a base64 string")),
+            Some(_) => Err(invalid(name, "a base64 string")),
+        }
+    }
+
+    pub(crate) fn bool(&self, name: &str) -> Result<bool, crate::Error> {
+        match self.get(name) {
+            None => Ok(false),
+            Some(Value::Bool(b)) => Ok(*b),
+            Some(_) => Err(invalid(name, "a boolean")),
+        }
+    }
+
+    pub(crate) fn u64(&self, name: &str) -> Result<u64, crate::Error> {
+        self.get(name)
+            .map_or(Some(0), to_u64)
+            .ok_or_else(|| invalid(name, "an unsigned integer"))
+    }
+
+    pub(crate) fn i64(&self, name: &str) -> Result<i64, crate::Error> {
+        self.get(name)
+            .map_or(Some(0), to_i64)
+            .ok_or_else(|| invalid(name, "an integer"))
+    }
+
+    pub(crate) fn u32(&self, name: &str) -> Result<u32, crate::Error> {
+        self.get(name)
+            .map_or(Some(0), |value| {
+                to_u64(value).and_then(|n| n.try_into().ok())
+            })
+            .ok_or_else(|| invalid(name, "a 32 bit unsigned integer"))
+    }
+
+    pub(crate) fn i32(&self, name: &str) -> Result<i32, crate::Error> {
+        self.get(name)
+            .map_or(Some(0), |value| {
+                to_i64(value).and_then(|n| n.try_into().ok())
+            })
+            .ok_or_else(|| invalid(name, "a 32 bit integer"))
+    }
+
+    pub(crate) fn f64(&self, name: &str) -> Result<f64, crate::Error> {
+        self.get(name)
+            .map_or(Some(0.0), to_f64)
+            .ok_or_else(|| invalid(name, "a number"))
+    }
+
+    pub(crate) fn strings(&self, name: &str) -> Result<Vec<Cow<'static, str>>, crate::Error> {
+        self.array(name)?
+            .iter()
+            .map(|value| match value {
+                Value::String(s) => Ok(Cow::Owned(s.clone())),
+                _ => Err(invalid(name, "an array of strings")),
+            })
+            .collect()
+    }
+
+    pub(crate) fn u64s(&self, name: &str) -> Result<Vec<u64>, crate::Error> {
+        self.array(name)?
+            .iter()
+            .map(|value| to_u64(value).ok_or_else(|| invalid(name, "an array of integers")))
+            .collect()
+    }
+
+    pub(crate) fn f64s(&self, name: &str) -> Result<Vec<f64>, crate::Error> {
+        self.array(name)?
+            .iter()
+            .map(|value| to_f64(value).ok_or_else(|| invalid(name, "an array of numbers")))
+            .collect()
+    }
+
+    /// Reads an enum given either as its integer value or by name.
+    pub(crate) fn enumeration<E: Enumeration>(&self, name: &str) -> Result<E, crate::Error> {
+        match self.get(name) {
+            None => Ok(E::default()),
+            Some(Value::String(s)) => {
+                E::from_name(s).ok_or_else(|| invalid(name, "a known enum name or an integer"))
+            }
+            Some(value) => to_i64(value)
+                .and_then(|n| i32::try_from(n).ok())
+                .map(E::from)
+                .ok_or_else(|| invalid(name, "an enum name or integer")),
+        }
+    }
+
+    pub(crate) fn message<T: FromJson>(&self, name: &str) -> Result<Option<T>, crate::Error> {
+        self.get(name).map(T::from_json).transpose()
+    }
+
+    pub(crate) fn messages<T: FromJson>(&self, name: &str) -> Result<Vec<T>, crate::Error> {
+        self.array(name)?.iter().map(T::from_json).collect()
+    }
+
+    fn array(&self, name: &str) -> Result<&'j [Value], crate::Error> {
+        match self.get(name) {
+            None => Ok(&[]),
+            Some(Value::Array(values)) => Ok(values),
+            Some(_) => Err(invalid(name, "an array")),
+        }
+    }
+}
+
+fn camel_case(name: &str) -> String {
+    let mut key = String::with_capacity(name.len());
+    let mut upper = false;
+    for c in name.chars() {
+        if c == '_' {
+            upper = true;
+        } else if upper {
+            key.push(c.to_ascii_uppercase());
+            upper = false;
+        } else {
+            key.push(c);
+        }
+    }
+    key
+}
+
+/// Builds the JSON object of a message. Fields holding their default value
+/// are left out, and field names are converted to lowerCamelCase.
+#[derive(Default)]
+pub(crate) struct Object {
+    object: Map<String, Value>,
+}
+
+impl Object {
+    pub(crate) fn new() -> Self {
+        Object::default()
+    }
+
+    pub(crate) fn value(mut self, name: &str, value: Value) -> Self {
+        self.object.insert(camel_case(name), value);
+        self
+    }
+
+    pub(crate) fn string(self, name: &str, value: &str) -> Self {
+        if value.is_empty() {
+            return self;
+        }
+        self.value(name, Value::from(value))
+    }
+
+    pub(crate) fn hex(self, name: &str, value: &[u8]) -> Self {
+        if value.is_empty() {
+            return self;
+        }
+        self.value(name, Value::from(to_hex(value)))
+    }
+
+    pub(crate) fn bool(self, name: &str, value: bool) -> Self {
+        if !value {
+            return self;
+        }
+        self.value(name, Value::from(value))
+    }
+
+    pub(crate) fn u64(self, name: &str, value: u64) -> Self {
+        if value == 0 {
+            return self;
+        }
+        self.value(name, Value::from(value.to_string()))
+    }
+
+    pub(crate) fn i64(self, name: &str, value: i64) -> Self {
+        if value == 0 {
+            return self;
+        }
+        self.value(name, Value::from(value.to_string()))
+    }
+
+    pub(crate) fn u32(self, name: &str, value: u32) -> Self {
+        if value == 0 {
+            return self;
+        }
+        self.value(name, Value::from(value))
+    }
+
+    pub(crate) fn i32(self, name: &str, value: i32) -> Self {
+        if value == 0 {
+            return self;
+        }
+        self.value(name, Value::from(value))
+    }
+
+    pub(crate) fn f64(self, name: &str, value: f64) -> Self {
+        if value == 0.0 {
+            return self;
+        }
+        self.value(name, from_f64(value))
+    }
+
+    pub(crate) fn strings(self, name: &str, values: &[Cow<'_, str>]) -> Self {
+        if values.is_empty() {
+            return self;
+        }
+        let values = values.iter().map(|value| Value::from(value.as_ref()));
+        self.value(name, Value::Array(values.collect()))
+    }
+
+    pub(crate) fn u64s(self, name: &str, values: &[u64]) -> Self {
+        if values.is_empty() {
+            return self;
+        }
+        let values = values.iter().map(|value| Value::from(value.to_string()));
+        self.value(name, Value::Array(values.collect()))
+    }
+
+    pub(crate) fn f64s(self, name: &str, values: &[f64]) -> Self {
+        if values.is_empty() {
+            return self;
+        }
+        let values = values.iter().map(|value| from_f64(*value));
+        self.value(name, Value::Array(values.collect()))
+    }
+
+    pub(crate) fn message<T: ToJson>(self, name: &str, value: Option<&T>) -> Self {
+        match value {
+            Some(value) => self.value(name, value.to_json()),
+            None => self,
+        }
+    }
+
+    pub(crate) fn messages<T: ToJson>(self, name: &str, values: &[T]) -> Self {
+        if values.is_empty() {
+            return self;
+        }
+        let values = values.iter().map(ToJson::to_json);
+        self.value(name, Value::Array(values.collect()))
+    }
+
+    pub(crate) fn build(self) -> Value {
+        Value::Object(self.object)
+    }
+}
+
+pub(crate) fn from_f64(value: f64) -> Value {
+    if value.is_nan() {
+        Value::from("NaN")
+    } else if value == f64::INFINITY {
+        Value::from("Infinity")
+    } else if value == f64::NEG_INFINITY {
+        Value::from("-Infinity")
+    } else {
+        Value::from(value)
+    }
+}
+
+pub(crate) fn to_base64(value: &[u8]) -> String {
+    base64::engine::general_purpose::STANDARD.encode(value)
+}
+
+fn to_hex(value: &[u8]) -> String {
+    value.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+fn to_u64(value: &Value) -> Option<u64> {
+    match value {
+        Value::Number(n) => n.as_u64(),
+        Value::String(s) => s.parse().ok(),
+        _ => None,
+    }
+}
+
+fn to_i64(value: &Value) -> Option<i64> {
+    match value {
+        Value::Number(n) => n.as_i64(),
+        Value::String(s) => s.parse().ok(),
+        _ => None,
+    }
+}
+
+fn to_f64(value: &Value) -> Option<f64> {
+    match value {
+        Value::Number(n) => n.as_f64(),
+        Value::String(s) => match s.as_str() {
+            "NaN" => Some(f64::NAN),
+            "Infinity" => Some(f64::INFINITY),
+            "-Infinity" => Some(f64::NEG_INFINITY),
+            s => s.parse().ok(),
+        },
+        _ => None,
+    }
+}
+
+fn from_hex(s: &str) -> Option<Vec<u8>> {
+    if !s.len().is_multiple_of(2) {
+        return None;
+    }
+    s.as_bytes()
+        .chunks(2)
+        .map(|pair| {
+            let high = (pair[0] as char).to_digit(16)?;
+            let low = (pair[1] as char).to_digit(16)?;
+            Some((high * 16 + low) as u8)
+        })
+        .collect()
+}
+
+fn from_base64(s: &str) -> Option<Vec<u8>> {
+    use base64::engine::general_purpose::{STANDARD, URL_SAFE};
+
+    STANDARD.decode(s).or_else(|_| URL_SAFE.decode(s)).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::is_camel_case_of;
+    use crate::opentelemetry::common::{AnyValue, AnyValueOneOfvalue, KeyValue};
+    use crate::opentelemetry::logs::{self, ExportLogsServiceRequest, SeverityNumber};
+    use crate::opentelemetry::metrics::{
+        self, AggregationTemporality, ExportMetricsServiceRequest, MetricOneOfdata,
+        NumberDataPointOneOfvalue,
+    };
+    use crate::opentelemetry::trace::{self, ExportTraceServiceRequest, SpanKind, StatusCode};
+    use std::borrow::Cow;
+
+    #[test]
+    fn camel_case_field_names() {
+        assert!(is_camel_case_of(
+            "startTimeUnixNano",
+            "start_time_unix_nano"
+        ));
+        assert!(is_camel_case_of("name", "name"));
+        assert!(!is_camel_case_of("startTime", "start_time_unix_nano"));
+        assert!(!is_camel_case_of(
+            "start_time_unix_nano",
+            "start_time_unix_nano"
+        ));
+    }
+
+    #[test]
+    fn from_json_trace_request() {
+        use trace::Validate;
+
+        let json = br#"{
+          "resourceSpans": [{
+            "resource": {
+              "attributes": [{ "key": "service.name", "value": { "stringValue": "my.service" } }]
+            },
+            "scopeSpans": [{
+              "scope": { "name": "my.library", "version": "1.0.0" },
+              "spans": [{
+                "traceId": "5B8EFFF798038103D269B633813FC60C",
+                "spanId": "eee19b7ec3c1b174",
+                "parentSpanId": "eee19b7ec3c1b173",
+                "name": "I'm a server span",
+                "startTimeUnixNano": "1544712660000000000",
+                "end_time_unix_nano": 1544712661000000000,
+                "kind": 2,
+                "attributes": [{ "key": "my.span.attr", "value": { "intValue": "42" } }],
+                "status": { "code": "STATUS_CODE_ERROR", "message": "failed" },
+                "unknownField": { "ignored": true }
+              }]
+            }],
+            "schemaUrl": "https://opentelemetry.io/schemas/1.21.0"
+          }]
+        }"#;
+
+        let request = ExportTraceServiceRequest::from_json(json).expect("Parsing of JSON failed");
+        request.validate().expect("validation failed");
+
+        let resource_spans = &request.resource_spans[0];
+        assert_eq!(
+            resource_spans.schema_url,
+            "https://opentelemetry.io/schemas/1.21.0"
+        );
+        let scope_spans = &resource_spans.scope_spans[0];
+        assert_eq!(scope_spans.scope.as_ref().unwrap().version, "1.0.0");
+
+        let span = &scope_spans.spans[0];
+        assert_eq!(
+            &span.trace_id[..],
+            &[
+                0x5b, 0x8e, 0xff, 0xf7, 0x98, 0x03, 0x81, 0x03, 0xd2, 0x69, 0xb6, 0x33, 0x81, 0x3f,
+                0xc6, 0x0c
+            ]
+        );
+        assert_eq!(
+            &span.span_id[..],
+            &[0xee, 0xe1, 0x9b, 0x7e, 0xc3, 0xc1, 0xb1, 0x74]
+        );
+        assert_eq!(span.start_time_unix_nano, 1544712660000000000);
+        assert_eq!(span.end_time_unix_nano, 1544712661000000000);
+        assert_eq!(span.kind, SpanKind::SPAN_KIND_SERVER);
+        assert_eq!(
+            span.attributes[0],
+            KeyValue {
+                key: Cow::from("my.span.attr"),
+                value: Some(AnyValue {
+                    value: AnyValueOneOfvalue::int_value(42),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }
+        );
+        let status = span.status.as_ref().unwrap();
+        assert_eq!(status.code, StatusCode::STATUS_CODE_ERROR);
+        assert_eq!(status.message, "failed");
+    }
+
+    #[test]
+    fn from_json_metrics_request() {
+        use metrics::Validate;
+
+        let json = br#"{
+          "resourceMetrics": [{
+            "scopeMetrics": [{
+              "metrics": [{
+                "name": "my_counter",
+                "unit": "1",
+                "sum": {
+                  "aggregationTemporality": "AGGREGATION_TEMPORALITY_CUMULATIVE",
+                  "isMonotonic": true,
+                  "dataPoints": [{ "asInt": "5", "timeUnixNano": "1544712660300000000" }]
+                }
+              }, {
+                "name": "my_histogram",
+                "histogram": {
+                  "aggregationTemporality": 1,
+                  "dataPoints": [{
+                    "count": "2",
+                    "sum": 2,
+                    "bucketCounts": ["1", 1],
+                    "explicitBounds": [1],
+                    "min": "NaN",
+                    "max": "Infinity"
+                  }]
+                }
+              }]
+            }]
+          }]
+        }"#;
+
+        let request = ExportMetricsServiceRequest::from_json(json).expect("Parsing of JSON failed");
+        request.validate().expect("validation failed");
+
+        let metrics = &request.resource_metrics[0].scope_metrics[0].metrics;
+        match &metrics[0].data {
+            MetricOneOfdata::sum(sum) => {
+                assert!(sum.is_monotonic);
+                assert_eq!(
+                    sum.aggregation_temporality,
+                    AggregationTemporality::AGGREGATION_TEMPORALITY_CUMULATIVE
+                );
+                assert_eq!(
+                    sum.data_points[0].value,
+                    NumberDataPointOneOfvalue::as_int(5)
+                );
+                assert_eq!(sum.data_points[0].time_unix_nano, 1544712660300000000);
+            }
+            other => panic!("unexpected data {:?}", other),
+        }
+        match &metrics[1].data {
+            MetricOneOfdata::histogram(histogram) => {
+                let data_point = &histogram.data_points[0];
+                assert_eq!(&data_point.bucket_counts[..], &[1, 1]);
+                assert_eq!(&data_point.explicit_bounds[..], &[1.0]);
+                assert!(data_point.min.is_nan());
+                assert_eq!(data_point.max, f64::INFINITY);
+            }
+            other => panic!("unexpected data {:?}", other),
+        }
+    }
+
+    #[test]
+    fn from_json_logs_request() {
+        use logs::Validate;
+
+        let json = br#"{
+          "resource_logs": [{
+            "scope_logs": [{
+              "log_records": [{
+                "time_unix_nano": "1544712660300000000",
+                "severityNumber": "SEVERITY_NUMBER_INFO",
+                "severityText": "Information",
+                "traceId": "5b8efff798038103d269b633813fc60c",
+                "body": {
+                  "kvlistValue": {
+                    "values": [
+                      { "key": "bytes", "value": { "bytesValue": "aGVsbG8=" } },
+                      { "key": "list", "value": { "arrayValue": { "values": [{ "boolValue": true }, { "doubleValue": 1.5 }] } } }
+                    ]
+                  }
+                }
+              }]
+            }]
+          }]
+        }"#;
+
+        let request = ExportLogsServiceRequest::from_json(json).expect("Parsing of JSON failed");
+        request.validate().expect("validation failed");
+
+        let log_record = &request.resource_logs[0].scope_logs[0].log_records[0];
+        assert_eq!(log_record.time_unix_nano, 1544712660300000000);
+        assert_eq!(
+            log_record.severity_number,
+            SeverityNumber::SEVERITY_NUMBER_INFO
+        );
+        assert_eq!(log_record.trace_id.len(), 16);
+
+        let body = match &log_record.body.as_ref().unwrap().value {
+            AnyValueOneOfvalue::kvlist_value(kvlist) => kvlist,
+            other => panic!("unexpected body {:?}", other),
+        };
+        assert_eq!(
+            body.values[0].value.as_ref().unwrap().value,
+            AnyValueOneOfvalue::bytes_value(Cow::from(&b"hello"[..]))
+        );
+        match &body.values[1].value.as_ref().unwrap().value {
+            AnyValueOneOfvalue::array_value(array) => {
+                assert_eq!(array.values[0].value, AnyValueOneOfvalue::bool_value(true));
+                assert_eq!(array.values[1].value, AnyValueOneOfvalue::double_value(1.5));
+            }
+            other => panic!("unexpected value {:?}", other),
+        }
+    }
+
+    #[test]
+    fn from_json_rejects_invalid_input() {
+        match ExportTraceServiceRequest::from_json(
+            br#"{"resourceSpans": [{"scopeSpans": [{"spans": [{"traceId": "xyz"}]}]}]}"#,
+        ) {
+            Err(e) => assert_eq!(
+                e.to_string(),
+                "invalid OTLP/JSON: field `trace_id` must be a hex string"
+            ),
+            Ok(_) => panic!("Decoding should fail"),
+        }
+
+        assert!(matches!(
+            ExportTraceServiceRequest::from_json(b"{"),
+            Err(crate::Error::JsonParse(_))
+        ));
+
+        // Unknown enum names are not read as the default value
+        for json in [
+            &br#"{"resourceSpans": [{"scopeSpans": [{"spans": [{"kind": "SPAN_KIND_SERVERS"}]}]}]}"#[..],
+            br#"{"resourceSpans": [{"scopeSpans": [{"spans": [{"status": {"code": "ERROR"}}]}]}]}"#,
+        ] {
+            assert!(matches!(
+                ExportTraceServiceRequest::from_json(json),
+                Err(crate::Error::InvalidJson(_))
+            ));
+        }
+        assert!(matches!(
+            ExportLogsServiceRequest::from_json(
+                br#"{"resourceLogs": [{"scopeLogs": [{"logRecords": [{"severityNumber": "INFO"}]}]}]}"#
+            ),
+            Err(crate::Error::InvalidJson(_))
+        ));
+        match ExportMetricsServiceRequest::from_json(
+            br#"{"resourceMetrics": [{"scopeMetrics": [{"metrics": [{"sum": {"aggregationTemporality": "DELTA"}}]}]}]}"#,
+        ) {
+            Err(e) => assert_eq!(
+                e.to_string(),
+                "invalid OTLP/JSON: field `aggregation_temporality` must be a known enum name or an integer"
+            ),
+            Ok(_) => panic!("Decoding should fail"),
+        }
+    }
+
+    #[test]
+    fn from_json_with_enforces_limits() {
+        let logs = |attributes: &str, body: &str| {
+            format!(
+                r#"{{"resourceLogs": [{{"resource": {{"attributes": [{attributes}]}},
+                    "scopeLogs": [{{"logRecords": [{{"body": {body}}}]}}]}}]}}"#
+            )
+        };
+        let attribute = r#"{"key": "k", "value": {"intValue": 1}}"#;
+        let options = crate::DecodeOptions {
+            max_repeated_elements: 2,
+            max_any_value_depth: 2,
+            max_string_len: 4,
+            ..Default::default()
+        };
+
+        let json = logs(&[attribute; 2].join(","), r#"{"arrayValue": {}}"#);
+        ExportLogsServiceRequest::from_json_with(json.as_bytes(), &options).expect("within limits");
+
+        let json = logs(&[attribute; 3].join(","), "{}");
+        assert!(matches!(
+            ExportLogsServiceRequest::from_json_with(json.as_bytes(), &options),
+            Err(crate::Error::TooManyElements { limit: 2 })
+        ));
+        let nested =
+            r#"{"arrayValue": {"values": [{"arrayValue": {"values": [{"intValue": 1}]}}]}}"#;
+        let json = logs("", nested);
+        assert!(matches!(
+            ExportLogsServiceRequest::from_json_with(json.as_bytes(), &options),
+            Err(crate::Error::NestingTooDeep { limit: 2 })
+        ));
+        let json = logs("", r#"{"stringValue": "12345"}"#);
+        assert!(matches!(
+            ExportLogsServiceRequest::from_json_with(json.as_bytes(), &options),
+            Err(crate::Error::FieldTooLarge { size: 5, limit: 4 })
+        ));
+
+        let options = crate::DecodeOptions {
+            max_total_bytes: json.len() - 1,
+            ..Default::default()
+        };
+        assert!(matches!(
+            ExportLogsServiceRequest::from_json_with(json.as_bytes(), &options),
+            Err(crate::Error::MessageTooLarge { .. })
+        ));
+    }
+
+    #[test]
+    fn to_json_trace_request() {
+        let json = br#"{
+          "resourceSpans": [{
+            "scopeSpans": [{
+              "spans": [{
+                "traceId": "5B8EFFF798038103D269B633813FC60C",
+                "spanId": "eee19b7ec3c1b174",
+                "name": "span",
+                "kind": "SPAN_KIND_SERVER",
+                "startTimeUnixNano": 1544712660000000000,
+                "droppedAttributesCount": 0,
+                "attributes": [{ "key": "answer", "value": { "intValue": 42 } }]
+              }]
+            }]
+          }]
+        }"#;
+
+        let request = ExportTraceServiceRequest::from_json(json).expect("Parsing of JSON failed");
+        let value: serde_json::Value = serde_json::from_str(&request.to_json()).unwrap();
+        assert_eq!(
+            value,
+            serde_json::json!({
+              "resourceSpans": [{
+                "scopeSpans": [{
+                  "spans": [{
+                    "traceId": "5b8efff798038103d269b633813fc60c",
+                    "spanId": "eee19b7ec3c1b174",
+                    "name": "span",
+                    "kind": 2,
+                    "startTimeUnixNano": "1544712660000000000",
+                    "attributes": [{ "key": "answer", "value": { "intValue": "42" } }]
+                  }]
+                }]
+              }]
+            })
+        );
+        assert!(request
+            .to_json_pretty()
+            .contains("\n  \"resourceSpans\": ["));
+    }
+
+    #[test]
+    fn to_json_round_trips() {
+        let json = br#"{
+          "resourceMetrics": [{
+            "scopeMetrics": [{
+              "metrics": [{
+                "name": "my_histogram",
+                "histogram": {
+                  "aggregationTemporality": 2,
+                  "dataPoints": [{
+                    "count": "2",
+                    "bucketCounts": ["1", "1"],
+                    "explicitBounds": [1.5],
+                    "min": "-Infinity",
+                    "max": "NaN"
+                  }]
+                }
+              }, {
+                "name": "my_gauge",
+                "gauge": { "dataPoints": [{ "asDouble": 0 }, { "asInt": "-3" }] }
+              }]
+            }]
+          }]
+        }"#;
+
+        let request = ExportMetricsServiceRequest::from_json(json).expect("Parsing of JSON failed");
+        let encoded = request.to_json();
+        let decoded = ExportMetricsServiceRequest::from_json(encoded.as_bytes()).unwrap();
+        assert_eq!(decoded.to_json(), encoded);
+
+        let value: serde_json::Value = serde_json::from_str(&encoded).unwrap();
+        let metrics = &value["resourceMetrics"][0]["scopeMetrics"][0]["metrics"];
+        assert_eq!(metrics[0]["histogram"]["dataPoints"][0]["max"], "NaN");
+        assert_eq!(
+            metrics[1]["gauge"]["dataPoints"],
+            serde_json::json!([{ "asDouble": 0.0 }, { "asInt": "-3" }])
+        );
+
+        let request = ExportLogsServiceRequest {
+            resource_logs: vec![logs::ResourceLogs {
+                scope_logs: vec![logs::ScopeLogs {
+                    log_records: vec![logs::LogRecord {
+                        time_unix_nano: u64::MAX,
+                        severity_number: SeverityNumber::SEVERITY_NUMBER_WARN,
+                        span_id: Cow::from(&[0xab; 8][..]),
+                        body: Some(AnyValue {
+                            value: AnyValueOneOfvalue::bytes_value(Cow::from(&b"hello"[..])),
+                            ..Default::default()
+                        }),
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        let decoded = ExportLogsServiceRequest::from_json(request.to_json().as_bytes()).unwrap();
+        assert_eq!(decoded, request);
+    }
+}
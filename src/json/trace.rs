@@ -0,0 +1,335 @@
+use serde_json::Value;
+
+use crate::descriptor;
+use crate::json::{Enumeration, Fields, FromJson, Object, ToJson};
+use crate::opentelemetry::trace::{
+    ExportTracePartialSuccess, ExportTraceServiceRequest, ExportTraceServiceResponse,
+    ResourceSpans, ScopeSpans, Span, SpanEvent, SpanKind, SpanLink, Status, StatusCode, TracesData,
+};
+use crate::DecodeOptions;
+
+impl ExportTraceServiceRequest<'static> {
+    /// Decodes a request from its OTLP/JSON encoding.
+    pub fn from_json(json: &[u8]) -> Result<Self, crate::Error> {
+        crate::json::decode(json)
+    }
+
+    /// Decodes a request from its OTLP/JSON encoding, enforcing the given
+    /// limits.
+    pub fn from_json_with(json: &[u8], options: &DecodeOptions) -> Result<Self, crate::Error> {
+        crate::json::decode_with(json, options, &descriptor::EXPORT_TRACE_SERVICE_REQUEST)
+    }
+}
+
+impl ExportTraceServiceResponse<'static> {
+    /// Decodes a response from its OTLP/JSON encoding.
+    pub fn from_json(json: &[u8]) -> Result<Self, crate::Error> {
+        crate::json::decode(json)
+    }
+}
+
+impl TracesData<'static> {
+    /// Decodes traces data from its OTLP/JSON encoding.
+    pub fn from_json(json: &[u8]) -> Result<Self, crate::Error> {
+        crate::json::decode(json)
+    }
+}
+
+impl ExportTraceServiceRequest<'_> {
+    /// Encodes the request using OTLP/JSON.
+    pub fn to_json(&self) -> String {
+        crate::json::encode(self, false)
+    }
+
+    /// Encodes the request using OTLP/JSON, pretty-printed.
+    pub fn to_json_pretty(&self) -> String {
+        crate::json::encode(self, true)
+    }
+}
+
+impl ExportTraceServiceResponse<'_> {
+    /// Encodes the response using OTLP/JSON.
+    pub fn to_json(&self) -> String {
+        crate::json::encode(self, false)
+    }
+
+    /// Encodes the response using OTLP/JSON, pretty-printed.
+    pub fn to_json_pretty(&self) -> String {
+        crate::json::encode(self, true)
+    }
+}
+
+impl TracesData<'_> {
+    /// Encodes the traces data using OTLP/JSON.
+    pub fn to_json(&self) -> String {
+        crate::json::encode(self, false)
+    }
+
+    /// Encodes the traces data using OTLP/JSON, pretty-printed.
+    pub fn to_json_pretty(&self) -> String {
+        crate::json::encode(self, true)
+    }
+}
+
+impl Enumeration for SpanKind {
+    fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "SPAN_KIND_UNSPECIFIED" => SpanKind::SPAN_KIND_UNSPECIFIED,
+            "SPAN_KIND_INTERNAL" => SpanKind::SPAN_KIND_INTERNAL,
+            "SPAN_KIND_SERVER" => SpanKind::SPAN_KIND_SERVER,
+            "SPAN_KIND_CLIENT" => SpanKind::SPAN_KIND_CLIENT,
+            "SPAN_KIND_PRODUCER" => SpanKind::SPAN_KIND_PRODUCER,
+            "SPAN_KIND_CONSUMER" => SpanKind::SPAN_KIND_CONSUMER,
+            _ => return None,
+        })
+    }
+}
+
+impl Enumeration for StatusCode {
+    fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "STATUS_CODE_UNSET" => StatusCode::STATUS_CODE_UNSET,
+            "STATUS_CODE_OK" => StatusCode::STATUS_CODE_OK,
+            "STATUS_CODE_ERROR" => StatusCode::STATUS_CODE_ERROR,
+            _ => return None,
+        })
+    }
+}
+
+impl FromJson for ExportTraceServiceRequest<'static> {
+    fn from_json(value: &Value) -> Result<Self, crate::Error> {
+        let fields = Fields::new(value)?;
+        Ok(ExportTraceServiceRequest {
+            resource_spans: fields.messages("resource_spans")?,
+            unknown_fields: Default::default(),
+        })
+    }
+}
+
+impl FromJson for ExportTraceServiceResponse<'static> {
+    fn from_json(value: &Value) -> Result<Self, crate::Error> {
+        let fields = Fields::new(value)?;
+        Ok(ExportTraceServiceResponse {
+            partial_success: fields.message("partial_success")?,
+            unknown_fields: Default::default(),
+        })
+    }
+}
+
+impl FromJson for ExportTracePartialSuccess<'static> {
+    fn from_json(value: &Value) -> Result<Self, crate::Error> {
+        let fields = Fields::new(value)?;
+        Ok(ExportTracePartialSuccess {
+            rejected_spans: fields.i64("rejected_spans")?,
+            error_message: fields.string("error_message")?,
+            unknown_fields: Default::default(),
+        })
+    }
+}
+
+impl FromJson for TracesData<'static> {
+    fn from_json(value: &Value) -> Result<Self, crate::Error> {
+        let fields = Fields::new(value)?;
+        Ok(TracesData {
+            resource_spans: fields.messages("resource_spans")?,
+            unknown_fields: Default::default(),
+        })
+    }
+}
+
+impl FromJson for ResourceSpans<'static> {
+    fn from_json(value: &Value) -> Result<Self, crate::Error> {
+        let fields = Fields::new(value)?;
+        Ok(ResourceSpans {
+            resource: fields.message("resource")?,
+            scope_spans: fields.messages("scope_spans")?,
+            schema_url: fields.string("schema_url")?,
+            unknown_fields: Default::default(),
+        })
+    }
+}
+
+impl FromJson for ScopeSpans<'static> {
+    fn from_json(value: &Value) -> Result<Self, crate::Error> {
+        let fields = Fields::new(value)?;
+        Ok(ScopeSpans {
+            scope: fields.message("scope")?,
+            spans: fields.messages("spans")?,
+            schema_url: fields.string("schema_url")?,
+            unknown_fields: Default::default(),
+        })
+    }
+}
+
+impl FromJson for Span<'static> {
+    fn from_json(value: &Value) -> Result<Self, crate::Error> {
+        let fields = Fields::new(value)?;
+        Ok(Span {
+            trace_id: fields.hex("trace_id")?,
+            span_id: fields.hex("span_id")?,
+            trace_state: fields.string("trace_state")?,
+            parent_span_id: fields.hex("parent_span_id")?,
+            flags: fields.u32("flags")?,
+            name: fields.string("name")?,
+            kind: fields.enumeration("kind")?,
+            start_time_unix_nano: fields.u64("start_time_unix_nano")?,
+            end_time_unix_nano: fields.u64("end_time_unix_nano")?,
+            attributes: fields.messages("attributes")?,
+            dropped_attributes_count: fields.u32("dropped_attributes_count")?,
+            events: fields.messages("events")?,
+            dropped_events_count: fields.u32("dropped_events_count")?,
+            links: fields.messages("links")?,
+            dropped_links_count: fields.u32("dropped_links_count")?,
+            status: fields.message("status")?,
+            unknown_fields: Default::default(),
+        })
+    }
+}
+
+impl FromJson for SpanEvent<'static> {
+    fn from_json(value: &Value) -> Result<Self, crate::Error> {
+        let fields = Fields::new(value)?;
+        Ok(SpanEvent {
+            time_unix_nano: fields.u64("time_unix_nano")?,
+            name: fields.string("name")?,
+            attributes: fields.messages("attributes")?,
+            dropped_attributes_count: fields.u32("dropped_attributes_count")?,
+            unknown_fields: Default::default(),
+        })
+    }
+}
+
+impl FromJson for SpanLink<'static> {
+    fn from_json(value: &Value) -> Result<Self, crate::Error> {
+        let fields = Fields::new(value)?;
+        Ok(SpanLink {
+            trace_id: fields.hex("trace_id")?,
+            span_id: fields.hex("span_id")?,
+            trace_state: fields.string("trace_state")?,
+            attributes: fields.messages("attributes")?,
+            dropped_attributes_count: fields.u32("dropped_attributes_count")?,
+            flags: fields.u32("flags")?,
+            unknown_fields: Default::default(),
+        })
+    }
+}
+
+impl FromJson for Status<'static> {
+    fn from_json(value: &Value) -> Result<Self, crate::Error> {
+        let fields = Fields::new(value)?;
+        Ok(Status {
+            message: fields.string("message")?,
+            code: fields.enumeration("code")?,
+            unknown_fields: Default::default(),
+        })
+    }
+}
+
+impl ToJson for ExportTraceServiceRequest<'_> {
+    fn to_json(&self) -> Value {
+        Object::new()
+            .messages("resource_spans", &self.resource_spans)
+            .build()
+    }
+}
+
+impl ToJson for ExportTraceServiceResponse<'_> {
+    fn to_json(&self) -> Value {
+        Object::new()
+            .message("partial_success", self.partial_success.as_ref())
+            .build()
+    }
+}
+
+impl ToJson for ExportTracePartialSuccess<'_> {
+    fn to_json(&self) -> Value {
+        Object::new()
+            .i64("rejected_spans", self.rejected_spans)
+            .string("error_message", &self.error_message)
+            .build()
+    }
+}
+
+impl ToJson for TracesData<'_> {
+    fn to_json(&self) -> Value {
+        Object::new()
+            .messages("resource_spans", &self.resource_spans)
+            .build()
+    }
+}
+
+impl ToJson for ResourceSpans<'_> {
+    fn to_json(&self) -> Value {
+        Object::new()
+            .message("resource", self.resource.as_ref())
+            .messages("scope_spans", &self.scope_spans)
+            .string("schema_url", &self.schema_url)
+            .build()
+    }
+}
+
+impl ToJson for ScopeSpans<'_> {
+    fn to_json(&self) -> Value {
+        Object::new()
+            .message("scope", self.scope.as_ref())
+            .messages("spans", &self.spans)
+            .string("schema_url", &self.schema_url)
+            .build()
+    }
+}
+
+impl ToJson for Span<'_> {
+    fn to_json(&self) -> Value {
+        Object::new()
+            .hex("trace_id", &self.trace_id)
+            .hex("span_id", &self.span_id)
+            .string("trace_state", &self.trace_state)
+            .hex("parent_span_id", &self.parent_span_id)
+            .u32("flags", self.flags)
+            .string("name", &self.name)
+            .i32("kind", self.kind as i32)
+            .u64("start_time_unix_nano", self.start_time_unix_nano)
+            .u64("end_time_unix_nano", self.end_time_unix_nano)
+            .messages("attributes", &self.attributes)
+            .u32("dropped_attributes_count", self.dropped_attributes_count)
+            .messages("events", &self.events)
+            .u32("dropped_events_count", self.dropped_events_count)
+            .messages("links", &self.links)
+            .u32("dropped_links_count", self.dropped_links_count)
+            .message("status", self.status.as_ref())
+            .build()
+    }
+}
+
+impl ToJson for SpanEvent<'_> {
+    fn to_json(&self) -> Value {
+        Object::new()
+            .u64("time_unix_nano", self.time_unix_nano)
+            .string("name", &self.name)
+            .messages("attributes", &self.attributes)
+            .u32("dropped_attributes_count", self.dropped_attributes_count)
+            .build()
+    }
+}
+
+impl ToJson for SpanLink<'_> {
+    fn to_json(&self) -> Value {
+        Object::new()
+            .hex("trace_id", &self.trace_id)
+            .hex("span_id", &self.span_id)
+            .string("trace_state", &self.trace_state)
+            .messages("attributes", &self.attributes)
+            .u32("dropped_attributes_count", self.dropped_attributes_count)
+            .u32("flags", self.flags)
+            .build()
+    }
+}
+
+impl ToJson for Status<'_> {
+    fn to_json(&self) -> Value {
+        Object::new()
+            .string("message", &self.message)
+            .i32("code", self.code as i32)
+            .build()
+    }
+}
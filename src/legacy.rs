@@ -0,0 +1,453 @@
+// Pre-1.0 OTLP payloads carry their spans, logs and metrics in the
+// deprecated `instrumentation_library_*` field (number 1000) of each
+// `Resource*`, holding `InstrumentationLibrary*` messages. Those have the
+// same wire layout as the `Scope*` messages, with `InstrumentationLibrary`
+// matching the `name` and `version` of `InstrumentationScope`, so they are
+// decoded as such and appended to the `scope_*` entries. Upgraded fields are
+// not kept among the unknown fields.
+
+use quick_protobuf::BytesReader;
+
+use crate::descriptor;
+
+use crate::opentelemetry::logs::{ExportLogsServiceRequest, ResourceLogs, ScopeLogs};
+use crate::opentelemetry::metrics::{ExportMetricsServiceRequest, ResourceMetrics, ScopeMetrics};
+use crate::opentelemetry::trace::{
+    ExportTraceServiceRequest, ResourceSpans, ScopeSpans, Status, StatusCode,
+};
+use crate::stream::{read_message, FieldSlices, TAG_RESOURCE_ITEMS, TAG_SCOPE_ITEMS};
+use crate::{DecodeOptions, UnknownFields};
+
+pub(crate) const TAG_INSTRUMENTATION_LIBRARY_ITEMS: u32 = (1000 << 3) | 2;
+const TAG_SPANS: u32 = 18;
+const TAG_STATUS: u32 = 122;
+const TAG_DEPRECATED_CODE: u32 = 8;
+
+// `DEPRECATED_STATUS_CODE_OK`, every other deprecated code is an error
+const DEPRECATED_STATUS_CODE_OK: i32 = 0;
+
+fn legacy_scopes<'a, M: quick_protobuf::MessageRead<'a>>(
+    resource: &'a [u8],
+) -> impl Iterator<Item = Result<(M, &'a [u8]), crate::Error>> {
+    FieldSlices::new(resource, TAG_INSTRUMENTATION_LIBRARY_ITEMS)
+        .map(|slice| slice.and_then(|slice| Ok((read_message(slice)?, slice))))
+}
+
+// The fields of an encoded export request other than its resources
+fn unknown_fields(bytes: &[u8]) -> Result<UnknownFields, crate::Error> {
+    let mut unknown_fields = UnknownFields::default();
+    let mut reader = BytesReader::from_bytes(bytes);
+    while !reader.is_eof() {
+        match reader.next_tag(bytes)? {
+            TAG_RESOURCE_ITEMS => reader.read_unknown(bytes, TAG_RESOURCE_ITEMS)?,
+            tag => unknown_fields.read(&mut reader, bytes, tag)?,
+        }
+    }
+
+    Ok(unknown_fields)
+}
+
+fn deprecated_code(status: &[u8]) -> Result<Option<i32>, crate::Error> {
+    let mut reader = BytesReader::from_bytes(status);
+    let mut code = None;
+
+    while !reader.is_eof() {
+        match reader.next_tag(status)? {
+            TAG_DEPRECATED_CODE => code = Some(reader.read_int32(status)?),
+            tag => reader.read_unknown(status, tag)?,
+        }
+    }
+
+    Ok(code)
+}
+
+// A status without a code but with a deprecated error code is an error
+fn upgrade_status(status: &mut Status, encoded: &[u8]) -> Result<(), crate::Error> {
+    status.unknown_fields.remove(TAG_DEPRECATED_CODE >> 3);
+    if status.code == StatusCode::STATUS_CODE_UNSET {
+        if let Some(code) = deprecated_code(encoded)? {
+            if code != DEPRECATED_STATUS_CODE_OK {
+                status.code = StatusCode::STATUS_CODE_ERROR;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn upgrade_statuses(scope_spans: &mut ScopeSpans, encoded: &[u8]) -> Result<(), crate::Error> {
+    for (span, encoded) in scope_spans
+        .spans
+        .iter_mut()
+        .zip(FieldSlices::new(encoded, TAG_SPANS))
+    {
+        // The last occurrence of a message field is the one decoded
+        let status = FieldSlices::new(encoded?, TAG_STATUS).last().transpose()?;
+        if let (Some(status), Some(encoded)) = (span.status.as_mut(), status) {
+            upgrade_status(status, encoded)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn resource_spans(encoded: &[u8]) -> Result<ResourceSpans<'_>, crate::Error> {
+    let mut resource_spans: ResourceSpans = read_message(encoded)?;
+    resource_spans
+        .unknown_fields
+        .remove(TAG_INSTRUMENTATION_LIBRARY_ITEMS >> 3);
+
+    for (scope_spans, encoded) in resource_spans
+        .scope_spans
+        .iter_mut()
+        .zip(FieldSlices::new(encoded, TAG_SCOPE_ITEMS))
+    {
+        upgrade_statuses(scope_spans, encoded?)?;
+    }
+
+    for legacy in legacy_scopes(encoded) {
+        let (mut scope_spans, encoded): (ScopeSpans, _) = legacy?;
+        upgrade_statuses(&mut scope_spans, encoded)?;
+        resource_spans.scope_spans.push(scope_spans);
+    }
+
+    Ok(resource_spans)
+}
+
+fn resource_logs(encoded: &[u8]) -> Result<ResourceLogs<'_>, crate::Error> {
+    let mut resource_logs: ResourceLogs = read_message(encoded)?;
+    resource_logs
+        .unknown_fields
+        .remove(TAG_INSTRUMENTATION_LIBRARY_ITEMS >> 3);
+    for legacy in legacy_scopes(encoded) {
+        let (scope_logs, _): (ScopeLogs, _) = legacy?;
+        resource_logs.scope_logs.push(scope_logs);
+    }
+
+    Ok(resource_logs)
+}
+
+fn resource_metrics(encoded: &[u8]) -> Result<ResourceMetrics<'_>, crate::Error> {
+    let mut resource_metrics: ResourceMetrics = read_message(encoded)?;
+    resource_metrics
+        .unknown_fields
+        .remove(TAG_INSTRUMENTATION_LIBRARY_ITEMS >> 3);
+    for legacy in legacy_scopes(encoded) {
+        let (scope_metrics, _): (ScopeMetrics, _) = legacy?;
+        resource_metrics.scope_metrics.push(scope_metrics);
+    }
+
+    Ok(resource_metrics)
+}
+
+impl<'a> ExportTraceServiceRequest<'a> {
+    /// Decodes a request that may come from a pre-1.0 OTLP exporter.
+    ///
+    /// Deprecated `instrumentation_library_spans` are upgraded to
+    /// `scope_spans`, and a `Status` with no code but a deprecated error
+    /// code gets `STATUS_CODE_ERROR`.
+    pub fn decode_legacy(bytes: &'a [u8]) -> Result<Self, crate::Error> {
+        Ok(ExportTraceServiceRequest {
+            resource_spans: FieldSlices::new(bytes, TAG_RESOURCE_ITEMS)
+                .map(|encoded| resource_spans(encoded?))
+                .collect::<Result<_, _>>()?,
+            unknown_fields: unknown_fields(bytes)?,
+        })
+    }
+
+    /// Decodes a request that may come from a pre-1.0 OTLP exporter,
+    /// enforcing the given limits.
+    ///
+    /// The deprecated fields count against the limits like the current ones.
+    pub fn decode_legacy_with(
+        bytes: &'a [u8],
+        options: &DecodeOptions,
+    ) -> Result<Self, crate::Error> {
+        options.check_encoded(bytes, &descriptor::EXPORT_TRACE_SERVICE_REQUEST)?;
+        Self::decode_legacy(bytes)
+    }
+}
+
+impl<'a> ExportLogsServiceRequest<'a> {
+    /// Decodes a request that may come from a pre-1.0 OTLP exporter.
+    ///
+    /// Deprecated `instrumentation_library_logs` are upgraded to
+    /// `scope_logs`.
+    pub fn decode_legacy(bytes: &'a [u8]) -> Result<Self, crate::Error> {
+        Ok(ExportLogsServiceRequest {
+            resource_logs: FieldSlices::new(bytes, TAG_RESOURCE_ITEMS)
+                .map(|encoded| resource_logs(encoded?))
+                .collect::<Result<_, _>>()?,
+            unknown_fields: unknown_fields(bytes)?,
+        })
+    }
+
+    /// Decodes a request that may come from a pre-1.0 OTLP exporter,
+    /// enforcing the given limits.
+    ///
+    /// The deprecated fields count against the limits like the current ones.
+    pub fn decode_legacy_with(
+        bytes: &'a [u8],
+        options: &DecodeOptions,
+    ) -> Result<Self, crate::Error> {
+        options.check_encoded(bytes, &descriptor::EXPORT_LOGS_SERVICE_REQUEST)?;
+        Self::decode_legacy(bytes)
+    }
+}
+
+impl<'a> ExportMetricsServiceRequest<'a> {
+    /// Decodes a request that may come from a pre-1.0 OTLP exporter.
+    ///
+    /// Deprecated `instrumentation_library_metrics` are upgraded to
+    /// `scope_metrics`.
+    pub fn decode_legacy(bytes: &'a [u8]) -> Result<Self, crate::Error> {
+        Ok(ExportMetricsServiceRequest {
+            resource_metrics: FieldSlices::new(bytes, TAG_RESOURCE_ITEMS)
+                .map(|encoded| resource_metrics(encoded?))
+                .collect::<Result<_, _>>()?,
+            unknown_fields: unknown_fields(bytes)?,
+        })
+    }
+
+    /// Decodes a request that may come from a pre-1.0 OTLP exporter,
+    /// enforcing the given limits.
+    ///
+    /// The deprecated fields count against the limits like the current ones.
+    pub fn decode_legacy_with(
+        bytes: &'a [u8],
+        options: &DecodeOptions,
+    ) -> Result<Self, crate::Error> {
+        options.check_encoded(bytes, &descriptor::EXPORT_METRICS_SERVICE_REQUEST)?;
+        Self::decode_legacy(bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TAG_INSTRUMENTATION_LIBRARY_ITEMS;
+    use crate::opentelemetry::common::InstrumentationScope;
+    use crate::opentelemetry::logs::{ExportLogsServiceRequest, LogRecord, ScopeLogs};
+    use crate::opentelemetry::metrics::{
+        ExportMetricsServiceRequest, Gauge, Metric, MetricOneOfdata, NumberDataPoint, ScopeMetrics,
+    };
+    use crate::opentelemetry::trace::{
+        ExportTraceServiceRequest, ScopeSpans, Span, Status, StatusCode,
+    };
+    use crate::DecodeOptions;
+    use quick_protobuf::{MessageWrite, Writer};
+    use std::borrow::Cow;
+
+    fn encode<M: MessageWrite>(message: &M) -> Vec<u8> {
+        let mut out = vec![];
+        message
+            .write_message(&mut Writer::new(&mut out))
+            .expect("failed to write");
+        out
+    }
+
+    // An export request with one resource holding the given fields
+    fn request(fields: &[(u32, Vec<u8>)]) -> Vec<u8> {
+        let mut resource = vec![];
+        {
+            let mut writer = Writer::new(&mut resource);
+            for (tag, bytes) in fields {
+                writer.write_tag(*tag).expect("failed to write");
+                writer.write_bytes(bytes).expect("failed to write");
+            }
+        }
+
+        let mut out = vec![];
+        let mut writer = Writer::new(&mut out);
+        writer.write_tag(10).expect("failed to write");
+        writer.write_bytes(&resource).expect("failed to write");
+        out
+    }
+
+    fn library() -> Option<InstrumentationScope<'static>> {
+        Some(InstrumentationScope {
+            name: Cow::from("io.opentelemetry.jdbc"),
+            version: Cow::from("1.0.0"),
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn decode_legacy_upgrades_instrumentation_library_logs() {
+        let legacy = ScopeLogs {
+            scope: library(),
+            log_records: vec![LogRecord {
+                severity_text: Cow::from("INFO"),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        let current = ScopeLogs::default();
+        let out = request(&[
+            (TAG_INSTRUMENTATION_LIBRARY_ITEMS, encode(&legacy)),
+            (18, encode(&current)),
+        ]);
+
+        // Without the compatibility decoder the legacy field is dropped
+        let request = ExportLogsServiceRequest::try_from(&out[..]).expect("Parsing failed");
+        assert_eq!(request.resource_logs[0].scope_logs, vec![current.clone()]);
+
+        let request = ExportLogsServiceRequest::decode_legacy(&out).expect("Parsing failed");
+        assert_eq!(request.resource_logs[0].scope_logs, vec![current, legacy]);
+    }
+
+    #[test]
+    fn decode_legacy_upgrades_instrumentation_library_metrics() {
+        let legacy = ScopeMetrics {
+            scope: library(),
+            metrics: vec![Metric {
+                name: Cow::from("jdbc.connections"),
+                data: MetricOneOfdata::gauge(Gauge {
+                    data_points: vec![NumberDataPoint::default()],
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        let current = ScopeMetrics::default();
+        let out = request(&[
+            (TAG_INSTRUMENTATION_LIBRARY_ITEMS, encode(&legacy)),
+            (18, encode(&current)),
+        ]);
+
+        let request = ExportMetricsServiceRequest::try_from(&out[..]).expect("Parsing failed");
+        assert_eq!(
+            request.resource_metrics[0].scope_metrics,
+            vec![current.clone()]
+        );
+
+        let request = ExportMetricsServiceRequest::decode_legacy(&out).expect("Parsing failed");
+        assert_eq!(
+            request.resource_metrics[0].scope_metrics,
+            vec![current, legacy]
+        );
+    }
+
+    #[test]
+    fn decode_legacy_with_limits() {
+        let legacy = ScopeLogs {
+            scope: library(),
+            log_records: vec![LogRecord::default(); 3],
+            ..Default::default()
+        };
+        let out = request(&[(TAG_INSTRUMENTATION_LIBRARY_ITEMS, encode(&legacy))]);
+
+        let options = DecodeOptions::default();
+        let request =
+            ExportLogsServiceRequest::decode_legacy_with(&out, &options).expect("Parsing failed");
+        assert_eq!(request.resource_logs[0].scope_logs, vec![legacy]);
+
+        // Records in legacy scopes count against the limits
+        let options = DecodeOptions {
+            max_repeated_elements: 2,
+            ..Default::default()
+        };
+        assert!(matches!(
+            ExportLogsServiceRequest::decode_legacy_with(&out, &options),
+            Err(crate::Error::TooManyElements { limit: 2 })
+        ));
+        let options = DecodeOptions {
+            max_total_bytes: out.len() - 1,
+            ..Default::default()
+        };
+        assert!(matches!(
+            ExportLogsServiceRequest::decode_legacy_with(&out, &options),
+            Err(crate::Error::MessageTooLarge { .. })
+        ));
+    }
+
+    #[test]
+    fn decode_legacy_maps_deprecated_status_codes() {
+        // Status { deprecated_code: DEPRECATED_STATUS_CODE_UNKNOWN_ERROR (2), message: "failed" }
+        let deprecated_error = [0x08, 0x02, 0x12, 0x06, b'f', b'a', b'i', b'l', b'e', b'd'];
+        // Status { deprecated_code: DEPRECATED_STATUS_CODE_OK (0) }
+        let deprecated_ok = [0x08, 0x00];
+        // Status { deprecated_code: 2, code: STATUS_CODE_OK (1) }
+        let both = [0x08, 0x02, 0x18, 0x01];
+
+        let span = |status: &[u8]| {
+            let mut span = encode(&Span {
+                name: Cow::from("span"),
+                ..Default::default()
+            });
+            let mut writer = Writer::new(&mut span);
+            writer.write_tag(122).expect("failed to write");
+            writer.write_bytes(status).expect("failed to write");
+            span
+        };
+        let scope = |spans: Vec<Vec<u8>>| {
+            let mut scope = encode(&ScopeSpans {
+                scope: library(),
+                ..Default::default()
+            });
+            let mut writer = Writer::new(&mut scope);
+            for span in spans {
+                writer.write_tag(18).expect("failed to write");
+                writer.write_bytes(&span).expect("failed to write");
+            }
+            scope
+        };
+
+        let out = request(&[
+            (18, scope(vec![span(&deprecated_error)])),
+            (
+                TAG_INSTRUMENTATION_LIBRARY_ITEMS,
+                scope(vec![span(&deprecated_ok), span(&both)]),
+            ),
+        ]);
+        let request = ExportTraceServiceRequest::decode_legacy(&out).expect("Parsing failed");
+
+        let scope_spans = &request.resource_spans[0].scope_spans;
+        assert_eq!(scope_spans.len(), 2);
+        assert_eq!(scope_spans[1].scope, library());
+
+        let status = |scope: usize, span: usize| -> &Status {
+            scope_spans[scope].spans[span].status.as_ref().unwrap()
+        };
+        assert_eq!(status(0, 0).code, StatusCode::STATUS_CODE_ERROR);
+        assert_eq!(status(0, 0).message, "failed");
+        assert!(status(0, 0).unknown_fields.is_empty());
+        assert_eq!(status(1, 0).code, StatusCode::STATUS_CODE_UNSET);
+        assert_eq!(status(1, 1).code, StatusCode::STATUS_CODE_OK);
+    }
+
+    #[cfg(feature = "unknown-fields")]
+    #[test]
+    fn decode_legacy_does_not_keep_upgraded_fields_as_unknown() {
+        let legacy = ScopeLogs {
+            scope: library(),
+            ..Default::default()
+        };
+        let mut out = request(&[
+            (TAG_INSTRUMENTATION_LIBRARY_ITEMS, encode(&legacy)),
+            (2000 << 3 | 2, b"new".to_vec()),
+        ]);
+        Writer::new(&mut out)
+            .write_with_tag(2001 << 3, |w| w.write_uint64(1))
+            .expect("failed to write");
+
+        // Decoded as is, the deprecated field is an unknown one
+        let request = ExportLogsServiceRequest::try_from(&out[..]).expect("Parsing failed");
+        assert!(request.resource_logs[0].scope_logs.is_empty());
+        assert_eq!(encode(&request), out);
+
+        // Upgraded, the scope is sent once, as `scope_logs`, along with the
+        // other unknown fields
+        let request = ExportLogsServiceRequest::decode_legacy(&out).expect("Parsing failed");
+        let mut unknown = vec![];
+        Writer::new(&mut unknown)
+            .write_with_tag(2000 << 3 | 2, |w| w.write_bytes(b"new"))
+            .expect("failed to write");
+        assert_eq!(request.resource_logs[0].unknown_fields.as_bytes(), unknown);
+        assert!(!request.unknown_fields.is_empty());
+        let out = encode(&request);
+        let decoded = ExportLogsServiceRequest::decode_legacy(&out).expect("Parsing failed");
+        assert_eq!(decoded, request);
+        assert_eq!(decoded.resource_logs[0].scope_logs, vec![legacy]);
+    }
+}
@@ -1,20 +1,77 @@
 use quick_protobuf::MessageRead;
 
+mod any_value;
+mod attributes;
+mod batch;
+mod builder;
+mod bytes_request;
+mod canonical;
+#[cfg(feature = "client")]
+mod client;
+mod compression;
+mod descriptor;
+mod encode;
+mod grpc;
+#[cfg(feature = "server")]
+mod grpc_server;
+mod http;
+#[cfg(feature = "json")]
+mod json;
+mod legacy;
+mod limits;
 mod opentelemetry_types;
+mod owned;
+mod retain;
+#[cfg(feature = "server")]
+mod server;
+mod split;
+mod stream;
+mod unknown;
 mod validation;
 
+pub use crate::attributes::Attributes;
+pub use crate::builder::{
+    LogRecordBuilder, LogsRequestBuilder, MetricBuilder, MetricsRequestBuilder, SpanBuilder,
+    TraceRequestBuilder,
+};
+pub use crate::bytes_request::BytesRequest;
+pub use crate::canonical::attributes_content_hash;
+#[cfg(feature = "client")]
+pub use crate::client::{Exporter, ExporterConfig, PartialSuccess, Protocol};
+pub use crate::compression::{decompress, DecodedRequest};
+pub use crate::grpc::{
+    write_compressed_grpc_frame, write_grpc_frame, GrpcCode, GrpcFrame, GrpcFrames, GrpcStatus,
+    GRPC_CONTENT_TYPE, GRPC_FRAME_HEADER_LEN,
+};
+#[cfg(feature = "server")]
+pub use crate::grpc_server::GrpcServer;
+pub use crate::http::{
+    handle_otlp_http, handle_otlp_http_with, ExportRequest, OtlpHttpResponse, JSON_CONTENT_TYPE,
+    PROTOBUF_CONTENT_TYPE,
+};
+pub use crate::limits::DecodeOptions;
+pub use crate::owned::IntoOwned;
+#[cfg(feature = "server")]
+pub use crate::server::{ExportHandler, Server, ServerConfig};
+pub use crate::stream::{ResourceStream, ScopeStream, ScopedResource};
+pub use crate::unknown::UnknownFields;
+
+/// Version of the OTLP protocol release the bundled protos come from.
+pub const OTLP_VERSION: &str = "1.7.0";
+
 pub mod opentelemetry {
     pub mod common {
         pub use crate::opentelemetry_types::opentelemetry::proto::common::v1::{
-            mod_AnyValue::OneOfvalue as AnyValueOneOfvalue, AnyValue, ArrayValue,
-            InstrumentationScope, KeyValue, Resource,
+            mod_AnyValue::OneOfvalue as AnyValueOneOfvalue, AnyValue, ArrayValue, EntityRef,
+            InstrumentationScope, KeyValue, KeyValueList, Resource,
         };
         pub use crate::validation::common::CommonValidate as Validate;
     }
 
     pub mod metrics {
         pub use crate::opentelemetry::common::{
-            AnyValue, AnyValueOneOfvalue, ArrayValue, InstrumentationScope, KeyValue, Resource,
+            AnyValue, AnyValueOneOfvalue, ArrayValue, EntityRef, InstrumentationScope, KeyValue,
+            KeyValueList, Resource,
         };
         pub use crate::opentelemetry_types::opentelemetry::proto::metrics::v1::{
             mod_Exemplar::OneOfvalue as ExemplarOneOfvalue,
@@ -22,33 +79,55 @@ pub mod opentelemetry {
             mod_Metric::OneOfdata as MetricOneOfdata,
             mod_NumberDataPoint::OneOfvalue as NumberDataPointOneOfvalue,
             mod_SummaryDataPoint::ValueAtQuantile as SummaryDataPointValueAtQuantile,
-            AggregationTemporality, Exemplar, ExponentialHistogram, ExponentialHistogramDataPoint,
-            ExportMetricsServiceRequest, Gauge, Histogram, HistogramDataPoint, Metric,
-            NumberDataPoint, ResourceMetrics, ScopeMetrics, Sum, Summary, SummaryDataPoint,
+            AggregationTemporality, DataPointFlags, Exemplar, ExponentialHistogram,
+            ExponentialHistogramDataPoint, ExportMetricsPartialSuccess,
+            ExportMetricsServiceRequest, ExportMetricsServiceResponse, Gauge, Histogram,
+            HistogramDataPoint, Metric, MetricsData, NumberDataPoint, ResourceMetrics,
+            ScopeMetrics, Sum, Summary, SummaryDataPoint,
         };
+        pub use crate::retain::DataPoint;
         pub use crate::validation::metrics::MetricValidate as Validate;
     }
 
     pub mod logs {
         pub use crate::opentelemetry::common::{
-            AnyValue, AnyValueOneOfvalue, ArrayValue, InstrumentationScope, KeyValue, Resource,
+            AnyValue, AnyValueOneOfvalue, ArrayValue, EntityRef, InstrumentationScope, KeyValue,
+            KeyValueList, Resource,
         };
         pub use crate::opentelemetry_types::opentelemetry::proto::logs::v1::{
-            ExportLogsServiceRequest, LogRecord, ResourceLogs, ScopeLogs, SeverityNumber,
+            ExportLogsPartialSuccess, ExportLogsServiceRequest, ExportLogsServiceResponse,
+            LogRecord, LogRecordFlags, LogsData, ResourceLogs, ScopeLogs, SeverityNumber,
         };
         pub use crate::validation::logs::LogValidate as Validate;
     }
 
     pub mod trace {
         pub use crate::opentelemetry::common::{
-            AnyValue, AnyValueOneOfvalue, ArrayValue, InstrumentationScope, KeyValue, Resource,
+            AnyValue, AnyValueOneOfvalue, ArrayValue, EntityRef, InstrumentationScope, KeyValue,
+            KeyValueList, Resource,
         };
         pub use crate::opentelemetry_types::opentelemetry::proto::trace::v1::{
             mod_Span::Event as SpanEvent, mod_Span::Link as SpanLink, mod_Span::SpanKind,
-            mod_Status::StatusCode, ResourceSpans, ScopeSpans, Span, Status, ExportTraceServiceRequest,
+            mod_Status::StatusCode, ExportTracePartialSuccess, ExportTraceServiceRequest,
+            ExportTraceServiceResponse, ResourceSpans, ScopeSpans, Span, SpanFlags, Status,
+            TracesData,
         };
         pub use crate::validation::trace::TraceValidate as Validate;
     }
+
+    pub mod profiles {
+        pub use crate::opentelemetry::common::{
+            AnyValue, AnyValueOneOfvalue, ArrayValue, EntityRef, InstrumentationScope, KeyValue,
+            KeyValueList, Resource,
+        };
+        pub use crate::opentelemetry_types::opentelemetry::proto::profiles::v1development::{
+            AggregationTemporality, AttributeUnit, ExportProfilesPartialSuccess,
+            ExportProfilesServiceRequest, ExportProfilesServiceResponse, Function, Line, Link,
+            Location, Mapping, Profile, ProfilesData, ResourceProfiles, Sample, ScopeProfiles,
+            ValueType,
+        };
+        pub use crate::validation::profiles::ProfileValidate as Validate;
+    }
 }
 
 #[derive(thiserror::Error, std::fmt::Debug)]
@@ -63,6 +142,53 @@ pub enum Error {
     UrlParse(#[from] url::ParseError),
     #[error("empty label value")]
     EmptyLabelValue,
+    #[error("message of {size} bytes exceeds the limit of {limit} bytes")]
+    MessageTooLarge { size: usize, limit: usize },
+    #[error("repeated field exceeds the limit of {limit} elements")]
+    TooManyElements { limit: usize },
+    #[error("message exceeds the limit of {limit} elements in total")]
+    TooManyTotalElements { limit: usize },
+    #[error("AnyValue nesting exceeds the limit of {limit}")]
+    NestingTooDeep { limit: usize },
+    #[error("field of {size} bytes exceeds the limit of {limit} bytes")]
+    FieldTooLarge { size: usize, limit: usize },
+    #[error("string_table must start with the empty string")]
+    InvalidStringTable,
+    #[error("invalid entity reference: {0}")]
+    InvalidEntityRef(String),
+    #[error("{field} {index} is out of bounds for {len} entries")]
+    IndexOutOfBounds {
+        field: &'static str,
+        index: i64,
+        len: usize,
+    },
+    #[error("invalid gRPC frame: {0}")]
+    InvalidGrpcFrame(String),
+    #[error("unsupported encoding {0:?}")]
+    UnsupportedEncoding(String),
+    #[error("invalid compressed body: {0}")]
+    Decompression(String),
+    #[error("decompressed body exceeds the limit of {limit} bytes")]
+    DecompressedTooLarge { limit: usize },
+    #[error("cannot build: {0}")]
+    Build(String),
+    #[error("expected {expected} value, found {found}")]
+    UnexpectedAnyValue {
+        expected: &'static str,
+        found: &'static str,
+    },
+    #[cfg(feature = "client")]
+    #[error("invalid endpoint {0:?}, expected an http:// URL")]
+    InvalidEndpoint(String),
+    #[cfg(feature = "client")]
+    #[error("export failed: {0}")]
+    ExportFailed(String),
+    #[cfg(feature = "json")]
+    #[error(transparent)]
+    JsonParse(#[from] serde_json::Error),
+    #[cfg(feature = "json")]
+    #[error("invalid OTLP/JSON: {0}")]
+    InvalidJson(String),
 }
 
 use std::convert::TryFrom;
@@ -93,10 +219,16 @@ impl<'a> TryFrom<&'a [u8]> for opentelemetry::trace::ExportTraceServiceRequest<'
 
     fn try_from(bytes: &'a [u8]) -> Result<Self, Self::Error> {
         let mut reader = quick_protobuf::BytesReader::from_bytes(bytes);
-        Ok(opentelemetry::trace::ExportTraceServiceRequest::from_reader(
-            &mut reader,
-            bytes,
-        )?)
+        Ok(opentelemetry::trace::ExportTraceServiceRequest::from_reader(&mut reader, bytes)?)
+    }
+}
+
+impl<'a> TryFrom<&'a [u8]> for opentelemetry::profiles::ExportProfilesServiceRequest<'a> {
+    type Error = crate::Error;
+
+    fn try_from(bytes: &'a [u8]) -> Result<Self, Self::Error> {
+        let mut reader = quick_protobuf::BytesReader::from_bytes(bytes);
+        Ok(opentelemetry::profiles::ExportProfilesServiceRequest::from_reader(&mut reader, bytes)?)
     }
 }
 
@@ -105,6 +237,7 @@ mod tests {
     use crate::validation::common::CommonValidate;
     use crate::validation::logs::LogValidate;
     use crate::validation::metrics::MetricValidate;
+    use crate::validation::profiles::ProfileValidate;
     use crate::validation::trace::TraceValidate;
     use quick_protobuf::{BytesReader, Writer};
     use std::borrow::Cow;
@@ -124,7 +257,9 @@ mod tests {
             key: Cow::from("test"),
             value: Some(AnyValue {
                 value: AnyValueOneOfvalue::string_value(Cow::from("test")),
+                ..Default::default()
             }),
+            ..Default::default()
         };
 
         let metrics_data = ExportMetricsServiceRequest {
@@ -132,6 +267,8 @@ mod tests {
                 resource: Some(Resource {
                     attributes: vec![key_value.clone()],
                     dropped_attributes_count: 10,
+                    entity_refs: vec![],
+                    ..Default::default()
                 }),
                 scope_metrics: vec![ScopeMetrics {
                     scope: Some(InstrumentationScope {
@@ -139,6 +276,7 @@ mod tests {
                         version: Cow::from("1.2.3"),
                         attributes: vec![key_value.clone()],
                         dropped_attributes_count: 10,
+                        ..Default::default()
                     }),
                     metrics: vec![Metric {
                         name: Cow::from("test_name"),
@@ -156,15 +294,23 @@ mod tests {
                                     value: ExemplarOneOfvalue::as_int(10),
                                     span_id: Cow::from("test".as_bytes()),
                                     trace_id: Cow::from("test".as_bytes()),
+                                    ..Default::default()
                                 }],
                                 flags: 1,
+                                ..Default::default()
                             }],
+                            ..Default::default()
                         }),
+                        metadata: vec![],
+                        ..Default::default()
                     }],
                     schema_url: Cow::from("https://some_url.com"),
+                    ..Default::default()
                 }],
                 schema_url: Cow::from("https://some_url.com"),
+                ..Default::default()
             }],
+            ..Default::default()
         };
         {
             let mut writer = Writer::new(&mut out);
@@ -216,7 +362,9 @@ mod tests {
             key: Cow::from("test"),
             value: Some(AnyValue {
                 value: AnyValueOneOfvalue::string_value(Cow::from("test")),
+                ..Default::default()
             }),
+            ..Default::default()
         };
 
         let metrics_data = ExportMetricsServiceRequest {
@@ -224,6 +372,8 @@ mod tests {
                 resource: Some(Resource {
                     attributes: vec![key_value.clone()],
                     dropped_attributes_count: 10,
+                    entity_refs: vec![],
+                    ..Default::default()
                 }),
                 scope_metrics: vec![ScopeMetrics {
                     scope: Some(InstrumentationScope {
@@ -231,6 +381,7 @@ mod tests {
                         version: Cow::from("1.2.3"),
                         attributes: vec![key_value.clone()],
                         dropped_attributes_count: 10,
+                        ..Default::default()
                     }),
                     metrics: vec![Metric {
                         name: Cow::from("test_name"),
@@ -248,18 +399,26 @@ mod tests {
                                     value: ExemplarOneOfvalue::as_int(10),
                                     span_id: Cow::from("test".as_bytes()),
                                     trace_id: Cow::from("test".as_bytes()),
+                                    ..Default::default()
                                 }],
                                 flags: 1,
+                                ..Default::default()
                             }],
                             aggregation_temporality:
                                 AggregationTemporality::AGGREGATION_TEMPORALITY_UNSPECIFIED,
                             is_monotonic: true,
+                            ..Default::default()
                         }),
+                        metadata: vec![],
+                        ..Default::default()
                     }],
                     schema_url: Cow::from("https://some_url.com"),
+                    ..Default::default()
                 }],
                 schema_url: Cow::from("https://some_url.com"),
+                ..Default::default()
             }],
+            ..Default::default()
         };
         {
             let mut writer = Writer::new(&mut out);
@@ -310,7 +469,9 @@ mod tests {
             key: Cow::from("test"),
             value: Some(AnyValue {
                 value: AnyValueOneOfvalue::string_value(Cow::from("test")),
+                ..Default::default()
             }),
+            ..Default::default()
         };
 
         let metrics_data = ExportMetricsServiceRequest {
@@ -318,6 +479,8 @@ mod tests {
                 resource: Some(Resource {
                     attributes: vec![key_value.clone()],
                     dropped_attributes_count: 10,
+                    entity_refs: vec![],
+                    ..Default::default()
                 }),
                 scope_metrics: vec![ScopeMetrics {
                     scope: Some(InstrumentationScope {
@@ -325,6 +488,7 @@ mod tests {
                         version: Cow::from("1.2.3"),
                         attributes: vec![key_value.clone()],
                         dropped_attributes_count: 10,
+                        ..Default::default()
                     }),
                     metrics: vec![Metric {
                         name: Cow::from("test_name"),
@@ -345,19 +509,27 @@ mod tests {
                                     value: ExemplarOneOfvalue::as_int(10),
                                     span_id: Cow::from("test".as_bytes()),
                                     trace_id: Cow::from("test".as_bytes()),
+                                    ..Default::default()
                                 }],
                                 flags: 1,
                                 min: 0.1_f64,
                                 max: 9.9_f64,
+                                ..Default::default()
                             }],
                             aggregation_temporality:
                                 AggregationTemporality::AGGREGATION_TEMPORALITY_CUMULATIVE,
+                            ..Default::default()
                         }),
+                        metadata: vec![],
+                        ..Default::default()
                     }],
                     schema_url: Cow::from("https://some_url.com"),
+                    ..Default::default()
                 }],
                 schema_url: Cow::from("https://some_url.com"),
+                ..Default::default()
             }],
+            ..Default::default()
         };
         {
             let mut writer = Writer::new(&mut out);
@@ -414,7 +586,9 @@ mod tests {
             key: Cow::from("test"),
             value: Some(AnyValue {
                 value: AnyValueOneOfvalue::string_value(Cow::from("test")),
+                ..Default::default()
             }),
+            ..Default::default()
         };
 
         let metrics_data = ExportMetricsServiceRequest {
@@ -422,6 +596,8 @@ mod tests {
                 resource: Some(Resource {
                     attributes: vec![key_value.clone()],
                     dropped_attributes_count: 10,
+                    entity_refs: vec![],
+                    ..Default::default()
                 }),
                 scope_metrics: vec![ScopeMetrics {
                     scope: Some(InstrumentationScope {
@@ -429,6 +605,7 @@ mod tests {
                         version: Cow::from("1.2.3"),
                         attributes: vec![key_value.clone()],
                         dropped_attributes_count: 10,
+                        ..Default::default()
                     }),
                     metrics: vec![Metric {
                         name: Cow::from("test_name"),
@@ -446,10 +623,12 @@ mod tests {
                                 positive: Some(ExponentialHistogramDataPointBuckets {
                                     offset: 1,
                                     bucket_counts: vec![18446744073709551615],
+                                    ..Default::default()
                                 }),
                                 negative: Some(ExponentialHistogramDataPointBuckets {
                                     offset: 1,
                                     bucket_counts: vec![0, 18446744073709551615],
+                                    ..Default::default()
                                 }),
                                 flags: 1,
                                 exemplars: vec![Exemplar {
@@ -458,19 +637,27 @@ mod tests {
                                     value: ExemplarOneOfvalue::as_int(10),
                                     span_id: Cow::from("test".as_bytes()),
                                     trace_id: Cow::from("test".as_bytes()),
+                                    ..Default::default()
                                 }],
                                 min: 0.1_f64,
                                 max: 9.9_f64,
                                 zero_threshold: 3.3_f64,
+                                ..Default::default()
                             }],
                             aggregation_temporality:
                                 AggregationTemporality::AGGREGATION_TEMPORALITY_CUMULATIVE,
+                            ..Default::default()
                         }),
+                        metadata: vec![],
+                        ..Default::default()
                     }],
                     schema_url: Cow::from("https://some_url.com"),
+                    ..Default::default()
                 }],
                 schema_url: Cow::from("https://some_url.com"),
+                ..Default::default()
             }],
+            ..Default::default()
         };
         {
             let mut writer = Writer::new(&mut out);
@@ -525,7 +712,9 @@ mod tests {
             key: Cow::from("test"),
             value: Some(AnyValue {
                 value: AnyValueOneOfvalue::string_value(Cow::from("test")),
+                ..Default::default()
             }),
+            ..Default::default()
         };
 
         let metrics_data = ExportMetricsServiceRequest {
@@ -533,6 +722,8 @@ mod tests {
                 resource: Some(Resource {
                     attributes: vec![key_value.clone()],
                     dropped_attributes_count: 10,
+                    entity_refs: vec![],
+                    ..Default::default()
                 }),
                 scope_metrics: vec![ScopeMetrics {
                     scope: Some(InstrumentationScope {
@@ -540,6 +731,7 @@ mod tests {
                         version: Cow::from("1.2.3"),
                         attributes: vec![key_value.clone()],
                         dropped_attributes_count: 10,
+                        ..Default::default()
                     }),
                     metrics: vec![Metric {
                         name: Cow::from("test_name"),
@@ -555,15 +747,23 @@ mod tests {
                                 quantile_values: vec![SummaryDataPointValueAtQuantile {
                                     quantile: 1.0_f64,
                                     value: 2.0_f64,
+                                    ..Default::default()
                                 }],
                                 flags: 1,
+                                ..Default::default()
                             }],
+                            ..Default::default()
                         }),
+                        metadata: vec![],
+                        ..Default::default()
                     }],
                     schema_url: Cow::from("https://some_url.com"),
+                    ..Default::default()
                 }],
                 schema_url: Cow::from("https://some_url.com"),
+                ..Default::default()
             }],
+            ..Default::default()
         };
         {
             let mut writer = Writer::new(&mut out);
@@ -607,11 +807,13 @@ mod tests {
 
         let any_value = AnyValue {
             value: AnyValueOneOfvalue::string_value(Cow::from("test")),
+            ..Default::default()
         };
 
         let message = KeyValue {
             key: Cow::from("test"),
             value: Some(any_value),
+            ..Default::default()
         };
         {
             let mut writer = Writer::new(&mut out);
@@ -648,7 +850,9 @@ mod tests {
             key: Cow::from("test"),
             value: Some(AnyValue {
                 value: AnyValueOneOfvalue::string_value(Cow::from("test")),
+                ..Default::default()
             }),
+            ..Default::default()
         };
 
         let metrics_data = ExportMetricsServiceRequest {
@@ -656,6 +860,8 @@ mod tests {
                 resource: Some(Resource {
                     attributes: vec![key_value.clone()],
                     dropped_attributes_count: 10,
+                    entity_refs: vec![],
+                    ..Default::default()
                 }),
                 scope_metrics: vec![ScopeMetrics {
                     scope: Some(InstrumentationScope {
@@ -663,6 +869,7 @@ mod tests {
                         version: Cow::from("1.2.3"),
                         attributes: vec![key_value.clone()],
                         dropped_attributes_count: 10,
+                        ..Default::default()
                     }),
                     metrics: vec![Metric {
                         name: Cow::from("test_name"),
@@ -678,15 +885,23 @@ mod tests {
                                 quantile_values: vec![SummaryDataPointValueAtQuantile {
                                     quantile: 1.0_f64,
                                     value: 2.0_f64,
+                                    ..Default::default()
                                 }],
                                 flags: 1,
+                                ..Default::default()
                             }],
+                            ..Default::default()
                         }),
+                        metadata: vec![],
+                        ..Default::default()
                     }],
                     schema_url: Cow::from("https://"),
+                    ..Default::default()
                 }],
                 schema_url: Cow::from("https://"),
+                ..Default::default()
             }],
+            ..Default::default()
         };
         {
             let mut writer = Writer::new(&mut out);
@@ -751,7 +966,9 @@ mod tests {
             key: Cow::from("test"),
             value: Some(AnyValue {
                 value: AnyValueOneOfvalue::string_value(Cow::from("test")),
+                ..Default::default()
             }),
+            ..Default::default()
         };
 
         let logs_data = ExportLogsServiceRequest {
@@ -759,6 +976,8 @@ mod tests {
                 resource: Some(Resource {
                     attributes: vec![key_value.clone()],
                     dropped_attributes_count: 10,
+                    entity_refs: vec![],
+                    ..Default::default()
                 }),
                 scope_logs: vec![ScopeLogs {
                     scope: Some(InstrumentationScope {
@@ -766,6 +985,7 @@ mod tests {
                         version: Cow::from("1.2.3"),
                         attributes: vec![key_value.clone()],
                         dropped_attributes_count: 10,
+                        ..Default::default()
                     }),
                     log_records: vec![LogRecord {
                         time_unix_nano: 1681339577345243523,
@@ -774,17 +994,23 @@ mod tests {
                         severity_text: Cow::from("test_text"),
                         body: Some(AnyValue {
                             value: AnyValueOneOfvalue::string_value(Cow::from("test")),
+                            ..Default::default()
                         }),
                         attributes: vec![key_value.clone()],
                         dropped_attributes_count: 10,
                         flags: 1,
                         span_id: Cow::from("test".as_bytes()),
                         trace_id: Cow::from("test".as_bytes()),
+                        event_name: Cow::from(""),
+                        ..Default::default()
                     }],
                     schema_url: Cow::from("https://some_url.com"),
+                    ..Default::default()
                 }],
                 schema_url: Cow::from("https://some_url.com"),
+                ..Default::default()
             }],
+            ..Default::default()
         };
         {
             let mut writer = Writer::new(&mut out);
@@ -829,7 +1055,9 @@ mod tests {
             key: Cow::from("test"),
             value: Some(AnyValue {
                 value: AnyValueOneOfvalue::string_value(Cow::from("test")),
+                ..Default::default()
             }),
+            ..Default::default()
         };
 
         let logs_data = ExportLogsServiceRequest {
@@ -837,6 +1065,8 @@ mod tests {
                 resource: Some(Resource {
                     attributes: vec![key_value.clone()],
                     dropped_attributes_count: 10,
+                    entity_refs: vec![],
+                    ..Default::default()
                 }),
                 scope_logs: vec![ScopeLogs {
                     scope: Some(InstrumentationScope {
@@ -844,6 +1074,7 @@ mod tests {
                         version: Cow::from("1.2.3"),
                         attributes: vec![key_value.clone()],
                         dropped_attributes_count: 10,
+                        ..Default::default()
                     }),
                     log_records: vec![LogRecord {
                         time_unix_nano: 1681339577345243523,
@@ -852,17 +1083,23 @@ mod tests {
                         severity_text: Cow::from("test_text"),
                         body: Some(AnyValue {
                             value: AnyValueOneOfvalue::string_value(Cow::from("test")),
+                            ..Default::default()
                         }),
                         attributes: vec![key_value.clone()],
                         dropped_attributes_count: 10,
                         flags: 1,
                         span_id: Cow::from("test".as_bytes()),
                         trace_id: Cow::from("test".as_bytes()),
+                        event_name: Cow::from(""),
+                        ..Default::default()
                     }],
                     schema_url: Cow::from("https://"),
+                    ..Default::default()
                 }],
                 schema_url: Cow::from("https://"),
+                ..Default::default()
             }],
+            ..Default::default()
         };
         {
             let mut writer = Writer::new(&mut out);
@@ -912,8 +1149,9 @@ mod tests {
     #[test]
     fn roundtrip_trace_valid_data() {
         use crate::opentelemetry::trace::{
-            AnyValue, AnyValueOneOfvalue, InstrumentationScope, KeyValue, Resource, ResourceSpans,
-            ScopeSpans, Span, SpanEvent, SpanKind, SpanLink, Status, StatusCode, ExportTraceServiceRequest,
+            AnyValue, AnyValueOneOfvalue, ExportTraceServiceRequest, InstrumentationScope,
+            KeyValue, Resource, ResourceSpans, ScopeSpans, Span, SpanEvent, SpanKind, SpanLink,
+            Status, StatusCode,
         };
 
         let mut out = vec![];
@@ -922,7 +1160,9 @@ mod tests {
             key: Cow::from("test"),
             value: Some(AnyValue {
                 value: AnyValueOneOfvalue::string_value(Cow::from("test")),
+                ..Default::default()
             }),
+            ..Default::default()
         };
 
         let logs_data = ExportTraceServiceRequest {
@@ -930,6 +1170,8 @@ mod tests {
                 resource: Some(Resource {
                     attributes: vec![key_value.clone()],
                     dropped_attributes_count: 10,
+                    entity_refs: vec![],
+                    ..Default::default()
                 }),
                 scope_spans: vec![ScopeSpans {
                     scope: Some(InstrumentationScope {
@@ -937,6 +1179,7 @@ mod tests {
                         version: Cow::from("1.2.3"),
                         attributes: vec![key_value.clone()],
                         dropped_attributes_count: 10,
+                        ..Default::default()
                     }),
                     spans: vec![Span {
                         trace_id: Cow::from("trace_id".as_bytes()),
@@ -954,6 +1197,7 @@ mod tests {
                             name: Cow::from("test_name"),
                             attributes: vec![key_value.clone()],
                             dropped_attributes_count: 10,
+                            ..Default::default()
                         }],
                         dropped_events_count: 10,
                         dropped_links_count: 10,
@@ -963,16 +1207,24 @@ mod tests {
                             trace_state: Cow::from("link_test_state"),
                             attributes: vec![key_value.clone()],
                             dropped_attributes_count: 10,
+                            flags: 0,
+                            ..Default::default()
                         }],
                         status: Some(Status {
                             message: Cow::from("test_message"),
                             code: StatusCode::STATUS_CODE_OK,
+                            ..Default::default()
                         }),
+                        flags: 0,
+                        ..Default::default()
                     }],
                     schema_url: Cow::from("https://some_url.com"),
+                    ..Default::default()
                 }],
                 schema_url: Cow::from("https://some_url.com"),
+                ..Default::default()
             }],
+            ..Default::default()
         };
         {
             let mut writer = Writer::new(&mut out);
@@ -1023,4 +1275,329 @@ mod tests {
             .validate()
             .expect("Validation of trace protobuf failed");
     }
+
+    #[test]
+    fn owned_trace_request_outlives_buffer() {
+        use crate::opentelemetry::trace::ExportTraceServiceRequest;
+        use crate::IntoOwned;
+
+        let out: Vec<u8> = b"\n=\n\x14\n\x12\n\x0cservice.name\x12\x02\n\0\x12%\n\x06\n\x04test\x12\x1b\n\x10\x01\x02\x03\x04\x05\x06\x07\x08\x09\x0a\x0b\x0c\x0d\x0e\x0f\x10\x12\x01\x01*\x04span".to_vec();
+
+        let expected = ExportTraceServiceRequest::try_from(&out[..])
+            .expect("Parsing of trace protobuf failed");
+
+        let owned: ExportTraceServiceRequest<'static> = {
+            let buffer = out.clone();
+            ExportTraceServiceRequest::try_from(&buffer[..])
+                .expect("Parsing of trace protobuf failed")
+                .into_owned()
+        };
+
+        let handle = std::thread::spawn(move || owned);
+        let owned = handle.join().expect("thread failed");
+
+        assert_eq!(owned, expected);
+        assert_eq!(owned.resource_spans[0].scope_spans[0].spans[0].name, "span");
+        owned.validate().expect("validation failed");
+    }
+
+    #[test]
+    fn bytes_metrics_real_otlp_request_body() {
+        use crate::opentelemetry::metrics::ExportMetricsServiceRequest;
+        use crate::BytesRequest;
+        use bytes::Bytes;
+
+        let out = Bytes::from_static(b"\n\xa7\x02\n\xb8\x01\n)\n\x11service.namespace\x12\x14\n\x12opentelemetry-demo\n!\n\x0cservice.name\x12\x11\n\x0fcurrencyservice\n \n\x15telemetry.sdk.version\x12\x07\n\x051.8.2\n%\n\x12telemetry.sdk.name\x12\x0f\n\ropentelemetry\n\x1f\n\x16telemetry.sdk.language\x12\x05\n\x03cpp\x12j\n\x15\n\x0capp_currency\x12\x051.3.0\x12Q\n\x14app_currency_counter:9\n3\x11\xdc\xf9\0xl\x18W\x17\x19\xb7\xa2\xa1\xb3l\x18W\x171\x02\0\0\0\0\0\0\0:\x16\n\rcurrency_code\x12\x05\n\x03USD\x10\x01\x18\x01");
+
+        let request = BytesRequest::<ExportMetricsServiceRequest>::try_from(out.clone())
+            .expect("Parsing of metrics protobuf failed");
+        drop(out);
+        let request = request.into_owned();
+
+        request
+            .validate()
+            .expect("Validation of metrics protobuf failed");
+        assert_eq!(
+            request.resource_metrics[0].scope_metrics[0].metrics[0].name,
+            "app_currency_counter"
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_roundtrip_trace_request() {
+        use crate::opentelemetry::common::{AnyValue, AnyValueOneOfvalue, KeyValue};
+        use crate::opentelemetry::metrics::{Metric, MetricOneOfdata, Sum};
+        use crate::opentelemetry::trace::{
+            ExportTraceServiceRequest, ResourceSpans, ScopeSpans, Span, SpanKind,
+        };
+
+        let request = ExportTraceServiceRequest {
+            resource_spans: vec![ResourceSpans {
+                scope_spans: vec![ScopeSpans {
+                    spans: vec![Span {
+                        trace_id: Cow::from(&[1; 16][..]),
+                        span_id: Cow::from(&[2; 8][..]),
+                        name: Cow::from("span"),
+                        kind: SpanKind::SPAN_KIND_CLIENT,
+                        attributes: vec![KeyValue {
+                            key: Cow::from("key"),
+                            value: Some(AnyValue {
+                                value: AnyValueOneOfvalue::string_value(Cow::from("value")),
+                                ..Default::default()
+                            }),
+                            ..Default::default()
+                        }],
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        // Deserialized values own their data, so they outlive the input
+        let decoded: ExportTraceServiceRequest<'static> = {
+            let json = serde_json::to_string(&request).expect("serialization failed");
+            serde_json::from_str(&json).expect("deserialization failed")
+        };
+        assert_eq!(decoded, request);
+
+        let metric = Metric {
+            name: Cow::from("counter"),
+            data: MetricOneOfdata::sum(Sum {
+                is_monotonic: true,
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let value = serde_json::to_value(&metric).expect("serialization failed");
+        assert_eq!(value["data"]["sum"]["is_monotonic"], true);
+        assert_eq!(serde_json::from_value::<Metric>(value).unwrap(), metric);
+    }
+
+    #[test]
+    fn roundtrip_profiles_valid_data() {
+        use crate::opentelemetry::profiles::{
+            ExportProfilesServiceRequest, Function, Line, Link, Location, Profile,
+            ResourceProfiles, Sample, ScopeProfiles, ValueType,
+        };
+
+        let profile = Profile {
+            sample_type: vec![ValueType {
+                type_strindex: 1,
+                unit_strindex: 2,
+                ..Default::default()
+            }],
+            sample: vec![Sample {
+                locations_start_index: 0,
+                locations_length: 2,
+                value: vec![10],
+                ..Default::default()
+            }],
+            location_table: vec![Location {
+                address: 0x1000,
+                line: vec![Line {
+                    function_index: 0,
+                    line: 42,
+                    column: 0,
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }],
+            location_indices: vec![0, 0],
+            function_table: vec![Function {
+                name_strindex: 3,
+                filename_strindex: 4,
+                ..Default::default()
+            }],
+            string_table: ["", "cpu", "nanoseconds", "main", "main.rs"]
+                .into_iter()
+                .map(Cow::from)
+                .collect(),
+            profile_id: Cow::from(&[1; 16][..]),
+            ..Default::default()
+        };
+        let request = ExportProfilesServiceRequest {
+            resource_profiles: vec![ResourceProfiles {
+                scope_profiles: vec![ScopeProfiles {
+                    profiles: vec![profile],
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        let mut out = vec![];
+        {
+            let mut writer = Writer::new(&mut out);
+            quick_protobuf::MessageWrite::write_message(&request, &mut writer)
+                .expect("failed to write");
+        }
+
+        let decoded = ExportProfilesServiceRequest::try_from(&out[..])
+            .expect("Parsing of profiles protobuf failed");
+        assert_eq!(decoded, request);
+        decoded.validate().expect("validation failed");
+
+        let mut invalid = request.clone();
+        let profile = &mut invalid.resource_profiles[0].scope_profiles[0].profiles[0];
+        profile.location_table[0].line[0].function_index = 1;
+        match invalid.validate() {
+            Err(e) => assert_eq!(
+                e.to_string(),
+                "function_index 1 is out of bounds for 1 entries"
+            ),
+            Ok(_) => panic!("Validation should fail"),
+        }
+
+        let mut invalid = request.clone();
+        let profile = &mut invalid.resource_profiles[0].scope_profiles[0].profiles[0];
+        profile.sample[0].locations_start_index = 1;
+        assert!(matches!(
+            invalid.validate(),
+            Err(crate::Error::IndexOutOfBounds {
+                field: "locations_length",
+                index: 2,
+                len: 2,
+            })
+        ));
+
+        let mut invalid = request.clone();
+        let profile = &mut invalid.resource_profiles[0].scope_profiles[0].profiles[0];
+        profile.string_table[0] = Cow::from("cpu");
+        assert!(matches!(
+            invalid.validate(),
+            Err(crate::Error::InvalidStringTable)
+        ));
+
+        // A set optional index must point at an entry, even when it is 0
+        let mut linked = request;
+        let profile = &mut linked.resource_profiles[0].scope_profiles[0].profiles[0];
+        profile.sample[0].link_index = Some(0);
+        assert!(matches!(
+            linked.validate(),
+            Err(crate::Error::IndexOutOfBounds {
+                field: "link_index",
+                index: 0,
+                len: 0,
+            })
+        ));
+        let profile = &mut linked.resource_profiles[0].scope_profiles[0].profiles[0];
+        profile.link_table = vec![Link::default()];
+        linked.validate().expect("validation failed");
+
+        let bytes = bytes::Bytes::from(crate::encode::encode_to_vec(&linked));
+        let decoded = crate::BytesRequest::<ExportProfilesServiceRequest>::try_from(bytes)
+            .expect("Parsing failed");
+        let decoded = decoded.get();
+        assert_eq!(decoded, &linked);
+        let sample = &decoded.resource_profiles[0].scope_profiles[0].profiles[0].sample[0];
+        assert_eq!(sample.link_index, Some(0));
+    }
+
+    #[test]
+    fn roundtrip_otlp_1_x_fields() {
+        use crate::opentelemetry::common::{EntityRef, KeyValue, Resource};
+        use crate::opentelemetry::logs::LogRecord;
+        use crate::opentelemetry::metrics::Metric;
+        use crate::opentelemetry::trace::{ExportTraceServiceRequest, ResourceSpans, ScopeSpans};
+        use crate::opentelemetry::trace::{Span, SpanFlags, SpanLink};
+
+        // Sampled, with a remote parent
+        let flags = 0x01
+            | SpanFlags::SPAN_FLAGS_CONTEXT_HAS_IS_REMOTE_MASK as u32
+            | SpanFlags::SPAN_FLAGS_CONTEXT_IS_REMOTE_MASK as u32;
+        let request = ExportTraceServiceRequest {
+            resource_spans: vec![ResourceSpans {
+                resource: Some(Resource {
+                    attributes: vec![KeyValue {
+                        key: Cow::from("service.name"),
+                        value: None,
+                        ..Default::default()
+                    }],
+                    entity_refs: vec![EntityRef {
+                        type_pb: Cow::from("service"),
+                        id_keys: vec![Cow::from("service.name")],
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                }),
+                scope_spans: vec![ScopeSpans {
+                    spans: vec![Span {
+                        flags,
+                        links: vec![SpanLink {
+                            flags: SpanFlags::SPAN_FLAGS_CONTEXT_HAS_IS_REMOTE_MASK as u32,
+                            ..Default::default()
+                        }],
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        let out = request.encode_to_vec();
+        let decoded = ExportTraceServiceRequest::try_from(&out[..])
+            .expect("Parsing of trace protobuf failed");
+        assert_eq!(decoded, request);
+        assert_eq!(
+            decoded.resource_spans[0].scope_spans[0].spans[0].flags,
+            flags
+        );
+        decoded.validate().expect("validation failed");
+
+        // IS_REMOTE means nothing without HAS_IS_REMOTE, but is not invalid
+        let mut unknown_remote = request.clone();
+        unknown_remote.resource_spans[0].scope_spans[0].spans[0].flags =
+            SpanFlags::SPAN_FLAGS_CONTEXT_IS_REMOTE_MASK as u32;
+        unknown_remote.validate().expect("validation failed");
+
+        let mut invalid = request;
+        let resource = invalid.resource_spans[0].resource.as_mut().unwrap();
+        resource.entity_refs[0]
+            .description_keys
+            .push(Cow::from("host.name"));
+        match invalid.validate() {
+            Err(e) => assert_eq!(
+                e.to_string(),
+                "invalid entity reference: key `host.name` is not a resource attribute"
+            ),
+            Ok(_) => panic!("Validation should fail"),
+        }
+
+        let log_record = LogRecord {
+            event_name: Cow::from("browser.page_view"),
+            ..Default::default()
+        };
+        let mut out = vec![];
+        quick_protobuf::MessageWrite::write_message(&log_record, &mut Writer::new(&mut out))
+            .expect("failed to write");
+        let decoded: LogRecord =
+            quick_protobuf::MessageRead::from_reader(&mut BytesReader::from_bytes(&out), &out)
+                .expect("Cannot read message");
+        assert_eq!(decoded.event_name, "browser.page_view");
+
+        let metric = Metric {
+            name: Cow::from("requests"),
+            metadata: vec![KeyValue {
+                key: Cow::from("prometheus.type"),
+                value: None,
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        let mut out = vec![];
+        quick_protobuf::MessageWrite::write_message(&metric, &mut Writer::new(&mut out))
+            .expect("failed to write");
+        let decoded: Metric =
+            quick_protobuf::MessageRead::from_reader(&mut BytesReader::from_bytes(&out), &out)
+                .expect("Cannot read message");
+        assert_eq!(decoded, metric);
+        decoded.validate().expect("validation failed");
+    }
 }
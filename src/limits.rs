@@ -0,0 +1,496 @@
+use quick_protobuf::{BytesReader, MessageRead};
+
+use crate::descriptor::{self, FieldKind, MessageDescriptor, MAX_FIELDS};
+use crate::opentelemetry::logs::ExportLogsServiceRequest;
+use crate::opentelemetry::metrics::ExportMetricsServiceRequest;
+use crate::opentelemetry::profiles::ExportProfilesServiceRequest;
+use crate::opentelemetry::trace::ExportTraceServiceRequest;
+
+const WIRE_TYPE_LENGTH_DELIMITED: u32 = 2;
+
+/// Limits applied while decoding untrusted input.
+///
+/// The encoded input is checked against every limit before anything is
+/// decoded, so exceeding one fails fast with a dedicated [`crate::Error`]
+/// variant instead of allocating. OTLP/JSON input is only checked against
+/// `max_total_bytes` up front, and against the other limits once parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecodeOptions {
+    /// Maximum size of the encoded message, in bytes.
+    pub max_total_bytes: usize,
+    /// Maximum number of elements of any single repeated field.
+    pub max_repeated_elements: usize,
+    /// Maximum number of messages and repeated field elements in the whole
+    /// message.
+    pub max_total_elements: usize,
+    /// Maximum number of `AnyValue`s nested within each other.
+    pub max_any_value_depth: usize,
+    /// Maximum length of any string or bytes field, in bytes.
+    pub max_string_len: usize,
+}
+
+impl Default for DecodeOptions {
+    fn default() -> Self {
+        DecodeOptions {
+            max_total_bytes: 64 * 1024 * 1024,
+            max_repeated_elements: 1024 * 1024,
+            max_total_elements: 8 * 1024 * 1024,
+            max_any_value_depth: 32,
+            max_string_len: 16 * 1024 * 1024,
+        }
+    }
+}
+
+impl DecodeOptions {
+    fn decode<'a, M: MessageRead<'a>>(
+        &self,
+        bytes: &'a [u8],
+        descriptor: &'static MessageDescriptor,
+    ) -> Result<M, crate::Error> {
+        self.check_encoded(bytes, descriptor)?;
+
+        let mut reader = BytesReader::from_bytes(bytes);
+        Ok(M::from_reader(&mut reader, bytes)?)
+    }
+
+    // Checks an encoded message against all the limits without decoding it
+    pub(crate) fn check_encoded(
+        &self,
+        bytes: &[u8],
+        descriptor: &'static MessageDescriptor,
+    ) -> Result<(), crate::Error> {
+        self.check_size(bytes)?;
+        self.check(bytes, descriptor, 0, &mut 0)
+    }
+
+    pub(crate) fn check_size(&self, bytes: &[u8]) -> Result<(), crate::Error> {
+        if bytes.len() > self.max_total_bytes {
+            return Err(crate::Error::MessageTooLarge {
+                size: bytes.len(),
+                limit: self.max_total_bytes,
+            });
+        }
+        Ok(())
+    }
+
+    // Checks a message decoded some other way, such as from JSON, against
+    // the limits other than the size, by looking at its protobuf encoding
+    #[cfg(feature = "json")]
+    pub(crate) fn check_message<M: quick_protobuf::MessageWrite>(
+        &self,
+        message: &M,
+        descriptor: &'static MessageDescriptor,
+    ) -> Result<(), crate::Error> {
+        self.check(
+            &crate::encode::encode_to_vec(message),
+            descriptor,
+            0,
+            &mut 0,
+        )
+    }
+
+    fn check(
+        &self,
+        bytes: &[u8],
+        descriptor: &'static MessageDescriptor,
+        depth: usize,
+        total: &mut usize,
+    ) -> Result<(), crate::Error> {
+        let mut counts = [0usize; MAX_FIELDS];
+        let mut reader = BytesReader::from_bytes(bytes);
+
+        while !reader.is_eof() {
+            let tag = reader.next_tag(bytes)?;
+            let field = descriptor.field(tag >> 3);
+
+            if tag & 7 != WIRE_TYPE_LENGTH_DELIMITED {
+                reader.read_unknown(bytes, tag)?;
+                // Repeated scalars may also be sent one element per field
+                if let Some((index, field)) = field {
+                    if matches!(
+                        field.kind,
+                        FieldKind::PackedFixed(_) | FieldKind::PackedVarint
+                    ) {
+                        self.count(&mut counts[index], total, 1)?;
+                    }
+                }
+                continue;
+            }
+
+            let value = reader.read_bytes(bytes)?;
+            match field {
+                Some((index, field)) => match field.kind {
+                    FieldKind::Message(nested) => {
+                        self.count(&mut counts[index], total, 1)?;
+                        let depth = if std::ptr::eq(nested, &descriptor::ANY_VALUE) {
+                            depth + 1
+                        } else {
+                            depth
+                        };
+                        if depth > self.max_any_value_depth {
+                            return Err(crate::Error::NestingTooDeep {
+                                limit: self.max_any_value_depth,
+                            });
+                        }
+                        self.check(value, nested, depth, total)?;
+                    }
+                    FieldKind::PackedFixed(size) => {
+                        self.count(&mut counts[index], total, value.len() / size)?;
+                    }
+                    FieldKind::PackedVarint => {
+                        let elements = value.iter().filter(|byte| *byte & 0x80 == 0).count();
+                        self.count(&mut counts[index], total, elements)?;
+                    }
+                    FieldKind::RepeatedBytes => {
+                        self.count(&mut counts[index], total, 1)?;
+                        self.check_len(value)?;
+                    }
+                    FieldKind::Scalar => self.check_len(value)?,
+                },
+                None => self.check_len(value)?,
+            }
+        }
+
+        Ok(())
+    }
+
+    fn check_len(&self, value: &[u8]) -> Result<(), crate::Error> {
+        if value.len() > self.max_string_len {
+            return Err(crate::Error::FieldTooLarge {
+                size: value.len(),
+                limit: self.max_string_len,
+            });
+        }
+        Ok(())
+    }
+
+    fn count(
+        &self,
+        count: &mut usize,
+        total: &mut usize,
+        elements: usize,
+    ) -> Result<(), crate::Error> {
+        *count += elements;
+        if *count > self.max_repeated_elements {
+            return Err(crate::Error::TooManyElements {
+                limit: self.max_repeated_elements,
+            });
+        }
+        *total += elements;
+        if *total > self.max_total_elements {
+            return Err(crate::Error::TooManyTotalElements {
+                limit: self.max_total_elements,
+            });
+        }
+        Ok(())
+    }
+}
+
+impl<'a> ExportMetricsServiceRequest<'a> {
+    /// Decodes an encoded request, enforcing the given limits.
+    pub fn decode_with(bytes: &'a [u8], options: &DecodeOptions) -> Result<Self, crate::Error> {
+        options.decode(bytes, &descriptor::EXPORT_METRICS_SERVICE_REQUEST)
+    }
+}
+
+impl<'a> ExportLogsServiceRequest<'a> {
+    /// Decodes an encoded request, enforcing the given limits.
+    pub fn decode_with(bytes: &'a [u8], options: &DecodeOptions) -> Result<Self, crate::Error> {
+        options.decode(bytes, &descriptor::EXPORT_LOGS_SERVICE_REQUEST)
+    }
+}
+
+impl<'a> ExportTraceServiceRequest<'a> {
+    /// Decodes an encoded request, enforcing the given limits.
+    pub fn decode_with(bytes: &'a [u8], options: &DecodeOptions) -> Result<Self, crate::Error> {
+        options.decode(bytes, &descriptor::EXPORT_TRACE_SERVICE_REQUEST)
+    }
+}
+
+impl<'a> ExportProfilesServiceRequest<'a> {
+    /// Decodes an encoded request, enforcing the given limits.
+    pub fn decode_with(bytes: &'a [u8], options: &DecodeOptions) -> Result<Self, crate::Error> {
+        options.decode(bytes, &descriptor::EXPORT_PROFILES_SERVICE_REQUEST)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DecodeOptions;
+    use crate::opentelemetry::common::{
+        AnyValue, AnyValueOneOfvalue, ArrayValue, EntityRef, KeyValue, Resource,
+    };
+    use crate::opentelemetry::logs::{
+        ExportLogsServiceRequest, LogRecord, ResourceLogs, ScopeLogs,
+    };
+    use crate::opentelemetry::metrics::{
+        ExportMetricsServiceRequest, Histogram, HistogramDataPoint, Metric, MetricOneOfdata,
+        ResourceMetrics, ScopeMetrics,
+    };
+    use crate::opentelemetry::profiles::{
+        ExportProfilesServiceRequest, Profile, ResourceProfiles, ScopeProfiles,
+    };
+    use quick_protobuf::{MessageWrite, Writer};
+    use std::borrow::Cow;
+
+    fn encode<M: MessageWrite>(message: &M) -> Vec<u8> {
+        let mut out = vec![];
+        message
+            .write_message(&mut Writer::new(&mut out))
+            .expect("failed to write");
+        out
+    }
+
+    fn logs_request(
+        body: AnyValue<'static>,
+        attributes: usize,
+    ) -> ExportLogsServiceRequest<'static> {
+        ExportLogsServiceRequest {
+            resource_logs: vec![ResourceLogs {
+                resource: Some(Resource {
+                    attributes: vec![
+                        KeyValue {
+                            key: Cow::from("key"),
+                            value: None,
+                            ..Default::default()
+                        };
+                        attributes
+                    ],
+                    dropped_attributes_count: 0,
+                    entity_refs: vec![],
+                    ..Default::default()
+                }),
+                scope_logs: vec![ScopeLogs {
+                    log_records: vec![LogRecord {
+                        body: Some(body),
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                }],
+                schema_url: Cow::from(""),
+                ..Default::default()
+            }],
+            ..Default::default()
+        }
+    }
+
+    fn nested(depth: usize) -> AnyValue<'static> {
+        (0..depth).fold(
+            AnyValue {
+                value: AnyValueOneOfvalue::int_value(1),
+                ..Default::default()
+            },
+            |value, _| AnyValue {
+                value: AnyValueOneOfvalue::array_value(ArrayValue {
+                    values: vec![value],
+                    ..Default::default()
+                }),
+                ..Default::default()
+            },
+        )
+    }
+
+    #[test]
+    fn decode_with_accepts_requests_within_limits() {
+        let request = logs_request(nested(3), 4);
+        let out = encode(&request);
+
+        let decoded = ExportLogsServiceRequest::decode_with(&out, &DecodeOptions::default())
+            .expect("Parsing of logs protobuf failed");
+        assert_eq!(decoded, request);
+    }
+
+    #[test]
+    fn decode_with_rejects_oversized_requests() {
+        let out = encode(&logs_request(nested(0), 4));
+        let options = DecodeOptions {
+            max_total_bytes: out.len() - 1,
+            ..Default::default()
+        };
+
+        match ExportLogsServiceRequest::decode_with(&out, &options) {
+            Err(crate::Error::MessageTooLarge { size, limit }) => {
+                assert_eq!(size, out.len());
+                assert_eq!(limit, out.len() - 1);
+            }
+            other => panic!("unexpected result {:?}", other),
+        }
+    }
+
+    #[test]
+    fn decode_with_rejects_deeply_nested_values() {
+        let options = DecodeOptions {
+            max_any_value_depth: 4,
+            ..Default::default()
+        };
+
+        // The log body itself is the first level
+        let out = encode(&logs_request(nested(3), 0));
+        ExportLogsServiceRequest::decode_with(&out, &options).expect("within limit");
+
+        let out = encode(&logs_request(nested(4), 0));
+        match ExportLogsServiceRequest::decode_with(&out, &options) {
+            Err(e) => assert_eq!(e.to_string(), "AnyValue nesting exceeds the limit of 4"),
+            Ok(_) => panic!("Decoding should fail"),
+        }
+    }
+
+    #[test]
+    fn decode_with_rejects_too_many_elements() {
+        let options = DecodeOptions {
+            max_repeated_elements: 8,
+            ..Default::default()
+        };
+
+        let out = encode(&logs_request(nested(0), 8));
+        ExportLogsServiceRequest::decode_with(&out, &options).expect("within limit");
+
+        let out = encode(&logs_request(nested(0), 9));
+        assert!(matches!(
+            ExportLogsServiceRequest::decode_with(&out, &options),
+            Err(crate::Error::TooManyElements { limit: 8 })
+        ));
+    }
+
+    #[test]
+    fn decode_with_counts_packed_elements() {
+        let request = ExportMetricsServiceRequest {
+            resource_metrics: vec![ResourceMetrics {
+                scope_metrics: vec![ScopeMetrics {
+                    metrics: vec![Metric {
+                        name: Cow::from("test_name"),
+                        data: MetricOneOfdata::histogram(Histogram {
+                            data_points: vec![HistogramDataPoint {
+                                bucket_counts: Cow::from(vec![1; 10]),
+                                explicit_bounds: Cow::from(vec![1.0; 9]),
+                                ..Default::default()
+                            }],
+                            ..Default::default()
+                        }),
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        let out = encode(&request);
+
+        let options = DecodeOptions {
+            max_repeated_elements: 9,
+            ..Default::default()
+        };
+        assert!(matches!(
+            ExportMetricsServiceRequest::decode_with(&out, &options),
+            Err(crate::Error::TooManyElements { limit: 9 })
+        ));
+    }
+
+    #[test]
+    fn decode_with_rejects_long_strings() {
+        let body = AnyValue {
+            value: AnyValueOneOfvalue::string_value(Cow::from("x".repeat(65))),
+            ..Default::default()
+        };
+        let out = encode(&logs_request(body, 0));
+        let options = DecodeOptions {
+            max_string_len: 64,
+            ..Default::default()
+        };
+
+        assert!(matches!(
+            ExportLogsServiceRequest::decode_with(&out, &options),
+            Err(crate::Error::FieldTooLarge {
+                size: 65,
+                limit: 64
+            })
+        ));
+    }
+
+    #[test]
+    fn decode_with_limits_elements_in_total() {
+        let options = DecodeOptions {
+            max_total_elements: 16,
+            ..Default::default()
+        };
+
+        // The resource logs, resource, scope logs, log record and its body
+        // make five more
+        let out = encode(&logs_request(nested(0), 11));
+        ExportLogsServiceRequest::decode_with(&out, &options).expect("within limit");
+
+        let out = encode(&logs_request(nested(0), 12));
+        assert!(matches!(
+            ExportLogsServiceRequest::decode_with(&out, &options),
+            Err(crate::Error::TooManyTotalElements { limit: 16 })
+        ));
+    }
+
+    #[test]
+    fn decode_with_counts_repeated_strings() {
+        let mut request = logs_request(nested(0), 0);
+        request.resource_logs[0].resource = Some(Resource {
+            entity_refs: vec![EntityRef {
+                id_keys: vec![Cow::from("id"); 8],
+                description_keys: vec![Cow::from("x".repeat(65))],
+                ..Default::default()
+            }],
+            ..Default::default()
+        });
+        let out = encode(&request);
+
+        let options = DecodeOptions {
+            max_repeated_elements: 7,
+            ..Default::default()
+        };
+        assert!(matches!(
+            ExportLogsServiceRequest::decode_with(&out, &options),
+            Err(crate::Error::TooManyElements { limit: 7 })
+        ));
+
+        let options = DecodeOptions {
+            max_string_len: 64,
+            ..Default::default()
+        };
+        assert!(matches!(
+            ExportLogsServiceRequest::decode_with(&out, &options),
+            Err(crate::Error::FieldTooLarge {
+                size: 65,
+                limit: 64
+            })
+        ));
+    }
+
+    #[test]
+    fn decode_with_limits_profiles() {
+        let request = ExportProfilesServiceRequest {
+            resource_profiles: vec![ResourceProfiles {
+                scope_profiles: vec![ScopeProfiles {
+                    profiles: vec![Profile {
+                        string_table: vec![Cow::from(""); 4],
+                        location_indices: vec![0; 10],
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        let out = encode(&request);
+
+        let decoded = ExportProfilesServiceRequest::decode_with(&out, &DecodeOptions::default())
+            .expect("within limits");
+        assert_eq!(decoded, request);
+
+        let options = DecodeOptions {
+            max_repeated_elements: 9,
+            ..Default::default()
+        };
+        assert!(matches!(
+            ExportProfilesServiceRequest::decode_with(&out, &options),
+            Err(crate::Error::TooManyElements { limit: 9 })
+        ));
+    }
+}
@@ -1 +1,65 @@
+// The bindings under opentelemetry/ are generated, see
+// scripts/regenerate-bindings.sh
+#[cfg(not(feature = "regenerate"))]
+pub mod opentelemetry;
+#[cfg(feature = "regenerate")]
 include!(concat!(env!("OUT_DIR"), "/proto/mod.rs"));
+
+use std::borrow::Cow;
+
+/// Reads a packed repeated fixed-size field (`fixed64`, `double`, ...).
+///
+/// Replaces `BytesReader::read_packed_fixed` in the generated code: that one
+/// reinterprets the input buffer in place, which is undefined behaviour
+/// whenever the field does not happen to start on an aligned address. The
+/// buffer is still borrowed when it is aligned and copied otherwise. A
+/// trailing partial element is an error.
+pub(crate) fn read_packed_fixed<'a, M: Copy>(
+    r: &mut quick_protobuf::BytesReader,
+    bytes: &'a [u8],
+) -> quick_protobuf::Result<Cow<'a, [M]>> {
+    let data = r.read_bytes(bytes)?;
+    let size = std::mem::size_of::<M>();
+    if data.len() % size != 0 {
+        return Err(quick_protobuf::Error::Message(format!(
+            "packed field of {} bytes is not a multiple of {size}",
+            data.len()
+        )));
+    }
+    let n = data.len() / size;
+
+    if data.as_ptr().align_offset(std::mem::align_of::<M>()) == 0 {
+        // SAFETY: the pointer is aligned for `M` and `n * size` bytes are in bounds
+        Ok(Cow::Borrowed(unsafe {
+            std::slice::from_raw_parts(data.as_ptr() as *const M, n)
+        }))
+    } else {
+        Ok(Cow::Owned(
+            (0..n)
+                // SAFETY: every read stays within `data`, unaligned reads are allowed
+                .map(|i| unsafe {
+                    std::ptr::read_unaligned(data.as_ptr().add(i * size) as *const M)
+                })
+                .collect(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use quick_protobuf::BytesReader;
+
+    #[test]
+    fn read_packed_fixed_rejects_partial_elements() {
+        let bytes = [12, 1, 0, 0, 0, 0, 0, 0, 0, 2, 0, 0, 0];
+        let mut r = BytesReader::from_bytes(&bytes);
+        assert!(super::read_packed_fixed::<u64>(&mut r, &bytes).is_err());
+
+        let bytes = [8, 1, 0, 0, 0, 0, 0, 0, 0];
+        let mut r = BytesReader::from_bytes(&bytes);
+        assert_eq!(
+            *super::read_packed_fixed::<u64>(&mut r, &bytes).unwrap(),
+            [1u64]
+        );
+    }
+}
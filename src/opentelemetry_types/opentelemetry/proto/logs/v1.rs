@@ -0,0 +1,470 @@
+// Automatically generated rust module for 'logs.proto' file
+
+#![allow(non_snake_case)]
+#![allow(non_upper_case_globals)]
+#![allow(non_camel_case_types)]
+#![allow(unused_imports)]
+#![allow(unknown_lints)]
+#![allow(clippy::all)]
+#![cfg_attr(rustfmt, rustfmt_skip)]
+
+
+use std::borrow::Cow;
+use quick_protobuf::{MessageInfo, MessageRead, MessageWrite, BytesReader, Writer, WriterBackend, Result};
+use quick_protobuf::sizeofs::*;
+use super::super::super::super::*;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SeverityNumber {
+    SEVERITY_NUMBER_UNSPECIFIED = 0,
+    SEVERITY_NUMBER_TRACE = 1,
+    SEVERITY_NUMBER_TRACE2 = 2,
+    SEVERITY_NUMBER_TRACE3 = 3,
+    SEVERITY_NUMBER_TRACE4 = 4,
+    SEVERITY_NUMBER_DEBUG = 5,
+    SEVERITY_NUMBER_DEBUG2 = 6,
+    SEVERITY_NUMBER_DEBUG3 = 7,
+    SEVERITY_NUMBER_DEBUG4 = 8,
+    SEVERITY_NUMBER_INFO = 9,
+    SEVERITY_NUMBER_INFO2 = 10,
+    SEVERITY_NUMBER_INFO3 = 11,
+    SEVERITY_NUMBER_INFO4 = 12,
+    SEVERITY_NUMBER_WARN = 13,
+    SEVERITY_NUMBER_WARN2 = 14,
+    SEVERITY_NUMBER_WARN3 = 15,
+    SEVERITY_NUMBER_WARN4 = 16,
+    SEVERITY_NUMBER_ERROR = 17,
+    SEVERITY_NUMBER_ERROR2 = 18,
+    SEVERITY_NUMBER_ERROR3 = 19,
+    SEVERITY_NUMBER_ERROR4 = 20,
+    SEVERITY_NUMBER_FATAL = 21,
+    SEVERITY_NUMBER_FATAL2 = 22,
+    SEVERITY_NUMBER_FATAL3 = 23,
+    SEVERITY_NUMBER_FATAL4 = 24,
+}
+
+impl Default for SeverityNumber {
+    fn default() -> Self {
+        SeverityNumber::SEVERITY_NUMBER_UNSPECIFIED
+    }
+}
+
+impl From<i32> for SeverityNumber {
+    fn from(i: i32) -> Self {
+        match i {
+            0 => SeverityNumber::SEVERITY_NUMBER_UNSPECIFIED,
+            1 => SeverityNumber::SEVERITY_NUMBER_TRACE,
+            2 => SeverityNumber::SEVERITY_NUMBER_TRACE2,
+            3 => SeverityNumber::SEVERITY_NUMBER_TRACE3,
+            4 => SeverityNumber::SEVERITY_NUMBER_TRACE4,
+            5 => SeverityNumber::SEVERITY_NUMBER_DEBUG,
+            6 => SeverityNumber::SEVERITY_NUMBER_DEBUG2,
+            7 => SeverityNumber::SEVERITY_NUMBER_DEBUG3,
+            8 => SeverityNumber::SEVERITY_NUMBER_DEBUG4,
+            9 => SeverityNumber::SEVERITY_NUMBER_INFO,
+            10 => SeverityNumber::SEVERITY_NUMBER_INFO2,
+            11 => SeverityNumber::SEVERITY_NUMBER_INFO3,
+            12 => SeverityNumber::SEVERITY_NUMBER_INFO4,
+            13 => SeverityNumber::SEVERITY_NUMBER_WARN,
+            14 => SeverityNumber::SEVERITY_NUMBER_WARN2,
+            15 => SeverityNumber::SEVERITY_NUMBER_WARN3,
+            16 => SeverityNumber::SEVERITY_NUMBER_WARN4,
+            17 => SeverityNumber::SEVERITY_NUMBER_ERROR,
+            18 => SeverityNumber::SEVERITY_NUMBER_ERROR2,
+            19 => SeverityNumber::SEVERITY_NUMBER_ERROR3,
+            20 => SeverityNumber::SEVERITY_NUMBER_ERROR4,
+            21 => SeverityNumber::SEVERITY_NUMBER_FATAL,
+            22 => SeverityNumber::SEVERITY_NUMBER_FATAL2,
+            23 => SeverityNumber::SEVERITY_NUMBER_FATAL3,
+            24 => SeverityNumber::SEVERITY_NUMBER_FATAL4,
+            _ => Self::default(),
+        }
+    }
+}
+
+impl<'a> From<&'a str> for SeverityNumber {
+    fn from(s: &'a str) -> Self {
+        match s {
+            "SEVERITY_NUMBER_UNSPECIFIED" => SeverityNumber::SEVERITY_NUMBER_UNSPECIFIED,
+            "SEVERITY_NUMBER_TRACE" => SeverityNumber::SEVERITY_NUMBER_TRACE,
+            "SEVERITY_NUMBER_TRACE2" => SeverityNumber::SEVERITY_NUMBER_TRACE2,
+            "SEVERITY_NUMBER_TRACE3" => SeverityNumber::SEVERITY_NUMBER_TRACE3,
+            "SEVERITY_NUMBER_TRACE4" => SeverityNumber::SEVERITY_NUMBER_TRACE4,
+            "SEVERITY_NUMBER_DEBUG" => SeverityNumber::SEVERITY_NUMBER_DEBUG,
+            "SEVERITY_NUMBER_DEBUG2" => SeverityNumber::SEVERITY_NUMBER_DEBUG2,
+            "SEVERITY_NUMBER_DEBUG3" => SeverityNumber::SEVERITY_NUMBER_DEBUG3,
+            "SEVERITY_NUMBER_DEBUG4" => SeverityNumber::SEVERITY_NUMBER_DEBUG4,
+            "SEVERITY_NUMBER_INFO" => SeverityNumber::SEVERITY_NUMBER_INFO,
+            "SEVERITY_NUMBER_INFO2" => SeverityNumber::SEVERITY_NUMBER_INFO2,
+            "SEVERITY_NUMBER_INFO3" => SeverityNumber::SEVERITY_NUMBER_INFO3,
+            "SEVERITY_NUMBER_INFO4" => SeverityNumber::SEVERITY_NUMBER_INFO4,
+            "SEVERITY_NUMBER_WARN" => SeverityNumber::SEVERITY_NUMBER_WARN,
+            "SEVERITY_NUMBER_WARN2" => SeverityNumber::SEVERITY_NUMBER_WARN2,
+            "SEVERITY_NUMBER_WARN3" => SeverityNumber::SEVERITY_NUMBER_WARN3,
+            "SEVERITY_NUMBER_WARN4" => SeverityNumber::SEVERITY_NUMBER_WARN4,
+            "SEVERITY_NUMBER_ERROR" => SeverityNumber::SEVERITY_NUMBER_ERROR,
+            "SEVERITY_NUMBER_ERROR2" => SeverityNumber::SEVERITY_NUMBER_ERROR2,
+            "SEVERITY_NUMBER_ERROR3" => SeverityNumber::SEVERITY_NUMBER_ERROR3,
+            "SEVERITY_NUMBER_ERROR4" => SeverityNumber::SEVERITY_NUMBER_ERROR4,
+            "SEVERITY_NUMBER_FATAL" => SeverityNumber::SEVERITY_NUMBER_FATAL,
+            "SEVERITY_NUMBER_FATAL2" => SeverityNumber::SEVERITY_NUMBER_FATAL2,
+            "SEVERITY_NUMBER_FATAL3" => SeverityNumber::SEVERITY_NUMBER_FATAL3,
+            "SEVERITY_NUMBER_FATAL4" => SeverityNumber::SEVERITY_NUMBER_FATAL4,
+            _ => Self::default(),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum LogRecordFlags {
+    LOG_RECORD_FLAG_UNSPECIFIED = 0,
+    LOG_RECORD_FLAG_TRACE_FLAGS_MASK = 255,
+}
+
+impl Default for LogRecordFlags {
+    fn default() -> Self {
+        LogRecordFlags::LOG_RECORD_FLAG_UNSPECIFIED
+    }
+}
+
+impl From<i32> for LogRecordFlags {
+    fn from(i: i32) -> Self {
+        match i {
+            0 => LogRecordFlags::LOG_RECORD_FLAG_UNSPECIFIED,
+            255 => LogRecordFlags::LOG_RECORD_FLAG_TRACE_FLAGS_MASK,
+            _ => Self::default(),
+        }
+    }
+}
+
+impl<'a> From<&'a str> for LogRecordFlags {
+    fn from(s: &'a str) -> Self {
+        match s {
+            "LOG_RECORD_FLAG_UNSPECIFIED" => LogRecordFlags::LOG_RECORD_FLAG_UNSPECIFIED,
+            "LOG_RECORD_FLAG_TRACE_FLAGS_MASK" => LogRecordFlags::LOG_RECORD_FLAG_TRACE_FLAGS_MASK,
+            _ => Self::default(),
+        }
+    }
+}
+
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Debug, Default, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ExportLogsServiceRequest<'a> {
+    pub resource_logs: Vec<opentelemetry::proto::logs::v1::ResourceLogs<'a>>,
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "crate::UnknownFields::is_empty"))]
+    pub unknown_fields: crate::UnknownFields,
+}
+
+impl<'a> MessageRead<'a> for ExportLogsServiceRequest<'a> {
+    fn from_reader(r: &mut BytesReader, bytes: &'a [u8]) -> Result<Self> {
+        let mut msg = Self::default();
+        while !r.is_eof() {
+            match r.next_tag(bytes) {
+                Ok(10) => msg.resource_logs.push(r.read_message::<opentelemetry::proto::logs::v1::ResourceLogs>(bytes)?),
+                Ok(t) => { msg.unknown_fields.read(r, bytes, t)?; }
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(msg)
+    }
+}
+
+impl<'a> MessageWrite for ExportLogsServiceRequest<'a> {
+    fn get_size(&self) -> usize {
+        0
+        + self.unknown_fields.len()
+        + self.resource_logs.iter().map(|s| 1 + sizeof_len((s).get_size())).sum::<usize>()
+    }
+
+    fn write_message<W: WriterBackend>(&self, w: &mut Writer<W>) -> Result<()> {
+        for s in &self.resource_logs { w.write_with_tag(10, |w| w.write_message(s))?; }
+        self.unknown_fields.write(w)?;
+        Ok(())
+    }
+}
+
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Debug, Default, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ExportLogsServiceResponse<'a> {
+    pub partial_success: Option<opentelemetry::proto::logs::v1::ExportLogsPartialSuccess<'a>>,
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "crate::UnknownFields::is_empty"))]
+    pub unknown_fields: crate::UnknownFields,
+}
+
+impl<'a> MessageRead<'a> for ExportLogsServiceResponse<'a> {
+    fn from_reader(r: &mut BytesReader, bytes: &'a [u8]) -> Result<Self> {
+        let mut msg = Self::default();
+        while !r.is_eof() {
+            match r.next_tag(bytes) {
+                Ok(10) => msg.partial_success = Some(r.read_message::<opentelemetry::proto::logs::v1::ExportLogsPartialSuccess>(bytes)?),
+                Ok(t) => { msg.unknown_fields.read(r, bytes, t)?; }
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(msg)
+    }
+}
+
+impl<'a> MessageWrite for ExportLogsServiceResponse<'a> {
+    fn get_size(&self) -> usize {
+        0
+        + self.unknown_fields.len()
+        + self.partial_success.as_ref().map_or(0, |m| 1 + sizeof_len((m).get_size()))
+    }
+
+    fn write_message<W: WriterBackend>(&self, w: &mut Writer<W>) -> Result<()> {
+        if let Some(ref s) = self.partial_success { w.write_with_tag(10, |w| w.write_message(s))?; }
+        self.unknown_fields.write(w)?;
+        Ok(())
+    }
+}
+
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Debug, Default, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ExportLogsPartialSuccess<'a> {
+    pub rejected_log_records: i64,
+    pub error_message: Cow<'a, str>,
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "crate::UnknownFields::is_empty"))]
+    pub unknown_fields: crate::UnknownFields,
+}
+
+impl<'a> MessageRead<'a> for ExportLogsPartialSuccess<'a> {
+    fn from_reader(r: &mut BytesReader, bytes: &'a [u8]) -> Result<Self> {
+        let mut msg = Self::default();
+        while !r.is_eof() {
+            match r.next_tag(bytes) {
+                Ok(8) => msg.rejected_log_records = r.read_int64(bytes)?,
+                Ok(18) => msg.error_message = r.read_string(bytes).map(Cow::Borrowed)?,
+                Ok(t) => { msg.unknown_fields.read(r, bytes, t)?; }
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(msg)
+    }
+}
+
+impl<'a> MessageWrite for ExportLogsPartialSuccess<'a> {
+    fn get_size(&self) -> usize {
+        0
+        + self.unknown_fields.len()
+        + if self.rejected_log_records == 0i64 { 0 } else { 1 + sizeof_varint(*(&self.rejected_log_records) as u64) }
+        + if self.error_message == "" { 0 } else { 1 + sizeof_len((&self.error_message).len()) }
+    }
+
+    fn write_message<W: WriterBackend>(&self, w: &mut Writer<W>) -> Result<()> {
+        if self.rejected_log_records != 0i64 { w.write_with_tag(8, |w| w.write_int64(*&self.rejected_log_records))?; }
+        if self.error_message != "" { w.write_with_tag(18, |w| w.write_string(&**&self.error_message))?; }
+        self.unknown_fields.write(w)?;
+        Ok(())
+    }
+}
+
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Debug, Default, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LogsData<'a> {
+    pub resource_logs: Vec<opentelemetry::proto::logs::v1::ResourceLogs<'a>>,
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "crate::UnknownFields::is_empty"))]
+    pub unknown_fields: crate::UnknownFields,
+}
+
+impl<'a> MessageRead<'a> for LogsData<'a> {
+    fn from_reader(r: &mut BytesReader, bytes: &'a [u8]) -> Result<Self> {
+        let mut msg = Self::default();
+        while !r.is_eof() {
+            match r.next_tag(bytes) {
+                Ok(10) => msg.resource_logs.push(r.read_message::<opentelemetry::proto::logs::v1::ResourceLogs>(bytes)?),
+                Ok(t) => { msg.unknown_fields.read(r, bytes, t)?; }
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(msg)
+    }
+}
+
+impl<'a> MessageWrite for LogsData<'a> {
+    fn get_size(&self) -> usize {
+        0
+        + self.unknown_fields.len()
+        + self.resource_logs.iter().map(|s| 1 + sizeof_len((s).get_size())).sum::<usize>()
+    }
+
+    fn write_message<W: WriterBackend>(&self, w: &mut Writer<W>) -> Result<()> {
+        for s in &self.resource_logs { w.write_with_tag(10, |w| w.write_message(s))?; }
+        self.unknown_fields.write(w)?;
+        Ok(())
+    }
+}
+
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Debug, Default, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ResourceLogs<'a> {
+    pub resource: Option<opentelemetry::proto::common::v1::Resource<'a>>,
+    pub scope_logs: Vec<opentelemetry::proto::logs::v1::ScopeLogs<'a>>,
+    pub schema_url: Cow<'a, str>,
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "crate::UnknownFields::is_empty"))]
+    pub unknown_fields: crate::UnknownFields,
+}
+
+impl<'a> MessageRead<'a> for ResourceLogs<'a> {
+    fn from_reader(r: &mut BytesReader, bytes: &'a [u8]) -> Result<Self> {
+        let mut msg = Self::default();
+        while !r.is_eof() {
+            match r.next_tag(bytes) {
+                Ok(10) => msg.resource = Some(r.read_message::<opentelemetry::proto::common::v1::Resource>(bytes)?),
+                Ok(18) => msg.scope_logs.push(r.read_message::<opentelemetry::proto::logs::v1::ScopeLogs>(bytes)?),
+                Ok(26) => msg.schema_url = r.read_string(bytes).map(Cow::Borrowed)?,
+                Ok(t) => { msg.unknown_fields.read(r, bytes, t)?; }
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(msg)
+    }
+}
+
+impl<'a> MessageWrite for ResourceLogs<'a> {
+    fn get_size(&self) -> usize {
+        0
+        + self.unknown_fields.len()
+        + self.resource.as_ref().map_or(0, |m| 1 + sizeof_len((m).get_size()))
+        + self.scope_logs.iter().map(|s| 1 + sizeof_len((s).get_size())).sum::<usize>()
+        + if self.schema_url == "" { 0 } else { 1 + sizeof_len((&self.schema_url).len()) }
+    }
+
+    fn write_message<W: WriterBackend>(&self, w: &mut Writer<W>) -> Result<()> {
+        if let Some(ref s) = self.resource { w.write_with_tag(10, |w| w.write_message(s))?; }
+        for s in &self.scope_logs { w.write_with_tag(18, |w| w.write_message(s))?; }
+        if self.schema_url != "" { w.write_with_tag(26, |w| w.write_string(&**&self.schema_url))?; }
+        self.unknown_fields.write(w)?;
+        Ok(())
+    }
+}
+
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Debug, Default, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ScopeLogs<'a> {
+    pub scope: Option<opentelemetry::proto::common::v1::InstrumentationScope<'a>>,
+    pub log_records: Vec<opentelemetry::proto::logs::v1::LogRecord<'a>>,
+    pub schema_url: Cow<'a, str>,
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "crate::UnknownFields::is_empty"))]
+    pub unknown_fields: crate::UnknownFields,
+}
+
+impl<'a> MessageRead<'a> for ScopeLogs<'a> {
+    fn from_reader(r: &mut BytesReader, bytes: &'a [u8]) -> Result<Self> {
+        let mut msg = Self::default();
+        while !r.is_eof() {
+            match r.next_tag(bytes) {
+                Ok(10) => msg.scope = Some(r.read_message::<opentelemetry::proto::common::v1::InstrumentationScope>(bytes)?),
+                Ok(18) => msg.log_records.push(r.read_message::<opentelemetry::proto::logs::v1::LogRecord>(bytes)?),
+                Ok(26) => msg.schema_url = r.read_string(bytes).map(Cow::Borrowed)?,
+                Ok(t) => { msg.unknown_fields.read(r, bytes, t)?; }
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(msg)
+    }
+}
+
+impl<'a> MessageWrite for ScopeLogs<'a> {
+    fn get_size(&self) -> usize {
+        0
+        + self.unknown_fields.len()
+        + self.scope.as_ref().map_or(0, |m| 1 + sizeof_len((m).get_size()))
+        + self.log_records.iter().map(|s| 1 + sizeof_len((s).get_size())).sum::<usize>()
+        + if self.schema_url == "" { 0 } else { 1 + sizeof_len((&self.schema_url).len()) }
+    }
+
+    fn write_message<W: WriterBackend>(&self, w: &mut Writer<W>) -> Result<()> {
+        if let Some(ref s) = self.scope { w.write_with_tag(10, |w| w.write_message(s))?; }
+        for s in &self.log_records { w.write_with_tag(18, |w| w.write_message(s))?; }
+        if self.schema_url != "" { w.write_with_tag(26, |w| w.write_string(&**&self.schema_url))?; }
+        self.unknown_fields.write(w)?;
+        Ok(())
+    }
+}
+
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Debug, Default, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LogRecord<'a> {
+    pub time_unix_nano: u64,
+    pub observed_time_unix_nano: u64,
+    pub severity_number: opentelemetry::proto::logs::v1::SeverityNumber,
+    pub severity_text: Cow<'a, str>,
+    pub body: Option<opentelemetry::proto::common::v1::AnyValue<'a>>,
+    pub attributes: Vec<opentelemetry::proto::common::v1::KeyValue<'a>>,
+    pub dropped_attributes_count: u32,
+    pub flags: u32,
+    pub trace_id: Cow<'a, [u8]>,
+    pub span_id: Cow<'a, [u8]>,
+    pub event_name: Cow<'a, str>,
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "crate::UnknownFields::is_empty"))]
+    pub unknown_fields: crate::UnknownFields,
+}
+
+impl<'a> MessageRead<'a> for LogRecord<'a> {
+    fn from_reader(r: &mut BytesReader, bytes: &'a [u8]) -> Result<Self> {
+        let mut msg = Self::default();
+        while !r.is_eof() {
+            match r.next_tag(bytes) {
+                Ok(9) => msg.time_unix_nano = r.read_fixed64(bytes)?,
+                Ok(89) => msg.observed_time_unix_nano = r.read_fixed64(bytes)?,
+                Ok(16) => msg.severity_number = r.read_enum(bytes)?,
+                Ok(26) => msg.severity_text = r.read_string(bytes).map(Cow::Borrowed)?,
+                Ok(42) => msg.body = Some(r.read_message::<opentelemetry::proto::common::v1::AnyValue>(bytes)?),
+                Ok(50) => msg.attributes.push(r.read_message::<opentelemetry::proto::common::v1::KeyValue>(bytes)?),
+                Ok(56) => msg.dropped_attributes_count = r.read_uint32(bytes)?,
+                Ok(69) => msg.flags = r.read_fixed32(bytes)?,
+                Ok(74) => msg.trace_id = r.read_bytes(bytes).map(Cow::Borrowed)?,
+                Ok(82) => msg.span_id = r.read_bytes(bytes).map(Cow::Borrowed)?,
+                Ok(98) => msg.event_name = r.read_string(bytes).map(Cow::Borrowed)?,
+                Ok(t) => { msg.unknown_fields.read(r, bytes, t)?; }
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(msg)
+    }
+}
+
+impl<'a> MessageWrite for LogRecord<'a> {
+    fn get_size(&self) -> usize {
+        0
+        + self.unknown_fields.len()
+        + if self.time_unix_nano == 0u64 { 0 } else { 1 + 8 }
+        + if self.observed_time_unix_nano == 0u64 { 0 } else { 1 + 8 }
+        + if self.severity_number == opentelemetry::proto::logs::v1::SeverityNumber::SEVERITY_NUMBER_UNSPECIFIED { 0 } else { 1 + sizeof_varint(*(&self.severity_number) as u64) }
+        + if self.severity_text == "" { 0 } else { 1 + sizeof_len((&self.severity_text).len()) }
+        + self.body.as_ref().map_or(0, |m| 1 + sizeof_len((m).get_size()))
+        + self.attributes.iter().map(|s| 1 + sizeof_len((s).get_size())).sum::<usize>()
+        + if self.dropped_attributes_count == 0u32 { 0 } else { 1 + sizeof_varint(*(&self.dropped_attributes_count) as u64) }
+        + if self.flags == 0u32 { 0 } else { 1 + 4 }
+        + if self.trace_id == Cow::Borrowed(b"") { 0 } else { 1 + sizeof_len((&self.trace_id).len()) }
+        + if self.span_id == Cow::Borrowed(b"") { 0 } else { 1 + sizeof_len((&self.span_id).len()) }
+        + if self.event_name == "" { 0 } else { 1 + sizeof_len((&self.event_name).len()) }
+    }
+
+    fn write_message<W: WriterBackend>(&self, w: &mut Writer<W>) -> Result<()> {
+        if self.time_unix_nano != 0u64 { w.write_with_tag(9, |w| w.write_fixed64(*&self.time_unix_nano))?; }
+        if self.observed_time_unix_nano != 0u64 { w.write_with_tag(89, |w| w.write_fixed64(*&self.observed_time_unix_nano))?; }
+        if self.severity_number != opentelemetry::proto::logs::v1::SeverityNumber::SEVERITY_NUMBER_UNSPECIFIED { w.write_with_tag(16, |w| w.write_enum(*&self.severity_number as i32))?; }
+        if self.severity_text != "" { w.write_with_tag(26, |w| w.write_string(&**&self.severity_text))?; }
+        if let Some(ref s) = self.body { w.write_with_tag(42, |w| w.write_message(s))?; }
+        for s in &self.attributes { w.write_with_tag(50, |w| w.write_message(s))?; }
+        if self.dropped_attributes_count != 0u32 { w.write_with_tag(56, |w| w.write_uint32(*&self.dropped_attributes_count))?; }
+        if self.flags != 0u32 { w.write_with_tag(69, |w| w.write_fixed32(*&self.flags))?; }
+        if self.trace_id != Cow::Borrowed(b"") { w.write_with_tag(74, |w| w.write_bytes(&**&self.trace_id))?; }
+        if self.span_id != Cow::Borrowed(b"") { w.write_with_tag(82, |w| w.write_bytes(&**&self.span_id))?; }
+        if self.event_name != "" { w.write_with_tag(98, |w| w.write_string(&**&self.event_name))?; }
+        self.unknown_fields.write(w)?;
+        Ok(())
+    }
+}
+
@@ -0,0 +1,1102 @@
+// Automatically generated rust module for 'metrics.proto' file
+
+#![allow(non_snake_case)]
+#![allow(non_upper_case_globals)]
+#![allow(non_camel_case_types)]
+#![allow(unused_imports)]
+#![allow(unknown_lints)]
+#![allow(clippy::all)]
+#![cfg_attr(rustfmt, rustfmt_skip)]
+
+
+use std::borrow::Cow;
+use quick_protobuf::{MessageInfo, MessageRead, MessageWrite, BytesReader, Writer, WriterBackend, Result};
+use quick_protobuf::sizeofs::*;
+use super::super::super::super::*;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AggregationTemporality {
+    AGGREGATION_TEMPORALITY_UNSPECIFIED = 0,
+    AGGREGATION_TEMPORALITY_DELTA = 1,
+    AGGREGATION_TEMPORALITY_CUMULATIVE = 2,
+}
+
+impl Default for AggregationTemporality {
+    fn default() -> Self {
+        AggregationTemporality::AGGREGATION_TEMPORALITY_UNSPECIFIED
+    }
+}
+
+impl From<i32> for AggregationTemporality {
+    fn from(i: i32) -> Self {
+        match i {
+            0 => AggregationTemporality::AGGREGATION_TEMPORALITY_UNSPECIFIED,
+            1 => AggregationTemporality::AGGREGATION_TEMPORALITY_DELTA,
+            2 => AggregationTemporality::AGGREGATION_TEMPORALITY_CUMULATIVE,
+            _ => Self::default(),
+        }
+    }
+}
+
+impl<'a> From<&'a str> for AggregationTemporality {
+    fn from(s: &'a str) -> Self {
+        match s {
+            "AGGREGATION_TEMPORALITY_UNSPECIFIED" => AggregationTemporality::AGGREGATION_TEMPORALITY_UNSPECIFIED,
+            "AGGREGATION_TEMPORALITY_DELTA" => AggregationTemporality::AGGREGATION_TEMPORALITY_DELTA,
+            "AGGREGATION_TEMPORALITY_CUMULATIVE" => AggregationTemporality::AGGREGATION_TEMPORALITY_CUMULATIVE,
+            _ => Self::default(),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DataPointFlags {
+    FLAG_NONE = 0,
+    FLAG_NO_RECORDED_VALUE = 1,
+}
+
+impl Default for DataPointFlags {
+    fn default() -> Self {
+        DataPointFlags::FLAG_NONE
+    }
+}
+
+impl From<i32> for DataPointFlags {
+    fn from(i: i32) -> Self {
+        match i {
+            0 => DataPointFlags::FLAG_NONE,
+            1 => DataPointFlags::FLAG_NO_RECORDED_VALUE,
+            _ => Self::default(),
+        }
+    }
+}
+
+impl<'a> From<&'a str> for DataPointFlags {
+    fn from(s: &'a str) -> Self {
+        match s {
+            "FLAG_NONE" => DataPointFlags::FLAG_NONE,
+            "FLAG_NO_RECORDED_VALUE" => DataPointFlags::FLAG_NO_RECORDED_VALUE,
+            _ => Self::default(),
+        }
+    }
+}
+
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Debug, Default, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ExportMetricsServiceRequest<'a> {
+    pub resource_metrics: Vec<opentelemetry::proto::metrics::v1::ResourceMetrics<'a>>,
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "crate::UnknownFields::is_empty"))]
+    pub unknown_fields: crate::UnknownFields,
+}
+
+impl<'a> MessageRead<'a> for ExportMetricsServiceRequest<'a> {
+    fn from_reader(r: &mut BytesReader, bytes: &'a [u8]) -> Result<Self> {
+        let mut msg = Self::default();
+        while !r.is_eof() {
+            match r.next_tag(bytes) {
+                Ok(10) => msg.resource_metrics.push(r.read_message::<opentelemetry::proto::metrics::v1::ResourceMetrics>(bytes)?),
+                Ok(t) => { msg.unknown_fields.read(r, bytes, t)?; }
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(msg)
+    }
+}
+
+impl<'a> MessageWrite for ExportMetricsServiceRequest<'a> {
+    fn get_size(&self) -> usize {
+        0
+        + self.unknown_fields.len()
+        + self.resource_metrics.iter().map(|s| 1 + sizeof_len((s).get_size())).sum::<usize>()
+    }
+
+    fn write_message<W: WriterBackend>(&self, w: &mut Writer<W>) -> Result<()> {
+        for s in &self.resource_metrics { w.write_with_tag(10, |w| w.write_message(s))?; }
+        self.unknown_fields.write(w)?;
+        Ok(())
+    }
+}
+
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Debug, Default, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ExportMetricsServiceResponse<'a> {
+    pub partial_success: Option<opentelemetry::proto::metrics::v1::ExportMetricsPartialSuccess<'a>>,
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "crate::UnknownFields::is_empty"))]
+    pub unknown_fields: crate::UnknownFields,
+}
+
+impl<'a> MessageRead<'a> for ExportMetricsServiceResponse<'a> {
+    fn from_reader(r: &mut BytesReader, bytes: &'a [u8]) -> Result<Self> {
+        let mut msg = Self::default();
+        while !r.is_eof() {
+            match r.next_tag(bytes) {
+                Ok(10) => msg.partial_success = Some(r.read_message::<opentelemetry::proto::metrics::v1::ExportMetricsPartialSuccess>(bytes)?),
+                Ok(t) => { msg.unknown_fields.read(r, bytes, t)?; }
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(msg)
+    }
+}
+
+impl<'a> MessageWrite for ExportMetricsServiceResponse<'a> {
+    fn get_size(&self) -> usize {
+        0
+        + self.unknown_fields.len()
+        + self.partial_success.as_ref().map_or(0, |m| 1 + sizeof_len((m).get_size()))
+    }
+
+    fn write_message<W: WriterBackend>(&self, w: &mut Writer<W>) -> Result<()> {
+        if let Some(ref s) = self.partial_success { w.write_with_tag(10, |w| w.write_message(s))?; }
+        self.unknown_fields.write(w)?;
+        Ok(())
+    }
+}
+
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Debug, Default, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ExportMetricsPartialSuccess<'a> {
+    pub rejected_data_points: i64,
+    pub error_message: Cow<'a, str>,
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "crate::UnknownFields::is_empty"))]
+    pub unknown_fields: crate::UnknownFields,
+}
+
+impl<'a> MessageRead<'a> for ExportMetricsPartialSuccess<'a> {
+    fn from_reader(r: &mut BytesReader, bytes: &'a [u8]) -> Result<Self> {
+        let mut msg = Self::default();
+        while !r.is_eof() {
+            match r.next_tag(bytes) {
+                Ok(8) => msg.rejected_data_points = r.read_int64(bytes)?,
+                Ok(18) => msg.error_message = r.read_string(bytes).map(Cow::Borrowed)?,
+                Ok(t) => { msg.unknown_fields.read(r, bytes, t)?; }
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(msg)
+    }
+}
+
+impl<'a> MessageWrite for ExportMetricsPartialSuccess<'a> {
+    fn get_size(&self) -> usize {
+        0
+        + self.unknown_fields.len()
+        + if self.rejected_data_points == 0i64 { 0 } else { 1 + sizeof_varint(*(&self.rejected_data_points) as u64) }
+        + if self.error_message == "" { 0 } else { 1 + sizeof_len((&self.error_message).len()) }
+    }
+
+    fn write_message<W: WriterBackend>(&self, w: &mut Writer<W>) -> Result<()> {
+        if self.rejected_data_points != 0i64 { w.write_with_tag(8, |w| w.write_int64(*&self.rejected_data_points))?; }
+        if self.error_message != "" { w.write_with_tag(18, |w| w.write_string(&**&self.error_message))?; }
+        self.unknown_fields.write(w)?;
+        Ok(())
+    }
+}
+
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Debug, Default, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MetricsData<'a> {
+    pub resource_metrics: Vec<opentelemetry::proto::metrics::v1::ResourceMetrics<'a>>,
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "crate::UnknownFields::is_empty"))]
+    pub unknown_fields: crate::UnknownFields,
+}
+
+impl<'a> MessageRead<'a> for MetricsData<'a> {
+    fn from_reader(r: &mut BytesReader, bytes: &'a [u8]) -> Result<Self> {
+        let mut msg = Self::default();
+        while !r.is_eof() {
+            match r.next_tag(bytes) {
+                Ok(10) => msg.resource_metrics.push(r.read_message::<opentelemetry::proto::metrics::v1::ResourceMetrics>(bytes)?),
+                Ok(t) => { msg.unknown_fields.read(r, bytes, t)?; }
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(msg)
+    }
+}
+
+impl<'a> MessageWrite for MetricsData<'a> {
+    fn get_size(&self) -> usize {
+        0
+        + self.unknown_fields.len()
+        + self.resource_metrics.iter().map(|s| 1 + sizeof_len((s).get_size())).sum::<usize>()
+    }
+
+    fn write_message<W: WriterBackend>(&self, w: &mut Writer<W>) -> Result<()> {
+        for s in &self.resource_metrics { w.write_with_tag(10, |w| w.write_message(s))?; }
+        self.unknown_fields.write(w)?;
+        Ok(())
+    }
+}
+
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Debug, Default, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ResourceMetrics<'a> {
+    pub resource: Option<opentelemetry::proto::common::v1::Resource<'a>>,
+    pub scope_metrics: Vec<opentelemetry::proto::metrics::v1::ScopeMetrics<'a>>,
+    pub schema_url: Cow<'a, str>,
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "crate::UnknownFields::is_empty"))]
+    pub unknown_fields: crate::UnknownFields,
+}
+
+impl<'a> MessageRead<'a> for ResourceMetrics<'a> {
+    fn from_reader(r: &mut BytesReader, bytes: &'a [u8]) -> Result<Self> {
+        let mut msg = Self::default();
+        while !r.is_eof() {
+            match r.next_tag(bytes) {
+                Ok(10) => msg.resource = Some(r.read_message::<opentelemetry::proto::common::v1::Resource>(bytes)?),
+                Ok(18) => msg.scope_metrics.push(r.read_message::<opentelemetry::proto::metrics::v1::ScopeMetrics>(bytes)?),
+                Ok(26) => msg.schema_url = r.read_string(bytes).map(Cow::Borrowed)?,
+                Ok(t) => { msg.unknown_fields.read(r, bytes, t)?; }
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(msg)
+    }
+}
+
+impl<'a> MessageWrite for ResourceMetrics<'a> {
+    fn get_size(&self) -> usize {
+        0
+        + self.unknown_fields.len()
+        + self.resource.as_ref().map_or(0, |m| 1 + sizeof_len((m).get_size()))
+        + self.scope_metrics.iter().map(|s| 1 + sizeof_len((s).get_size())).sum::<usize>()
+        + if self.schema_url == "" { 0 } else { 1 + sizeof_len((&self.schema_url).len()) }
+    }
+
+    fn write_message<W: WriterBackend>(&self, w: &mut Writer<W>) -> Result<()> {
+        if let Some(ref s) = self.resource { w.write_with_tag(10, |w| w.write_message(s))?; }
+        for s in &self.scope_metrics { w.write_with_tag(18, |w| w.write_message(s))?; }
+        if self.schema_url != "" { w.write_with_tag(26, |w| w.write_string(&**&self.schema_url))?; }
+        self.unknown_fields.write(w)?;
+        Ok(())
+    }
+}
+
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Debug, Default, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ScopeMetrics<'a> {
+    pub scope: Option<opentelemetry::proto::common::v1::InstrumentationScope<'a>>,
+    pub metrics: Vec<opentelemetry::proto::metrics::v1::Metric<'a>>,
+    pub schema_url: Cow<'a, str>,
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "crate::UnknownFields::is_empty"))]
+    pub unknown_fields: crate::UnknownFields,
+}
+
+impl<'a> MessageRead<'a> for ScopeMetrics<'a> {
+    fn from_reader(r: &mut BytesReader, bytes: &'a [u8]) -> Result<Self> {
+        let mut msg = Self::default();
+        while !r.is_eof() {
+            match r.next_tag(bytes) {
+                Ok(10) => msg.scope = Some(r.read_message::<opentelemetry::proto::common::v1::InstrumentationScope>(bytes)?),
+                Ok(18) => msg.metrics.push(r.read_message::<opentelemetry::proto::metrics::v1::Metric>(bytes)?),
+                Ok(26) => msg.schema_url = r.read_string(bytes).map(Cow::Borrowed)?,
+                Ok(t) => { msg.unknown_fields.read(r, bytes, t)?; }
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(msg)
+    }
+}
+
+impl<'a> MessageWrite for ScopeMetrics<'a> {
+    fn get_size(&self) -> usize {
+        0
+        + self.unknown_fields.len()
+        + self.scope.as_ref().map_or(0, |m| 1 + sizeof_len((m).get_size()))
+        + self.metrics.iter().map(|s| 1 + sizeof_len((s).get_size())).sum::<usize>()
+        + if self.schema_url == "" { 0 } else { 1 + sizeof_len((&self.schema_url).len()) }
+    }
+
+    fn write_message<W: WriterBackend>(&self, w: &mut Writer<W>) -> Result<()> {
+        if let Some(ref s) = self.scope { w.write_with_tag(10, |w| w.write_message(s))?; }
+        for s in &self.metrics { w.write_with_tag(18, |w| w.write_message(s))?; }
+        if self.schema_url != "" { w.write_with_tag(26, |w| w.write_string(&**&self.schema_url))?; }
+        self.unknown_fields.write(w)?;
+        Ok(())
+    }
+}
+
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Debug, Default, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Metric<'a> {
+    pub name: Cow<'a, str>,
+    pub description: Cow<'a, str>,
+    pub unit: Cow<'a, str>,
+    pub metadata: Vec<opentelemetry::proto::common::v1::KeyValue<'a>>,
+    pub data: opentelemetry::proto::metrics::v1::mod_Metric::OneOfdata<'a>,
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "crate::UnknownFields::is_empty"))]
+    pub unknown_fields: crate::UnknownFields,
+}
+
+impl<'a> MessageRead<'a> for Metric<'a> {
+    fn from_reader(r: &mut BytesReader, bytes: &'a [u8]) -> Result<Self> {
+        let mut msg = Self::default();
+        while !r.is_eof() {
+            match r.next_tag(bytes) {
+                Ok(10) => msg.name = r.read_string(bytes).map(Cow::Borrowed)?,
+                Ok(18) => msg.description = r.read_string(bytes).map(Cow::Borrowed)?,
+                Ok(26) => msg.unit = r.read_string(bytes).map(Cow::Borrowed)?,
+                Ok(98) => msg.metadata.push(r.read_message::<opentelemetry::proto::common::v1::KeyValue>(bytes)?),
+                Ok(42) => msg.data = opentelemetry::proto::metrics::v1::mod_Metric::OneOfdata::gauge(r.read_message::<opentelemetry::proto::metrics::v1::Gauge>(bytes)?),
+                Ok(58) => msg.data = opentelemetry::proto::metrics::v1::mod_Metric::OneOfdata::sum(r.read_message::<opentelemetry::proto::metrics::v1::Sum>(bytes)?),
+                Ok(74) => msg.data = opentelemetry::proto::metrics::v1::mod_Metric::OneOfdata::histogram(r.read_message::<opentelemetry::proto::metrics::v1::Histogram>(bytes)?),
+                Ok(82) => msg.data = opentelemetry::proto::metrics::v1::mod_Metric::OneOfdata::exponential_histogram(r.read_message::<opentelemetry::proto::metrics::v1::ExponentialHistogram>(bytes)?),
+                Ok(90) => msg.data = opentelemetry::proto::metrics::v1::mod_Metric::OneOfdata::summary(r.read_message::<opentelemetry::proto::metrics::v1::Summary>(bytes)?),
+                Ok(t) => { msg.unknown_fields.read(r, bytes, t)?; }
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(msg)
+    }
+}
+
+impl<'a> MessageWrite for Metric<'a> {
+    fn get_size(&self) -> usize {
+        0
+        + self.unknown_fields.len()
+        + if self.name == "" { 0 } else { 1 + sizeof_len((&self.name).len()) }
+        + if self.description == "" { 0 } else { 1 + sizeof_len((&self.description).len()) }
+        + if self.unit == "" { 0 } else { 1 + sizeof_len((&self.unit).len()) }
+        + self.metadata.iter().map(|s| 1 + sizeof_len((s).get_size())).sum::<usize>()
+        + match self.data {
+            opentelemetry::proto::metrics::v1::mod_Metric::OneOfdata::gauge(ref m) => 1 + sizeof_len((m).get_size()),
+            opentelemetry::proto::metrics::v1::mod_Metric::OneOfdata::sum(ref m) => 1 + sizeof_len((m).get_size()),
+            opentelemetry::proto::metrics::v1::mod_Metric::OneOfdata::histogram(ref m) => 1 + sizeof_len((m).get_size()),
+            opentelemetry::proto::metrics::v1::mod_Metric::OneOfdata::exponential_histogram(ref m) => 1 + sizeof_len((m).get_size()),
+            opentelemetry::proto::metrics::v1::mod_Metric::OneOfdata::summary(ref m) => 1 + sizeof_len((m).get_size()),
+            opentelemetry::proto::metrics::v1::mod_Metric::OneOfdata::None => 0,
+    }    }
+
+    fn write_message<W: WriterBackend>(&self, w: &mut Writer<W>) -> Result<()> {
+        if self.name != "" { w.write_with_tag(10, |w| w.write_string(&**&self.name))?; }
+        if self.description != "" { w.write_with_tag(18, |w| w.write_string(&**&self.description))?; }
+        if self.unit != "" { w.write_with_tag(26, |w| w.write_string(&**&self.unit))?; }
+        for s in &self.metadata { w.write_with_tag(98, |w| w.write_message(s))?; }
+        match self.data {            opentelemetry::proto::metrics::v1::mod_Metric::OneOfdata::gauge(ref m) => { w.write_with_tag(42, |w| w.write_message(m))? },
+            opentelemetry::proto::metrics::v1::mod_Metric::OneOfdata::sum(ref m) => { w.write_with_tag(58, |w| w.write_message(m))? },
+            opentelemetry::proto::metrics::v1::mod_Metric::OneOfdata::histogram(ref m) => { w.write_with_tag(74, |w| w.write_message(m))? },
+            opentelemetry::proto::metrics::v1::mod_Metric::OneOfdata::exponential_histogram(ref m) => { w.write_with_tag(82, |w| w.write_message(m))? },
+            opentelemetry::proto::metrics::v1::mod_Metric::OneOfdata::summary(ref m) => { w.write_with_tag(90, |w| w.write_message(m))? },
+            opentelemetry::proto::metrics::v1::mod_Metric::OneOfdata::None => {},
+    }        self.unknown_fields.write(w)?;
+        Ok(())
+    }
+}
+
+pub mod mod_Metric {
+
+use super::*;
+
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum OneOfdata<'a> {
+    gauge(opentelemetry::proto::metrics::v1::Gauge<'a>),
+    sum(opentelemetry::proto::metrics::v1::Sum<'a>),
+    histogram(opentelemetry::proto::metrics::v1::Histogram<'a>),
+    exponential_histogram(opentelemetry::proto::metrics::v1::ExponentialHistogram<'a>),
+    summary(opentelemetry::proto::metrics::v1::Summary<'a>),
+    None,
+}
+
+impl<'a> Default for OneOfdata<'a> {
+    fn default() -> Self {
+        OneOfdata::None
+    }
+}
+
+}
+
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Debug, Default, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Gauge<'a> {
+    pub data_points: Vec<opentelemetry::proto::metrics::v1::NumberDataPoint<'a>>,
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "crate::UnknownFields::is_empty"))]
+    pub unknown_fields: crate::UnknownFields,
+}
+
+impl<'a> MessageRead<'a> for Gauge<'a> {
+    fn from_reader(r: &mut BytesReader, bytes: &'a [u8]) -> Result<Self> {
+        let mut msg = Self::default();
+        while !r.is_eof() {
+            match r.next_tag(bytes) {
+                Ok(10) => msg.data_points.push(r.read_message::<opentelemetry::proto::metrics::v1::NumberDataPoint>(bytes)?),
+                Ok(t) => { msg.unknown_fields.read(r, bytes, t)?; }
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(msg)
+    }
+}
+
+impl<'a> MessageWrite for Gauge<'a> {
+    fn get_size(&self) -> usize {
+        0
+        + self.unknown_fields.len()
+        + self.data_points.iter().map(|s| 1 + sizeof_len((s).get_size())).sum::<usize>()
+    }
+
+    fn write_message<W: WriterBackend>(&self, w: &mut Writer<W>) -> Result<()> {
+        for s in &self.data_points { w.write_with_tag(10, |w| w.write_message(s))?; }
+        self.unknown_fields.write(w)?;
+        Ok(())
+    }
+}
+
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Debug, Default, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Sum<'a> {
+    pub data_points: Vec<opentelemetry::proto::metrics::v1::NumberDataPoint<'a>>,
+    pub aggregation_temporality: opentelemetry::proto::metrics::v1::AggregationTemporality,
+    pub is_monotonic: bool,
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "crate::UnknownFields::is_empty"))]
+    pub unknown_fields: crate::UnknownFields,
+}
+
+impl<'a> MessageRead<'a> for Sum<'a> {
+    fn from_reader(r: &mut BytesReader, bytes: &'a [u8]) -> Result<Self> {
+        let mut msg = Self::default();
+        while !r.is_eof() {
+            match r.next_tag(bytes) {
+                Ok(10) => msg.data_points.push(r.read_message::<opentelemetry::proto::metrics::v1::NumberDataPoint>(bytes)?),
+                Ok(16) => msg.aggregation_temporality = r.read_enum(bytes)?,
+                Ok(24) => msg.is_monotonic = r.read_bool(bytes)?,
+                Ok(t) => { msg.unknown_fields.read(r, bytes, t)?; }
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(msg)
+    }
+}
+
+impl<'a> MessageWrite for Sum<'a> {
+    fn get_size(&self) -> usize {
+        0
+        + self.unknown_fields.len()
+        + self.data_points.iter().map(|s| 1 + sizeof_len((s).get_size())).sum::<usize>()
+        + if self.aggregation_temporality == opentelemetry::proto::metrics::v1::AggregationTemporality::AGGREGATION_TEMPORALITY_UNSPECIFIED { 0 } else { 1 + sizeof_varint(*(&self.aggregation_temporality) as u64) }
+        + if self.is_monotonic == false { 0 } else { 1 + sizeof_varint(*(&self.is_monotonic) as u64) }
+    }
+
+    fn write_message<W: WriterBackend>(&self, w: &mut Writer<W>) -> Result<()> {
+        for s in &self.data_points { w.write_with_tag(10, |w| w.write_message(s))?; }
+        if self.aggregation_temporality != opentelemetry::proto::metrics::v1::AggregationTemporality::AGGREGATION_TEMPORALITY_UNSPECIFIED { w.write_with_tag(16, |w| w.write_enum(*&self.aggregation_temporality as i32))?; }
+        if self.is_monotonic != false { w.write_with_tag(24, |w| w.write_bool(*&self.is_monotonic))?; }
+        self.unknown_fields.write(w)?;
+        Ok(())
+    }
+}
+
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Debug, Default, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Histogram<'a> {
+    pub data_points: Vec<opentelemetry::proto::metrics::v1::HistogramDataPoint<'a>>,
+    pub aggregation_temporality: opentelemetry::proto::metrics::v1::AggregationTemporality,
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "crate::UnknownFields::is_empty"))]
+    pub unknown_fields: crate::UnknownFields,
+}
+
+impl<'a> MessageRead<'a> for Histogram<'a> {
+    fn from_reader(r: &mut BytesReader, bytes: &'a [u8]) -> Result<Self> {
+        let mut msg = Self::default();
+        while !r.is_eof() {
+            match r.next_tag(bytes) {
+                Ok(10) => msg.data_points.push(r.read_message::<opentelemetry::proto::metrics::v1::HistogramDataPoint>(bytes)?),
+                Ok(16) => msg.aggregation_temporality = r.read_enum(bytes)?,
+                Ok(t) => { msg.unknown_fields.read(r, bytes, t)?; }
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(msg)
+    }
+}
+
+impl<'a> MessageWrite for Histogram<'a> {
+    fn get_size(&self) -> usize {
+        0
+        + self.unknown_fields.len()
+        + self.data_points.iter().map(|s| 1 + sizeof_len((s).get_size())).sum::<usize>()
+        + if self.aggregation_temporality == opentelemetry::proto::metrics::v1::AggregationTemporality::AGGREGATION_TEMPORALITY_UNSPECIFIED { 0 } else { 1 + sizeof_varint(*(&self.aggregation_temporality) as u64) }
+    }
+
+    fn write_message<W: WriterBackend>(&self, w: &mut Writer<W>) -> Result<()> {
+        for s in &self.data_points { w.write_with_tag(10, |w| w.write_message(s))?; }
+        if self.aggregation_temporality != opentelemetry::proto::metrics::v1::AggregationTemporality::AGGREGATION_TEMPORALITY_UNSPECIFIED { w.write_with_tag(16, |w| w.write_enum(*&self.aggregation_temporality as i32))?; }
+        self.unknown_fields.write(w)?;
+        Ok(())
+    }
+}
+
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Debug, Default, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ExponentialHistogram<'a> {
+    pub data_points: Vec<opentelemetry::proto::metrics::v1::ExponentialHistogramDataPoint<'a>>,
+    pub aggregation_temporality: opentelemetry::proto::metrics::v1::AggregationTemporality,
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "crate::UnknownFields::is_empty"))]
+    pub unknown_fields: crate::UnknownFields,
+}
+
+impl<'a> MessageRead<'a> for ExponentialHistogram<'a> {
+    fn from_reader(r: &mut BytesReader, bytes: &'a [u8]) -> Result<Self> {
+        let mut msg = Self::default();
+        while !r.is_eof() {
+            match r.next_tag(bytes) {
+                Ok(10) => msg.data_points.push(r.read_message::<opentelemetry::proto::metrics::v1::ExponentialHistogramDataPoint>(bytes)?),
+                Ok(16) => msg.aggregation_temporality = r.read_enum(bytes)?,
+                Ok(t) => { msg.unknown_fields.read(r, bytes, t)?; }
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(msg)
+    }
+}
+
+impl<'a> MessageWrite for ExponentialHistogram<'a> {
+    fn get_size(&self) -> usize {
+        0
+        + self.unknown_fields.len()
+        + self.data_points.iter().map(|s| 1 + sizeof_len((s).get_size())).sum::<usize>()
+        + if self.aggregation_temporality == opentelemetry::proto::metrics::v1::AggregationTemporality::AGGREGATION_TEMPORALITY_UNSPECIFIED { 0 } else { 1 + sizeof_varint(*(&self.aggregation_temporality) as u64) }
+    }
+
+    fn write_message<W: WriterBackend>(&self, w: &mut Writer<W>) -> Result<()> {
+        for s in &self.data_points { w.write_with_tag(10, |w| w.write_message(s))?; }
+        if self.aggregation_temporality != opentelemetry::proto::metrics::v1::AggregationTemporality::AGGREGATION_TEMPORALITY_UNSPECIFIED { w.write_with_tag(16, |w| w.write_enum(*&self.aggregation_temporality as i32))?; }
+        self.unknown_fields.write(w)?;
+        Ok(())
+    }
+}
+
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Debug, Default, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Summary<'a> {
+    pub data_points: Vec<opentelemetry::proto::metrics::v1::SummaryDataPoint<'a>>,
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "crate::UnknownFields::is_empty"))]
+    pub unknown_fields: crate::UnknownFields,
+}
+
+impl<'a> MessageRead<'a> for Summary<'a> {
+    fn from_reader(r: &mut BytesReader, bytes: &'a [u8]) -> Result<Self> {
+        let mut msg = Self::default();
+        while !r.is_eof() {
+            match r.next_tag(bytes) {
+                Ok(10) => msg.data_points.push(r.read_message::<opentelemetry::proto::metrics::v1::SummaryDataPoint>(bytes)?),
+                Ok(t) => { msg.unknown_fields.read(r, bytes, t)?; }
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(msg)
+    }
+}
+
+impl<'a> MessageWrite for Summary<'a> {
+    fn get_size(&self) -> usize {
+        0
+        + self.unknown_fields.len()
+        + self.data_points.iter().map(|s| 1 + sizeof_len((s).get_size())).sum::<usize>()
+    }
+
+    fn write_message<W: WriterBackend>(&self, w: &mut Writer<W>) -> Result<()> {
+        for s in &self.data_points { w.write_with_tag(10, |w| w.write_message(s))?; }
+        self.unknown_fields.write(w)?;
+        Ok(())
+    }
+}
+
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Debug, Default, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NumberDataPoint<'a> {
+    pub attributes: Vec<opentelemetry::proto::common::v1::KeyValue<'a>>,
+    pub start_time_unix_nano: u64,
+    pub time_unix_nano: u64,
+    pub exemplars: Vec<opentelemetry::proto::metrics::v1::Exemplar<'a>>,
+    pub flags: u32,
+    pub value: opentelemetry::proto::metrics::v1::mod_NumberDataPoint::OneOfvalue,
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "crate::UnknownFields::is_empty"))]
+    pub unknown_fields: crate::UnknownFields,
+}
+
+impl<'a> MessageRead<'a> for NumberDataPoint<'a> {
+    fn from_reader(r: &mut BytesReader, bytes: &'a [u8]) -> Result<Self> {
+        let mut msg = Self::default();
+        while !r.is_eof() {
+            match r.next_tag(bytes) {
+                Ok(58) => msg.attributes.push(r.read_message::<opentelemetry::proto::common::v1::KeyValue>(bytes)?),
+                Ok(17) => msg.start_time_unix_nano = r.read_fixed64(bytes)?,
+                Ok(25) => msg.time_unix_nano = r.read_fixed64(bytes)?,
+                Ok(42) => msg.exemplars.push(r.read_message::<opentelemetry::proto::metrics::v1::Exemplar>(bytes)?),
+                Ok(64) => msg.flags = r.read_uint32(bytes)?,
+                Ok(33) => msg.value = opentelemetry::proto::metrics::v1::mod_NumberDataPoint::OneOfvalue::as_double(r.read_double(bytes)?),
+                Ok(49) => msg.value = opentelemetry::proto::metrics::v1::mod_NumberDataPoint::OneOfvalue::as_int(r.read_sfixed64(bytes)?),
+                Ok(t) => { msg.unknown_fields.read(r, bytes, t)?; }
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(msg)
+    }
+}
+
+impl<'a> MessageWrite for NumberDataPoint<'a> {
+    fn get_size(&self) -> usize {
+        0
+        + self.unknown_fields.len()
+        + self.attributes.iter().map(|s| 1 + sizeof_len((s).get_size())).sum::<usize>()
+        + if self.start_time_unix_nano == 0u64 { 0 } else { 1 + 8 }
+        + if self.time_unix_nano == 0u64 { 0 } else { 1 + 8 }
+        + self.exemplars.iter().map(|s| 1 + sizeof_len((s).get_size())).sum::<usize>()
+        + if self.flags == 0u32 { 0 } else { 1 + sizeof_varint(*(&self.flags) as u64) }
+        + match self.value {
+            opentelemetry::proto::metrics::v1::mod_NumberDataPoint::OneOfvalue::as_double(_) => 1 + 8,
+            opentelemetry::proto::metrics::v1::mod_NumberDataPoint::OneOfvalue::as_int(_) => 1 + 8,
+            opentelemetry::proto::metrics::v1::mod_NumberDataPoint::OneOfvalue::None => 0,
+    }    }
+
+    fn write_message<W: WriterBackend>(&self, w: &mut Writer<W>) -> Result<()> {
+        for s in &self.attributes { w.write_with_tag(58, |w| w.write_message(s))?; }
+        if self.start_time_unix_nano != 0u64 { w.write_with_tag(17, |w| w.write_fixed64(*&self.start_time_unix_nano))?; }
+        if self.time_unix_nano != 0u64 { w.write_with_tag(25, |w| w.write_fixed64(*&self.time_unix_nano))?; }
+        for s in &self.exemplars { w.write_with_tag(42, |w| w.write_message(s))?; }
+        if self.flags != 0u32 { w.write_with_tag(64, |w| w.write_uint32(*&self.flags))?; }
+        match self.value {            opentelemetry::proto::metrics::v1::mod_NumberDataPoint::OneOfvalue::as_double(ref m) => { w.write_with_tag(33, |w| w.write_double(*m))? },
+            opentelemetry::proto::metrics::v1::mod_NumberDataPoint::OneOfvalue::as_int(ref m) => { w.write_with_tag(49, |w| w.write_sfixed64(*m))? },
+            opentelemetry::proto::metrics::v1::mod_NumberDataPoint::OneOfvalue::None => {},
+    }        self.unknown_fields.write(w)?;
+        Ok(())
+    }
+}
+
+pub mod mod_NumberDataPoint {
+
+use super::*;
+
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum OneOfvalue {
+    as_double(f64),
+    as_int(i64),
+    None,
+}
+
+impl Default for OneOfvalue {
+    fn default() -> Self {
+        OneOfvalue::None
+    }
+}
+
+}
+
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Debug, Default, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HistogramDataPoint<'a> {
+    pub attributes: Vec<opentelemetry::proto::common::v1::KeyValue<'a>>,
+    pub start_time_unix_nano: u64,
+    pub time_unix_nano: u64,
+    pub count: u64,
+    pub sum: f64,
+    pub bucket_counts: Cow<'a, [u64]>,
+    pub explicit_bounds: Cow<'a, [f64]>,
+    pub exemplars: Vec<opentelemetry::proto::metrics::v1::Exemplar<'a>>,
+    pub flags: u32,
+    pub min: f64,
+    pub max: f64,
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "crate::UnknownFields::is_empty"))]
+    pub unknown_fields: crate::UnknownFields,
+}
+
+impl<'a> MessageRead<'a> for HistogramDataPoint<'a> {
+    fn from_reader(r: &mut BytesReader, bytes: &'a [u8]) -> Result<Self> {
+        let mut msg = Self::default();
+        while !r.is_eof() {
+            match r.next_tag(bytes) {
+                Ok(74) => msg.attributes.push(r.read_message::<opentelemetry::proto::common::v1::KeyValue>(bytes)?),
+                Ok(17) => msg.start_time_unix_nano = r.read_fixed64(bytes)?,
+                Ok(25) => msg.time_unix_nano = r.read_fixed64(bytes)?,
+                Ok(33) => msg.count = r.read_fixed64(bytes)?,
+                Ok(41) => msg.sum = r.read_double(bytes)?,
+                Ok(50) => msg.bucket_counts = crate::opentelemetry_types::read_packed_fixed(r, bytes)?,
+                Ok(58) => msg.explicit_bounds = crate::opentelemetry_types::read_packed_fixed(r, bytes)?,
+                Ok(66) => msg.exemplars.push(r.read_message::<opentelemetry::proto::metrics::v1::Exemplar>(bytes)?),
+                Ok(80) => msg.flags = r.read_uint32(bytes)?,
+                Ok(89) => msg.min = r.read_double(bytes)?,
+                Ok(97) => msg.max = r.read_double(bytes)?,
+                Ok(t) => { msg.unknown_fields.read(r, bytes, t)?; }
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(msg)
+    }
+}
+
+impl<'a> MessageWrite for HistogramDataPoint<'a> {
+    fn get_size(&self) -> usize {
+        0
+        + self.unknown_fields.len()
+        + self.attributes.iter().map(|s| 1 + sizeof_len((s).get_size())).sum::<usize>()
+        + if self.start_time_unix_nano == 0u64 { 0 } else { 1 + 8 }
+        + if self.time_unix_nano == 0u64 { 0 } else { 1 + 8 }
+        + if self.count == 0u64 { 0 } else { 1 + 8 }
+        + if self.sum == 0f64 { 0 } else { 1 + 8 }
+        + if self.bucket_counts.is_empty() { 0 } else { 1 + sizeof_len(self.bucket_counts.len() * 8) }
+        + if self.explicit_bounds.is_empty() { 0 } else { 1 + sizeof_len(self.explicit_bounds.len() * 8) }
+        + self.exemplars.iter().map(|s| 1 + sizeof_len((s).get_size())).sum::<usize>()
+        + if self.flags == 0u32 { 0 } else { 1 + sizeof_varint(*(&self.flags) as u64) }
+        + if self.min == 0f64 { 0 } else { 1 + 8 }
+        + if self.max == 0f64 { 0 } else { 1 + 8 }
+    }
+
+    fn write_message<W: WriterBackend>(&self, w: &mut Writer<W>) -> Result<()> {
+        for s in &self.attributes { w.write_with_tag(74, |w| w.write_message(s))?; }
+        if self.start_time_unix_nano != 0u64 { w.write_with_tag(17, |w| w.write_fixed64(*&self.start_time_unix_nano))?; }
+        if self.time_unix_nano != 0u64 { w.write_with_tag(25, |w| w.write_fixed64(*&self.time_unix_nano))?; }
+        if self.count != 0u64 { w.write_with_tag(33, |w| w.write_fixed64(*&self.count))?; }
+        if self.sum != 0f64 { w.write_with_tag(41, |w| w.write_double(*&self.sum))?; }
+        w.write_packed_fixed_with_tag(50, &self.bucket_counts)?;
+        w.write_packed_fixed_with_tag(58, &self.explicit_bounds)?;
+        for s in &self.exemplars { w.write_with_tag(66, |w| w.write_message(s))?; }
+        if self.flags != 0u32 { w.write_with_tag(80, |w| w.write_uint32(*&self.flags))?; }
+        if self.min != 0f64 { w.write_with_tag(89, |w| w.write_double(*&self.min))?; }
+        if self.max != 0f64 { w.write_with_tag(97, |w| w.write_double(*&self.max))?; }
+        self.unknown_fields.write(w)?;
+        Ok(())
+    }
+}
+
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Debug, Default, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ExponentialHistogramDataPoint<'a> {
+    pub attributes: Vec<opentelemetry::proto::common::v1::KeyValue<'a>>,
+    pub start_time_unix_nano: u64,
+    pub time_unix_nano: u64,
+    pub count: u64,
+    pub sum: f64,
+    pub scale: i32,
+    pub zero_count: u64,
+    pub positive: Option<opentelemetry::proto::metrics::v1::mod_ExponentialHistogramDataPoint::Buckets>,
+    pub negative: Option<opentelemetry::proto::metrics::v1::mod_ExponentialHistogramDataPoint::Buckets>,
+    pub flags: u32,
+    pub exemplars: Vec<opentelemetry::proto::metrics::v1::Exemplar<'a>>,
+    pub min: f64,
+    pub max: f64,
+    pub zero_threshold: f64,
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "crate::UnknownFields::is_empty"))]
+    pub unknown_fields: crate::UnknownFields,
+}
+
+impl<'a> MessageRead<'a> for ExponentialHistogramDataPoint<'a> {
+    fn from_reader(r: &mut BytesReader, bytes: &'a [u8]) -> Result<Self> {
+        let mut msg = Self::default();
+        while !r.is_eof() {
+            match r.next_tag(bytes) {
+                Ok(10) => msg.attributes.push(r.read_message::<opentelemetry::proto::common::v1::KeyValue>(bytes)?),
+                Ok(17) => msg.start_time_unix_nano = r.read_fixed64(bytes)?,
+                Ok(25) => msg.time_unix_nano = r.read_fixed64(bytes)?,
+                Ok(33) => msg.count = r.read_fixed64(bytes)?,
+                Ok(41) => msg.sum = r.read_double(bytes)?,
+                Ok(48) => msg.scale = r.read_sint32(bytes)?,
+                Ok(57) => msg.zero_count = r.read_fixed64(bytes)?,
+                Ok(66) => msg.positive = Some(r.read_message::<opentelemetry::proto::metrics::v1::mod_ExponentialHistogramDataPoint::Buckets>(bytes)?),
+                Ok(74) => msg.negative = Some(r.read_message::<opentelemetry::proto::metrics::v1::mod_ExponentialHistogramDataPoint::Buckets>(bytes)?),
+                Ok(80) => msg.flags = r.read_uint32(bytes)?,
+                Ok(90) => msg.exemplars.push(r.read_message::<opentelemetry::proto::metrics::v1::Exemplar>(bytes)?),
+                Ok(97) => msg.min = r.read_double(bytes)?,
+                Ok(105) => msg.max = r.read_double(bytes)?,
+                Ok(113) => msg.zero_threshold = r.read_double(bytes)?,
+                Ok(t) => { msg.unknown_fields.read(r, bytes, t)?; }
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(msg)
+    }
+}
+
+impl<'a> MessageWrite for ExponentialHistogramDataPoint<'a> {
+    fn get_size(&self) -> usize {
+        0
+        + self.unknown_fields.len()
+        + self.attributes.iter().map(|s| 1 + sizeof_len((s).get_size())).sum::<usize>()
+        + if self.start_time_unix_nano == 0u64 { 0 } else { 1 + 8 }
+        + if self.time_unix_nano == 0u64 { 0 } else { 1 + 8 }
+        + if self.count == 0u64 { 0 } else { 1 + 8 }
+        + if self.sum == 0f64 { 0 } else { 1 + 8 }
+        + if self.scale == 0i32 { 0 } else { 1 + sizeof_sint32(*(&self.scale)) }
+        + if self.zero_count == 0u64 { 0 } else { 1 + 8 }
+        + self.positive.as_ref().map_or(0, |m| 1 + sizeof_len((m).get_size()))
+        + self.negative.as_ref().map_or(0, |m| 1 + sizeof_len((m).get_size()))
+        + if self.flags == 0u32 { 0 } else { 1 + sizeof_varint(*(&self.flags) as u64) }
+        + self.exemplars.iter().map(|s| 1 + sizeof_len((s).get_size())).sum::<usize>()
+        + if self.min == 0f64 { 0 } else { 1 + 8 }
+        + if self.max == 0f64 { 0 } else { 1 + 8 }
+        + if self.zero_threshold == 0f64 { 0 } else { 1 + 8 }
+    }
+
+    fn write_message<W: WriterBackend>(&self, w: &mut Writer<W>) -> Result<()> {
+        for s in &self.attributes { w.write_with_tag(10, |w| w.write_message(s))?; }
+        if self.start_time_unix_nano != 0u64 { w.write_with_tag(17, |w| w.write_fixed64(*&self.start_time_unix_nano))?; }
+        if self.time_unix_nano != 0u64 { w.write_with_tag(25, |w| w.write_fixed64(*&self.time_unix_nano))?; }
+        if self.count != 0u64 { w.write_with_tag(33, |w| w.write_fixed64(*&self.count))?; }
+        if self.sum != 0f64 { w.write_with_tag(41, |w| w.write_double(*&self.sum))?; }
+        if self.scale != 0i32 { w.write_with_tag(48, |w| w.write_sint32(*&self.scale))?; }
+        if self.zero_count != 0u64 { w.write_with_tag(57, |w| w.write_fixed64(*&self.zero_count))?; }
+        if let Some(ref s) = self.positive { w.write_with_tag(66, |w| w.write_message(s))?; }
+        if let Some(ref s) = self.negative { w.write_with_tag(74, |w| w.write_message(s))?; }
+        if self.flags != 0u32 { w.write_with_tag(80, |w| w.write_uint32(*&self.flags))?; }
+        for s in &self.exemplars { w.write_with_tag(90, |w| w.write_message(s))?; }
+        if self.min != 0f64 { w.write_with_tag(97, |w| w.write_double(*&self.min))?; }
+        if self.max != 0f64 { w.write_with_tag(105, |w| w.write_double(*&self.max))?; }
+        if self.zero_threshold != 0f64 { w.write_with_tag(113, |w| w.write_double(*&self.zero_threshold))?; }
+        self.unknown_fields.write(w)?;
+        Ok(())
+    }
+}
+
+pub mod mod_ExponentialHistogramDataPoint {
+
+use super::*;
+
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Debug, Default, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Buckets {
+    pub offset: i32,
+    pub bucket_counts: Vec<u64>,
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "crate::UnknownFields::is_empty"))]
+    pub unknown_fields: crate::UnknownFields,
+}
+
+impl<'a> MessageRead<'a> for Buckets {
+    fn from_reader(r: &mut BytesReader, bytes: &'a [u8]) -> Result<Self> {
+        let mut msg = Self::default();
+        while !r.is_eof() {
+            match r.next_tag(bytes) {
+                Ok(8) => msg.offset = r.read_sint32(bytes)?,
+                Ok(16) => msg.bucket_counts.push(r.read_uint64(bytes)?),
+                Ok(t) => { msg.unknown_fields.read(r, bytes, t)?; }
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(msg)
+    }
+}
+
+impl MessageWrite for Buckets {
+    fn get_size(&self) -> usize {
+        0
+        + self.unknown_fields.len()
+        + if self.offset == 0i32 { 0 } else { 1 + sizeof_sint32(*(&self.offset)) }
+        + self.bucket_counts.iter().map(|s| 1 + sizeof_varint(*(s) as u64)).sum::<usize>()
+    }
+
+    fn write_message<W: WriterBackend>(&self, w: &mut Writer<W>) -> Result<()> {
+        if self.offset != 0i32 { w.write_with_tag(8, |w| w.write_sint32(*&self.offset))?; }
+        for s in &self.bucket_counts { w.write_with_tag(16, |w| w.write_uint64(*s))?; }
+        self.unknown_fields.write(w)?;
+        Ok(())
+    }
+}
+
+}
+
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Debug, Default, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SummaryDataPoint<'a> {
+    pub attributes: Vec<opentelemetry::proto::common::v1::KeyValue<'a>>,
+    pub start_time_unix_nano: u64,
+    pub time_unix_nano: u64,
+    pub count: u64,
+    pub sum: f64,
+    pub quantile_values: Vec<opentelemetry::proto::metrics::v1::mod_SummaryDataPoint::ValueAtQuantile>,
+    pub flags: u32,
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "crate::UnknownFields::is_empty"))]
+    pub unknown_fields: crate::UnknownFields,
+}
+
+impl<'a> MessageRead<'a> for SummaryDataPoint<'a> {
+    fn from_reader(r: &mut BytesReader, bytes: &'a [u8]) -> Result<Self> {
+        let mut msg = Self::default();
+        while !r.is_eof() {
+            match r.next_tag(bytes) {
+                Ok(58) => msg.attributes.push(r.read_message::<opentelemetry::proto::common::v1::KeyValue>(bytes)?),
+                Ok(17) => msg.start_time_unix_nano = r.read_fixed64(bytes)?,
+                Ok(25) => msg.time_unix_nano = r.read_fixed64(bytes)?,
+                Ok(33) => msg.count = r.read_fixed64(bytes)?,
+                Ok(41) => msg.sum = r.read_double(bytes)?,
+                Ok(50) => msg.quantile_values.push(r.read_message::<opentelemetry::proto::metrics::v1::mod_SummaryDataPoint::ValueAtQuantile>(bytes)?),
+                Ok(64) => msg.flags = r.read_uint32(bytes)?,
+                Ok(t) => { msg.unknown_fields.read(r, bytes, t)?; }
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(msg)
+    }
+}
+
+impl<'a> MessageWrite for SummaryDataPoint<'a> {
+    fn get_size(&self) -> usize {
+        0
+        + self.unknown_fields.len()
+        + self.attributes.iter().map(|s| 1 + sizeof_len((s).get_size())).sum::<usize>()
+        + if self.start_time_unix_nano == 0u64 { 0 } else { 1 + 8 }
+        + if self.time_unix_nano == 0u64 { 0 } else { 1 + 8 }
+        + if self.count == 0u64 { 0 } else { 1 + 8 }
+        + if self.sum == 0f64 { 0 } else { 1 + 8 }
+        + self.quantile_values.iter().map(|s| 1 + sizeof_len((s).get_size())).sum::<usize>()
+        + if self.flags == 0u32 { 0 } else { 1 + sizeof_varint(*(&self.flags) as u64) }
+    }
+
+    fn write_message<W: WriterBackend>(&self, w: &mut Writer<W>) -> Result<()> {
+        for s in &self.attributes { w.write_with_tag(58, |w| w.write_message(s))?; }
+        if self.start_time_unix_nano != 0u64 { w.write_with_tag(17, |w| w.write_fixed64(*&self.start_time_unix_nano))?; }
+        if self.time_unix_nano != 0u64 { w.write_with_tag(25, |w| w.write_fixed64(*&self.time_unix_nano))?; }
+        if self.count != 0u64 { w.write_with_tag(33, |w| w.write_fixed64(*&self.count))?; }
+        if self.sum != 0f64 { w.write_with_tag(41, |w| w.write_double(*&self.sum))?; }
+        for s in &self.quantile_values { w.write_with_tag(50, |w| w.write_message(s))?; }
+        if self.flags != 0u32 { w.write_with_tag(64, |w| w.write_uint32(*&self.flags))?; }
+        self.unknown_fields.write(w)?;
+        Ok(())
+    }
+}
+
+pub mod mod_SummaryDataPoint {
+
+use super::*;
+
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Debug, Default, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ValueAtQuantile {
+    pub quantile: f64,
+    pub value: f64,
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "crate::UnknownFields::is_empty"))]
+    pub unknown_fields: crate::UnknownFields,
+}
+
+impl<'a> MessageRead<'a> for ValueAtQuantile {
+    fn from_reader(r: &mut BytesReader, bytes: &'a [u8]) -> Result<Self> {
+        let mut msg = Self::default();
+        while !r.is_eof() {
+            match r.next_tag(bytes) {
+                Ok(9) => msg.quantile = r.read_double(bytes)?,
+                Ok(17) => msg.value = r.read_double(bytes)?,
+                Ok(t) => { msg.unknown_fields.read(r, bytes, t)?; }
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(msg)
+    }
+}
+
+impl MessageWrite for ValueAtQuantile {
+    fn get_size(&self) -> usize {
+        0
+        + self.unknown_fields.len()
+        + if self.quantile == 0f64 { 0 } else { 1 + 8 }
+        + if self.value == 0f64 { 0 } else { 1 + 8 }
+    }
+
+    fn write_message<W: WriterBackend>(&self, w: &mut Writer<W>) -> Result<()> {
+        if self.quantile != 0f64 { w.write_with_tag(9, |w| w.write_double(*&self.quantile))?; }
+        if self.value != 0f64 { w.write_with_tag(17, |w| w.write_double(*&self.value))?; }
+        self.unknown_fields.write(w)?;
+        Ok(())
+    }
+}
+
+}
+
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Debug, Default, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Exemplar<'a> {
+    pub filtered_attributes: Vec<opentelemetry::proto::common::v1::KeyValue<'a>>,
+    pub time_unix_nano: u64,
+    pub span_id: Cow<'a, [u8]>,
+    pub trace_id: Cow<'a, [u8]>,
+    pub value: opentelemetry::proto::metrics::v1::mod_Exemplar::OneOfvalue,
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "crate::UnknownFields::is_empty"))]
+    pub unknown_fields: crate::UnknownFields,
+}
+
+impl<'a> MessageRead<'a> for Exemplar<'a> {
+    fn from_reader(r: &mut BytesReader, bytes: &'a [u8]) -> Result<Self> {
+        let mut msg = Self::default();
+        while !r.is_eof() {
+            match r.next_tag(bytes) {
+                Ok(58) => msg.filtered_attributes.push(r.read_message::<opentelemetry::proto::common::v1::KeyValue>(bytes)?),
+                Ok(17) => msg.time_unix_nano = r.read_fixed64(bytes)?,
+                Ok(34) => msg.span_id = r.read_bytes(bytes).map(Cow::Borrowed)?,
+                Ok(42) => msg.trace_id = r.read_bytes(bytes).map(Cow::Borrowed)?,
+                Ok(25) => msg.value = opentelemetry::proto::metrics::v1::mod_Exemplar::OneOfvalue::as_double(r.read_double(bytes)?),
+                Ok(49) => msg.value = opentelemetry::proto::metrics::v1::mod_Exemplar::OneOfvalue::as_int(r.read_sfixed64(bytes)?),
+                Ok(t) => { msg.unknown_fields.read(r, bytes, t)?; }
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(msg)
+    }
+}
+
+impl<'a> MessageWrite for Exemplar<'a> {
+    fn get_size(&self) -> usize {
+        0
+        + self.unknown_fields.len()
+        + self.filtered_attributes.iter().map(|s| 1 + sizeof_len((s).get_size())).sum::<usize>()
+        + if self.time_unix_nano == 0u64 { 0 } else { 1 + 8 }
+        + if self.span_id == Cow::Borrowed(b"") { 0 } else { 1 + sizeof_len((&self.span_id).len()) }
+        + if self.trace_id == Cow::Borrowed(b"") { 0 } else { 1 + sizeof_len((&self.trace_id).len()) }
+        + match self.value {
+            opentelemetry::proto::metrics::v1::mod_Exemplar::OneOfvalue::as_double(_) => 1 + 8,
+            opentelemetry::proto::metrics::v1::mod_Exemplar::OneOfvalue::as_int(_) => 1 + 8,
+            opentelemetry::proto::metrics::v1::mod_Exemplar::OneOfvalue::None => 0,
+    }    }
+
+    fn write_message<W: WriterBackend>(&self, w: &mut Writer<W>) -> Result<()> {
+        for s in &self.filtered_attributes { w.write_with_tag(58, |w| w.write_message(s))?; }
+        if self.time_unix_nano != 0u64 { w.write_with_tag(17, |w| w.write_fixed64(*&self.time_unix_nano))?; }
+        if self.span_id != Cow::Borrowed(b"") { w.write_with_tag(34, |w| w.write_bytes(&**&self.span_id))?; }
+        if self.trace_id != Cow::Borrowed(b"") { w.write_with_tag(42, |w| w.write_bytes(&**&self.trace_id))?; }
+        match self.value {            opentelemetry::proto::metrics::v1::mod_Exemplar::OneOfvalue::as_double(ref m) => { w.write_with_tag(25, |w| w.write_double(*m))? },
+            opentelemetry::proto::metrics::v1::mod_Exemplar::OneOfvalue::as_int(ref m) => { w.write_with_tag(49, |w| w.write_sfixed64(*m))? },
+            opentelemetry::proto::metrics::v1::mod_Exemplar::OneOfvalue::None => {},
+    }        self.unknown_fields.write(w)?;
+        Ok(())
+    }
+}
+
+pub mod mod_Exemplar {
+
+use super::*;
+
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum OneOfvalue {
+    as_double(f64),
+    as_int(i64),
+    None,
+}
+
+impl Default for OneOfvalue {
+    fn default() -> Self {
+        OneOfvalue::None
+    }
+}
+
+}
+
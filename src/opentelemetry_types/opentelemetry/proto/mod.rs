@@ -0,0 +1,6 @@
+// Automatically generated mod.rs
+pub mod common;
+pub mod logs;
+pub mod metrics;
+pub mod profiles;
+pub mod trace;
@@ -0,0 +1,811 @@
+// Automatically generated rust module for 'profiles.proto' file
+
+#![allow(non_snake_case)]
+#![allow(non_upper_case_globals)]
+#![allow(non_camel_case_types)]
+#![allow(unused_imports)]
+#![allow(unknown_lints)]
+#![allow(clippy::all)]
+#![cfg_attr(rustfmt, rustfmt_skip)]
+
+
+use std::borrow::Cow;
+use quick_protobuf::{MessageInfo, MessageRead, MessageWrite, BytesReader, Writer, WriterBackend, Result};
+use quick_protobuf::sizeofs::*;
+use super::super::super::super::*;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AggregationTemporality {
+    AGGREGATION_TEMPORALITY_UNSPECIFIED = 0,
+    AGGREGATION_TEMPORALITY_DELTA = 1,
+    AGGREGATION_TEMPORALITY_CUMULATIVE = 2,
+}
+
+impl Default for AggregationTemporality {
+    fn default() -> Self {
+        AggregationTemporality::AGGREGATION_TEMPORALITY_UNSPECIFIED
+    }
+}
+
+impl From<i32> for AggregationTemporality {
+    fn from(i: i32) -> Self {
+        match i {
+            0 => AggregationTemporality::AGGREGATION_TEMPORALITY_UNSPECIFIED,
+            1 => AggregationTemporality::AGGREGATION_TEMPORALITY_DELTA,
+            2 => AggregationTemporality::AGGREGATION_TEMPORALITY_CUMULATIVE,
+            _ => Self::default(),
+        }
+    }
+}
+
+impl<'a> From<&'a str> for AggregationTemporality {
+    fn from(s: &'a str) -> Self {
+        match s {
+            "AGGREGATION_TEMPORALITY_UNSPECIFIED" => AggregationTemporality::AGGREGATION_TEMPORALITY_UNSPECIFIED,
+            "AGGREGATION_TEMPORALITY_DELTA" => AggregationTemporality::AGGREGATION_TEMPORALITY_DELTA,
+            "AGGREGATION_TEMPORALITY_CUMULATIVE" => AggregationTemporality::AGGREGATION_TEMPORALITY_CUMULATIVE,
+            _ => Self::default(),
+        }
+    }
+}
+
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Debug, Default, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ExportProfilesServiceRequest<'a> {
+    pub resource_profiles: Vec<opentelemetry::proto::profiles::v1development::ResourceProfiles<'a>>,
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "crate::UnknownFields::is_empty"))]
+    pub unknown_fields: crate::UnknownFields,
+}
+
+impl<'a> MessageRead<'a> for ExportProfilesServiceRequest<'a> {
+    fn from_reader(r: &mut BytesReader, bytes: &'a [u8]) -> Result<Self> {
+        let mut msg = Self::default();
+        while !r.is_eof() {
+            match r.next_tag(bytes) {
+                Ok(10) => msg.resource_profiles.push(r.read_message::<opentelemetry::proto::profiles::v1development::ResourceProfiles>(bytes)?),
+                Ok(t) => { msg.unknown_fields.read(r, bytes, t)?; }
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(msg)
+    }
+}
+
+impl<'a> MessageWrite for ExportProfilesServiceRequest<'a> {
+    fn get_size(&self) -> usize {
+        0
+        + self.unknown_fields.len()
+        + self.resource_profiles.iter().map(|s| 1 + sizeof_len((s).get_size())).sum::<usize>()
+    }
+
+    fn write_message<W: WriterBackend>(&self, w: &mut Writer<W>) -> Result<()> {
+        for s in &self.resource_profiles { w.write_with_tag(10, |w| w.write_message(s))?; }
+        self.unknown_fields.write(w)?;
+        Ok(())
+    }
+}
+
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Debug, Default, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ExportProfilesServiceResponse<'a> {
+    pub partial_success: Option<opentelemetry::proto::profiles::v1development::ExportProfilesPartialSuccess<'a>>,
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "crate::UnknownFields::is_empty"))]
+    pub unknown_fields: crate::UnknownFields,
+}
+
+impl<'a> MessageRead<'a> for ExportProfilesServiceResponse<'a> {
+    fn from_reader(r: &mut BytesReader, bytes: &'a [u8]) -> Result<Self> {
+        let mut msg = Self::default();
+        while !r.is_eof() {
+            match r.next_tag(bytes) {
+                Ok(10) => msg.partial_success = Some(r.read_message::<opentelemetry::proto::profiles::v1development::ExportProfilesPartialSuccess>(bytes)?),
+                Ok(t) => { msg.unknown_fields.read(r, bytes, t)?; }
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(msg)
+    }
+}
+
+impl<'a> MessageWrite for ExportProfilesServiceResponse<'a> {
+    fn get_size(&self) -> usize {
+        0
+        + self.unknown_fields.len()
+        + self.partial_success.as_ref().map_or(0, |m| 1 + sizeof_len((m).get_size()))
+    }
+
+    fn write_message<W: WriterBackend>(&self, w: &mut Writer<W>) -> Result<()> {
+        if let Some(ref s) = self.partial_success { w.write_with_tag(10, |w| w.write_message(s))?; }
+        self.unknown_fields.write(w)?;
+        Ok(())
+    }
+}
+
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Debug, Default, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ExportProfilesPartialSuccess<'a> {
+    pub rejected_profiles: i64,
+    pub error_message: Cow<'a, str>,
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "crate::UnknownFields::is_empty"))]
+    pub unknown_fields: crate::UnknownFields,
+}
+
+impl<'a> MessageRead<'a> for ExportProfilesPartialSuccess<'a> {
+    fn from_reader(r: &mut BytesReader, bytes: &'a [u8]) -> Result<Self> {
+        let mut msg = Self::default();
+        while !r.is_eof() {
+            match r.next_tag(bytes) {
+                Ok(8) => msg.rejected_profiles = r.read_int64(bytes)?,
+                Ok(18) => msg.error_message = r.read_string(bytes).map(Cow::Borrowed)?,
+                Ok(t) => { msg.unknown_fields.read(r, bytes, t)?; }
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(msg)
+    }
+}
+
+impl<'a> MessageWrite for ExportProfilesPartialSuccess<'a> {
+    fn get_size(&self) -> usize {
+        0
+        + self.unknown_fields.len()
+        + if self.rejected_profiles == 0i64 { 0 } else { 1 + sizeof_varint(*(&self.rejected_profiles) as u64) }
+        + if self.error_message == "" { 0 } else { 1 + sizeof_len((&self.error_message).len()) }
+    }
+
+    fn write_message<W: WriterBackend>(&self, w: &mut Writer<W>) -> Result<()> {
+        if self.rejected_profiles != 0i64 { w.write_with_tag(8, |w| w.write_int64(*&self.rejected_profiles))?; }
+        if self.error_message != "" { w.write_with_tag(18, |w| w.write_string(&**&self.error_message))?; }
+        self.unknown_fields.write(w)?;
+        Ok(())
+    }
+}
+
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Debug, Default, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ProfilesData<'a> {
+    pub resource_profiles: Vec<opentelemetry::proto::profiles::v1development::ResourceProfiles<'a>>,
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "crate::UnknownFields::is_empty"))]
+    pub unknown_fields: crate::UnknownFields,
+}
+
+impl<'a> MessageRead<'a> for ProfilesData<'a> {
+    fn from_reader(r: &mut BytesReader, bytes: &'a [u8]) -> Result<Self> {
+        let mut msg = Self::default();
+        while !r.is_eof() {
+            match r.next_tag(bytes) {
+                Ok(10) => msg.resource_profiles.push(r.read_message::<opentelemetry::proto::profiles::v1development::ResourceProfiles>(bytes)?),
+                Ok(t) => { msg.unknown_fields.read(r, bytes, t)?; }
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(msg)
+    }
+}
+
+impl<'a> MessageWrite for ProfilesData<'a> {
+    fn get_size(&self) -> usize {
+        0
+        + self.unknown_fields.len()
+        + self.resource_profiles.iter().map(|s| 1 + sizeof_len((s).get_size())).sum::<usize>()
+    }
+
+    fn write_message<W: WriterBackend>(&self, w: &mut Writer<W>) -> Result<()> {
+        for s in &self.resource_profiles { w.write_with_tag(10, |w| w.write_message(s))?; }
+        self.unknown_fields.write(w)?;
+        Ok(())
+    }
+}
+
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Debug, Default, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ResourceProfiles<'a> {
+    pub resource: Option<opentelemetry::proto::common::v1::Resource<'a>>,
+    pub scope_profiles: Vec<opentelemetry::proto::profiles::v1development::ScopeProfiles<'a>>,
+    pub schema_url: Cow<'a, str>,
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "crate::UnknownFields::is_empty"))]
+    pub unknown_fields: crate::UnknownFields,
+}
+
+impl<'a> MessageRead<'a> for ResourceProfiles<'a> {
+    fn from_reader(r: &mut BytesReader, bytes: &'a [u8]) -> Result<Self> {
+        let mut msg = Self::default();
+        while !r.is_eof() {
+            match r.next_tag(bytes) {
+                Ok(10) => msg.resource = Some(r.read_message::<opentelemetry::proto::common::v1::Resource>(bytes)?),
+                Ok(18) => msg.scope_profiles.push(r.read_message::<opentelemetry::proto::profiles::v1development::ScopeProfiles>(bytes)?),
+                Ok(26) => msg.schema_url = r.read_string(bytes).map(Cow::Borrowed)?,
+                Ok(t) => { msg.unknown_fields.read(r, bytes, t)?; }
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(msg)
+    }
+}
+
+impl<'a> MessageWrite for ResourceProfiles<'a> {
+    fn get_size(&self) -> usize {
+        0
+        + self.unknown_fields.len()
+        + self.resource.as_ref().map_or(0, |m| 1 + sizeof_len((m).get_size()))
+        + self.scope_profiles.iter().map(|s| 1 + sizeof_len((s).get_size())).sum::<usize>()
+        + if self.schema_url == "" { 0 } else { 1 + sizeof_len((&self.schema_url).len()) }
+    }
+
+    fn write_message<W: WriterBackend>(&self, w: &mut Writer<W>) -> Result<()> {
+        if let Some(ref s) = self.resource { w.write_with_tag(10, |w| w.write_message(s))?; }
+        for s in &self.scope_profiles { w.write_with_tag(18, |w| w.write_message(s))?; }
+        if self.schema_url != "" { w.write_with_tag(26, |w| w.write_string(&**&self.schema_url))?; }
+        self.unknown_fields.write(w)?;
+        Ok(())
+    }
+}
+
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Debug, Default, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ScopeProfiles<'a> {
+    pub scope: Option<opentelemetry::proto::common::v1::InstrumentationScope<'a>>,
+    pub profiles: Vec<opentelemetry::proto::profiles::v1development::Profile<'a>>,
+    pub schema_url: Cow<'a, str>,
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "crate::UnknownFields::is_empty"))]
+    pub unknown_fields: crate::UnknownFields,
+}
+
+impl<'a> MessageRead<'a> for ScopeProfiles<'a> {
+    fn from_reader(r: &mut BytesReader, bytes: &'a [u8]) -> Result<Self> {
+        let mut msg = Self::default();
+        while !r.is_eof() {
+            match r.next_tag(bytes) {
+                Ok(10) => msg.scope = Some(r.read_message::<opentelemetry::proto::common::v1::InstrumentationScope>(bytes)?),
+                Ok(18) => msg.profiles.push(r.read_message::<opentelemetry::proto::profiles::v1development::Profile>(bytes)?),
+                Ok(26) => msg.schema_url = r.read_string(bytes).map(Cow::Borrowed)?,
+                Ok(t) => { msg.unknown_fields.read(r, bytes, t)?; }
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(msg)
+    }
+}
+
+impl<'a> MessageWrite for ScopeProfiles<'a> {
+    fn get_size(&self) -> usize {
+        0
+        + self.unknown_fields.len()
+        + self.scope.as_ref().map_or(0, |m| 1 + sizeof_len((m).get_size()))
+        + self.profiles.iter().map(|s| 1 + sizeof_len((s).get_size())).sum::<usize>()
+        + if self.schema_url == "" { 0 } else { 1 + sizeof_len((&self.schema_url).len()) }
+    }
+
+    fn write_message<W: WriterBackend>(&self, w: &mut Writer<W>) -> Result<()> {
+        if let Some(ref s) = self.scope { w.write_with_tag(10, |w| w.write_message(s))?; }
+        for s in &self.profiles { w.write_with_tag(18, |w| w.write_message(s))?; }
+        if self.schema_url != "" { w.write_with_tag(26, |w| w.write_string(&**&self.schema_url))?; }
+        self.unknown_fields.write(w)?;
+        Ok(())
+    }
+}
+
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Debug, Default, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Profile<'a> {
+    pub sample_type: Vec<opentelemetry::proto::profiles::v1development::ValueType>,
+    pub sample: Vec<opentelemetry::proto::profiles::v1development::Sample>,
+    pub mapping_table: Vec<opentelemetry::proto::profiles::v1development::Mapping>,
+    pub location_table: Vec<opentelemetry::proto::profiles::v1development::Location>,
+    pub location_indices: Vec<i32>,
+    pub function_table: Vec<opentelemetry::proto::profiles::v1development::Function>,
+    pub attribute_table: Vec<opentelemetry::proto::common::v1::KeyValue<'a>>,
+    pub attribute_units: Vec<opentelemetry::proto::profiles::v1development::AttributeUnit>,
+    pub link_table: Vec<opentelemetry::proto::profiles::v1development::Link<'a>>,
+    pub string_table: Vec<Cow<'a, str>>,
+    pub time_nanos: i64,
+    pub duration_nanos: i64,
+    pub period_type: Option<opentelemetry::proto::profiles::v1development::ValueType>,
+    pub period: i64,
+    pub comment_strindices: Vec<i32>,
+    pub default_sample_type_index: i32,
+    pub profile_id: Cow<'a, [u8]>,
+    pub attribute_indices: Vec<i32>,
+    pub dropped_attributes_count: u32,
+    pub original_payload_format: Cow<'a, str>,
+    pub original_payload: Cow<'a, [u8]>,
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "crate::UnknownFields::is_empty"))]
+    pub unknown_fields: crate::UnknownFields,
+}
+
+impl<'a> MessageRead<'a> for Profile<'a> {
+    fn from_reader(r: &mut BytesReader, bytes: &'a [u8]) -> Result<Self> {
+        let mut msg = Self::default();
+        while !r.is_eof() {
+            match r.next_tag(bytes) {
+                Ok(10) => msg.sample_type.push(r.read_message::<opentelemetry::proto::profiles::v1development::ValueType>(bytes)?),
+                Ok(18) => msg.sample.push(r.read_message::<opentelemetry::proto::profiles::v1development::Sample>(bytes)?),
+                Ok(26) => msg.mapping_table.push(r.read_message::<opentelemetry::proto::profiles::v1development::Mapping>(bytes)?),
+                Ok(34) => msg.location_table.push(r.read_message::<opentelemetry::proto::profiles::v1development::Location>(bytes)?),
+                Ok(42) => msg.location_indices = r.read_packed(bytes, |r, bytes| Ok(r.read_int32(bytes)?))?,
+                Ok(50) => msg.function_table.push(r.read_message::<opentelemetry::proto::profiles::v1development::Function>(bytes)?),
+                Ok(58) => msg.attribute_table.push(r.read_message::<opentelemetry::proto::common::v1::KeyValue>(bytes)?),
+                Ok(66) => msg.attribute_units.push(r.read_message::<opentelemetry::proto::profiles::v1development::AttributeUnit>(bytes)?),
+                Ok(74) => msg.link_table.push(r.read_message::<opentelemetry::proto::profiles::v1development::Link>(bytes)?),
+                Ok(82) => msg.string_table.push(r.read_string(bytes).map(Cow::Borrowed)?),
+                Ok(88) => msg.time_nanos = r.read_int64(bytes)?,
+                Ok(96) => msg.duration_nanos = r.read_int64(bytes)?,
+                Ok(106) => msg.period_type = Some(r.read_message::<opentelemetry::proto::profiles::v1development::ValueType>(bytes)?),
+                Ok(112) => msg.period = r.read_int64(bytes)?,
+                Ok(122) => msg.comment_strindices = r.read_packed(bytes, |r, bytes| Ok(r.read_int32(bytes)?))?,
+                Ok(128) => msg.default_sample_type_index = r.read_int32(bytes)?,
+                Ok(138) => msg.profile_id = r.read_bytes(bytes).map(Cow::Borrowed)?,
+                Ok(146) => msg.attribute_indices = r.read_packed(bytes, |r, bytes| Ok(r.read_int32(bytes)?))?,
+                Ok(152) => msg.dropped_attributes_count = r.read_uint32(bytes)?,
+                Ok(162) => msg.original_payload_format = r.read_string(bytes).map(Cow::Borrowed)?,
+                Ok(170) => msg.original_payload = r.read_bytes(bytes).map(Cow::Borrowed)?,
+                Ok(t) => { msg.unknown_fields.read(r, bytes, t)?; }
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(msg)
+    }
+}
+
+impl<'a> MessageWrite for Profile<'a> {
+    fn get_size(&self) -> usize {
+        0
+        + self.unknown_fields.len()
+        + self.sample_type.iter().map(|s| 1 + sizeof_len((s).get_size())).sum::<usize>()
+        + self.sample.iter().map(|s| 1 + sizeof_len((s).get_size())).sum::<usize>()
+        + self.mapping_table.iter().map(|s| 1 + sizeof_len((s).get_size())).sum::<usize>()
+        + self.location_table.iter().map(|s| 1 + sizeof_len((s).get_size())).sum::<usize>()
+        + if self.location_indices.is_empty() { 0 } else { 1 + sizeof_len(self.location_indices.iter().map(|s| sizeof_varint(*(s) as u64)).sum::<usize>()) }
+        + self.function_table.iter().map(|s| 1 + sizeof_len((s).get_size())).sum::<usize>()
+        + self.attribute_table.iter().map(|s| 1 + sizeof_len((s).get_size())).sum::<usize>()
+        + self.attribute_units.iter().map(|s| 1 + sizeof_len((s).get_size())).sum::<usize>()
+        + self.link_table.iter().map(|s| 1 + sizeof_len((s).get_size())).sum::<usize>()
+        + self.string_table.iter().map(|s| 1 + sizeof_len((s).len())).sum::<usize>()
+        + if self.time_nanos == 0i64 { 0 } else { 1 + sizeof_varint(*(&self.time_nanos) as u64) }
+        + if self.duration_nanos == 0i64 { 0 } else { 1 + sizeof_varint(*(&self.duration_nanos) as u64) }
+        + self.period_type.as_ref().map_or(0, |m| 1 + sizeof_len((m).get_size()))
+        + if self.period == 0i64 { 0 } else { 1 + sizeof_varint(*(&self.period) as u64) }
+        + if self.comment_strindices.is_empty() { 0 } else { 1 + sizeof_len(self.comment_strindices.iter().map(|s| sizeof_varint(*(s) as u64)).sum::<usize>()) }
+        + if self.default_sample_type_index == 0i32 { 0 } else { 2 + sizeof_varint(*(&self.default_sample_type_index) as u64) }
+        + if self.profile_id == Cow::Borrowed(b"") { 0 } else { 2 + sizeof_len((&self.profile_id).len()) }
+        + if self.attribute_indices.is_empty() { 0 } else { 2 + sizeof_len(self.attribute_indices.iter().map(|s| sizeof_varint(*(s) as u64)).sum::<usize>()) }
+        + if self.dropped_attributes_count == 0u32 { 0 } else { 2 + sizeof_varint(*(&self.dropped_attributes_count) as u64) }
+        + if self.original_payload_format == "" { 0 } else { 2 + sizeof_len((&self.original_payload_format).len()) }
+        + if self.original_payload == Cow::Borrowed(b"") { 0 } else { 2 + sizeof_len((&self.original_payload).len()) }
+    }
+
+    fn write_message<W: WriterBackend>(&self, w: &mut Writer<W>) -> Result<()> {
+        for s in &self.sample_type { w.write_with_tag(10, |w| w.write_message(s))?; }
+        for s in &self.sample { w.write_with_tag(18, |w| w.write_message(s))?; }
+        for s in &self.mapping_table { w.write_with_tag(26, |w| w.write_message(s))?; }
+        for s in &self.location_table { w.write_with_tag(34, |w| w.write_message(s))?; }
+        w.write_packed_with_tag(42, &self.location_indices, |w, m| w.write_int32(*m), &|m| sizeof_varint(*(m) as u64))?;
+        for s in &self.function_table { w.write_with_tag(50, |w| w.write_message(s))?; }
+        for s in &self.attribute_table { w.write_with_tag(58, |w| w.write_message(s))?; }
+        for s in &self.attribute_units { w.write_with_tag(66, |w| w.write_message(s))?; }
+        for s in &self.link_table { w.write_with_tag(74, |w| w.write_message(s))?; }
+        for s in &self.string_table { w.write_with_tag(82, |w| w.write_string(&**s))?; }
+        if self.time_nanos != 0i64 { w.write_with_tag(88, |w| w.write_int64(*&self.time_nanos))?; }
+        if self.duration_nanos != 0i64 { w.write_with_tag(96, |w| w.write_int64(*&self.duration_nanos))?; }
+        if let Some(ref s) = self.period_type { w.write_with_tag(106, |w| w.write_message(s))?; }
+        if self.period != 0i64 { w.write_with_tag(112, |w| w.write_int64(*&self.period))?; }
+        w.write_packed_with_tag(122, &self.comment_strindices, |w, m| w.write_int32(*m), &|m| sizeof_varint(*(m) as u64))?;
+        if self.default_sample_type_index != 0i32 { w.write_with_tag(128, |w| w.write_int32(*&self.default_sample_type_index))?; }
+        if self.profile_id != Cow::Borrowed(b"") { w.write_with_tag(138, |w| w.write_bytes(&**&self.profile_id))?; }
+        w.write_packed_with_tag(146, &self.attribute_indices, |w, m| w.write_int32(*m), &|m| sizeof_varint(*(m) as u64))?;
+        if self.dropped_attributes_count != 0u32 { w.write_with_tag(152, |w| w.write_uint32(*&self.dropped_attributes_count))?; }
+        if self.original_payload_format != "" { w.write_with_tag(162, |w| w.write_string(&**&self.original_payload_format))?; }
+        if self.original_payload != Cow::Borrowed(b"") { w.write_with_tag(170, |w| w.write_bytes(&**&self.original_payload))?; }
+        self.unknown_fields.write(w)?;
+        Ok(())
+    }
+}
+
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Debug, Default, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AttributeUnit {
+    pub attribute_key_strindex: i32,
+    pub unit_strindex: i32,
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "crate::UnknownFields::is_empty"))]
+    pub unknown_fields: crate::UnknownFields,
+}
+
+impl<'a> MessageRead<'a> for AttributeUnit {
+    fn from_reader(r: &mut BytesReader, bytes: &'a [u8]) -> Result<Self> {
+        let mut msg = Self::default();
+        while !r.is_eof() {
+            match r.next_tag(bytes) {
+                Ok(8) => msg.attribute_key_strindex = r.read_int32(bytes)?,
+                Ok(16) => msg.unit_strindex = r.read_int32(bytes)?,
+                Ok(t) => { msg.unknown_fields.read(r, bytes, t)?; }
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(msg)
+    }
+}
+
+impl MessageWrite for AttributeUnit {
+    fn get_size(&self) -> usize {
+        0
+        + self.unknown_fields.len()
+        + if self.attribute_key_strindex == 0i32 { 0 } else { 1 + sizeof_varint(*(&self.attribute_key_strindex) as u64) }
+        + if self.unit_strindex == 0i32 { 0 } else { 1 + sizeof_varint(*(&self.unit_strindex) as u64) }
+    }
+
+    fn write_message<W: WriterBackend>(&self, w: &mut Writer<W>) -> Result<()> {
+        if self.attribute_key_strindex != 0i32 { w.write_with_tag(8, |w| w.write_int32(*&self.attribute_key_strindex))?; }
+        if self.unit_strindex != 0i32 { w.write_with_tag(16, |w| w.write_int32(*&self.unit_strindex))?; }
+        self.unknown_fields.write(w)?;
+        Ok(())
+    }
+}
+
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Debug, Default, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Link<'a> {
+    pub trace_id: Cow<'a, [u8]>,
+    pub span_id: Cow<'a, [u8]>,
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "crate::UnknownFields::is_empty"))]
+    pub unknown_fields: crate::UnknownFields,
+}
+
+impl<'a> MessageRead<'a> for Link<'a> {
+    fn from_reader(r: &mut BytesReader, bytes: &'a [u8]) -> Result<Self> {
+        let mut msg = Self::default();
+        while !r.is_eof() {
+            match r.next_tag(bytes) {
+                Ok(10) => msg.trace_id = r.read_bytes(bytes).map(Cow::Borrowed)?,
+                Ok(18) => msg.span_id = r.read_bytes(bytes).map(Cow::Borrowed)?,
+                Ok(t) => { msg.unknown_fields.read(r, bytes, t)?; }
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(msg)
+    }
+}
+
+impl<'a> MessageWrite for Link<'a> {
+    fn get_size(&self) -> usize {
+        0
+        + self.unknown_fields.len()
+        + if self.trace_id == Cow::Borrowed(b"") { 0 } else { 1 + sizeof_len((&self.trace_id).len()) }
+        + if self.span_id == Cow::Borrowed(b"") { 0 } else { 1 + sizeof_len((&self.span_id).len()) }
+    }
+
+    fn write_message<W: WriterBackend>(&self, w: &mut Writer<W>) -> Result<()> {
+        if self.trace_id != Cow::Borrowed(b"") { w.write_with_tag(10, |w| w.write_bytes(&**&self.trace_id))?; }
+        if self.span_id != Cow::Borrowed(b"") { w.write_with_tag(18, |w| w.write_bytes(&**&self.span_id))?; }
+        self.unknown_fields.write(w)?;
+        Ok(())
+    }
+}
+
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Debug, Default, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ValueType {
+    pub type_strindex: i32,
+    pub unit_strindex: i32,
+    pub aggregation_temporality: opentelemetry::proto::profiles::v1development::AggregationTemporality,
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "crate::UnknownFields::is_empty"))]
+    pub unknown_fields: crate::UnknownFields,
+}
+
+impl<'a> MessageRead<'a> for ValueType {
+    fn from_reader(r: &mut BytesReader, bytes: &'a [u8]) -> Result<Self> {
+        let mut msg = Self::default();
+        while !r.is_eof() {
+            match r.next_tag(bytes) {
+                Ok(8) => msg.type_strindex = r.read_int32(bytes)?,
+                Ok(16) => msg.unit_strindex = r.read_int32(bytes)?,
+                Ok(24) => msg.aggregation_temporality = r.read_enum(bytes)?,
+                Ok(t) => { msg.unknown_fields.read(r, bytes, t)?; }
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(msg)
+    }
+}
+
+impl MessageWrite for ValueType {
+    fn get_size(&self) -> usize {
+        0
+        + self.unknown_fields.len()
+        + if self.type_strindex == 0i32 { 0 } else { 1 + sizeof_varint(*(&self.type_strindex) as u64) }
+        + if self.unit_strindex == 0i32 { 0 } else { 1 + sizeof_varint(*(&self.unit_strindex) as u64) }
+        + if self.aggregation_temporality == opentelemetry::proto::profiles::v1development::AggregationTemporality::AGGREGATION_TEMPORALITY_UNSPECIFIED { 0 } else { 1 + sizeof_varint(*(&self.aggregation_temporality) as u64) }
+    }
+
+    fn write_message<W: WriterBackend>(&self, w: &mut Writer<W>) -> Result<()> {
+        if self.type_strindex != 0i32 { w.write_with_tag(8, |w| w.write_int32(*&self.type_strindex))?; }
+        if self.unit_strindex != 0i32 { w.write_with_tag(16, |w| w.write_int32(*&self.unit_strindex))?; }
+        if self.aggregation_temporality != opentelemetry::proto::profiles::v1development::AggregationTemporality::AGGREGATION_TEMPORALITY_UNSPECIFIED { w.write_with_tag(24, |w| w.write_enum(*&self.aggregation_temporality as i32))?; }
+        self.unknown_fields.write(w)?;
+        Ok(())
+    }
+}
+
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Debug, Default, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Sample {
+    pub locations_start_index: i32,
+    pub locations_length: i32,
+    pub value: Vec<i64>,
+    pub attribute_indices: Vec<i32>,
+    pub link_index: Option<i32>,
+    pub timestamps_unix_nano: Vec<u64>,
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "crate::UnknownFields::is_empty"))]
+    pub unknown_fields: crate::UnknownFields,
+}
+
+impl<'a> MessageRead<'a> for Sample {
+    fn from_reader(r: &mut BytesReader, bytes: &'a [u8]) -> Result<Self> {
+        let mut msg = Self::default();
+        while !r.is_eof() {
+            match r.next_tag(bytes) {
+                Ok(8) => msg.locations_start_index = r.read_int32(bytes)?,
+                Ok(16) => msg.locations_length = r.read_int32(bytes)?,
+                Ok(26) => msg.value = r.read_packed(bytes, |r, bytes| Ok(r.read_int64(bytes)?))?,
+                Ok(34) => msg.attribute_indices = r.read_packed(bytes, |r, bytes| Ok(r.read_int32(bytes)?))?,
+                Ok(40) => msg.link_index = Some(r.read_int32(bytes)?),
+                Ok(50) => msg.timestamps_unix_nano = r.read_packed(bytes, |r, bytes| Ok(r.read_uint64(bytes)?))?,
+                Ok(t) => { msg.unknown_fields.read(r, bytes, t)?; }
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(msg)
+    }
+}
+
+impl MessageWrite for Sample {
+    fn get_size(&self) -> usize {
+        0
+        + self.unknown_fields.len()
+        + if self.locations_start_index == 0i32 { 0 } else { 1 + sizeof_varint(*(&self.locations_start_index) as u64) }
+        + if self.locations_length == 0i32 { 0 } else { 1 + sizeof_varint(*(&self.locations_length) as u64) }
+        + if self.value.is_empty() { 0 } else { 1 + sizeof_len(self.value.iter().map(|s| sizeof_varint(*(s) as u64)).sum::<usize>()) }
+        + if self.attribute_indices.is_empty() { 0 } else { 1 + sizeof_len(self.attribute_indices.iter().map(|s| sizeof_varint(*(s) as u64)).sum::<usize>()) }
+        + self.link_index.as_ref().map_or(0, |m| 1 + sizeof_varint(*(m) as u64))
+        + if self.timestamps_unix_nano.is_empty() { 0 } else { 1 + sizeof_len(self.timestamps_unix_nano.iter().map(|s| sizeof_varint(*(s) as u64)).sum::<usize>()) }
+    }
+
+    fn write_message<W: WriterBackend>(&self, w: &mut Writer<W>) -> Result<()> {
+        if self.locations_start_index != 0i32 { w.write_with_tag(8, |w| w.write_int32(*&self.locations_start_index))?; }
+        if self.locations_length != 0i32 { w.write_with_tag(16, |w| w.write_int32(*&self.locations_length))?; }
+        w.write_packed_with_tag(26, &self.value, |w, m| w.write_int64(*m), &|m| sizeof_varint(*(m) as u64))?;
+        w.write_packed_with_tag(34, &self.attribute_indices, |w, m| w.write_int32(*m), &|m| sizeof_varint(*(m) as u64))?;
+        if let Some(ref m) = self.link_index { w.write_with_tag(40, |w| w.write_int32(*m))?; }
+        w.write_packed_with_tag(50, &self.timestamps_unix_nano, |w, m| w.write_uint64(*m), &|m| sizeof_varint(*(m) as u64))?;
+        self.unknown_fields.write(w)?;
+        Ok(())
+    }
+}
+
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Debug, Default, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Mapping {
+    pub memory_start: u64,
+    pub memory_limit: u64,
+    pub file_offset: u64,
+    pub filename_strindex: i32,
+    pub attribute_indices: Vec<i32>,
+    pub has_functions: bool,
+    pub has_filenames: bool,
+    pub has_line_numbers: bool,
+    pub has_inline_frames: bool,
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "crate::UnknownFields::is_empty"))]
+    pub unknown_fields: crate::UnknownFields,
+}
+
+impl<'a> MessageRead<'a> for Mapping {
+    fn from_reader(r: &mut BytesReader, bytes: &'a [u8]) -> Result<Self> {
+        let mut msg = Self::default();
+        while !r.is_eof() {
+            match r.next_tag(bytes) {
+                Ok(8) => msg.memory_start = r.read_uint64(bytes)?,
+                Ok(16) => msg.memory_limit = r.read_uint64(bytes)?,
+                Ok(24) => msg.file_offset = r.read_uint64(bytes)?,
+                Ok(32) => msg.filename_strindex = r.read_int32(bytes)?,
+                Ok(42) => msg.attribute_indices = r.read_packed(bytes, |r, bytes| Ok(r.read_int32(bytes)?))?,
+                Ok(48) => msg.has_functions = r.read_bool(bytes)?,
+                Ok(56) => msg.has_filenames = r.read_bool(bytes)?,
+                Ok(64) => msg.has_line_numbers = r.read_bool(bytes)?,
+                Ok(72) => msg.has_inline_frames = r.read_bool(bytes)?,
+                Ok(t) => { msg.unknown_fields.read(r, bytes, t)?; }
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(msg)
+    }
+}
+
+impl MessageWrite for Mapping {
+    fn get_size(&self) -> usize {
+        0
+        + self.unknown_fields.len()
+        + if self.memory_start == 0u64 { 0 } else { 1 + sizeof_varint(*(&self.memory_start) as u64) }
+        + if self.memory_limit == 0u64 { 0 } else { 1 + sizeof_varint(*(&self.memory_limit) as u64) }
+        + if self.file_offset == 0u64 { 0 } else { 1 + sizeof_varint(*(&self.file_offset) as u64) }
+        + if self.filename_strindex == 0i32 { 0 } else { 1 + sizeof_varint(*(&self.filename_strindex) as u64) }
+        + if self.attribute_indices.is_empty() { 0 } else { 1 + sizeof_len(self.attribute_indices.iter().map(|s| sizeof_varint(*(s) as u64)).sum::<usize>()) }
+        + if self.has_functions == false { 0 } else { 1 + sizeof_varint(*(&self.has_functions) as u64) }
+        + if self.has_filenames == false { 0 } else { 1 + sizeof_varint(*(&self.has_filenames) as u64) }
+        + if self.has_line_numbers == false { 0 } else { 1 + sizeof_varint(*(&self.has_line_numbers) as u64) }
+        + if self.has_inline_frames == false { 0 } else { 1 + sizeof_varint(*(&self.has_inline_frames) as u64) }
+    }
+
+    fn write_message<W: WriterBackend>(&self, w: &mut Writer<W>) -> Result<()> {
+        if self.memory_start != 0u64 { w.write_with_tag(8, |w| w.write_uint64(*&self.memory_start))?; }
+        if self.memory_limit != 0u64 { w.write_with_tag(16, |w| w.write_uint64(*&self.memory_limit))?; }
+        if self.file_offset != 0u64 { w.write_with_tag(24, |w| w.write_uint64(*&self.file_offset))?; }
+        if self.filename_strindex != 0i32 { w.write_with_tag(32, |w| w.write_int32(*&self.filename_strindex))?; }
+        w.write_packed_with_tag(42, &self.attribute_indices, |w, m| w.write_int32(*m), &|m| sizeof_varint(*(m) as u64))?;
+        if self.has_functions != false { w.write_with_tag(48, |w| w.write_bool(*&self.has_functions))?; }
+        if self.has_filenames != false { w.write_with_tag(56, |w| w.write_bool(*&self.has_filenames))?; }
+        if self.has_line_numbers != false { w.write_with_tag(64, |w| w.write_bool(*&self.has_line_numbers))?; }
+        if self.has_inline_frames != false { w.write_with_tag(72, |w| w.write_bool(*&self.has_inline_frames))?; }
+        self.unknown_fields.write(w)?;
+        Ok(())
+    }
+}
+
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Debug, Default, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Location {
+    pub mapping_index: Option<i32>,
+    pub address: u64,
+    pub line: Vec<opentelemetry::proto::profiles::v1development::Line>,
+    pub is_folded: bool,
+    pub attribute_indices: Vec<i32>,
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "crate::UnknownFields::is_empty"))]
+    pub unknown_fields: crate::UnknownFields,
+}
+
+impl<'a> MessageRead<'a> for Location {
+    fn from_reader(r: &mut BytesReader, bytes: &'a [u8]) -> Result<Self> {
+        let mut msg = Self::default();
+        while !r.is_eof() {
+            match r.next_tag(bytes) {
+                Ok(8) => msg.mapping_index = Some(r.read_int32(bytes)?),
+                Ok(16) => msg.address = r.read_uint64(bytes)?,
+                Ok(26) => msg.line.push(r.read_message::<opentelemetry::proto::profiles::v1development::Line>(bytes)?),
+                Ok(32) => msg.is_folded = r.read_bool(bytes)?,
+                Ok(42) => msg.attribute_indices = r.read_packed(bytes, |r, bytes| Ok(r.read_int32(bytes)?))?,
+                Ok(t) => { msg.unknown_fields.read(r, bytes, t)?; }
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(msg)
+    }
+}
+
+impl MessageWrite for Location {
+    fn get_size(&self) -> usize {
+        0
+        + self.unknown_fields.len()
+        + self.mapping_index.as_ref().map_or(0, |m| 1 + sizeof_varint(*(m) as u64))
+        + if self.address == 0u64 { 0 } else { 1 + sizeof_varint(*(&self.address) as u64) }
+        + self.line.iter().map(|s| 1 + sizeof_len((s).get_size())).sum::<usize>()
+        + if self.is_folded == false { 0 } else { 1 + sizeof_varint(*(&self.is_folded) as u64) }
+        + if self.attribute_indices.is_empty() { 0 } else { 1 + sizeof_len(self.attribute_indices.iter().map(|s| sizeof_varint(*(s) as u64)).sum::<usize>()) }
+    }
+
+    fn write_message<W: WriterBackend>(&self, w: &mut Writer<W>) -> Result<()> {
+        if let Some(ref m) = self.mapping_index { w.write_with_tag(8, |w| w.write_int32(*m))?; }
+        if self.address != 0u64 { w.write_with_tag(16, |w| w.write_uint64(*&self.address))?; }
+        for s in &self.line { w.write_with_tag(26, |w| w.write_message(s))?; }
+        if self.is_folded != false { w.write_with_tag(32, |w| w.write_bool(*&self.is_folded))?; }
+        w.write_packed_with_tag(42, &self.attribute_indices, |w, m| w.write_int32(*m), &|m| sizeof_varint(*(m) as u64))?;
+        self.unknown_fields.write(w)?;
+        Ok(())
+    }
+}
+
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Debug, Default, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Line {
+    pub function_index: i32,
+    pub line: i64,
+    pub column: i64,
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "crate::UnknownFields::is_empty"))]
+    pub unknown_fields: crate::UnknownFields,
+}
+
+impl<'a> MessageRead<'a> for Line {
+    fn from_reader(r: &mut BytesReader, bytes: &'a [u8]) -> Result<Self> {
+        let mut msg = Self::default();
+        while !r.is_eof() {
+            match r.next_tag(bytes) {
+                Ok(8) => msg.function_index = r.read_int32(bytes)?,
+                Ok(16) => msg.line = r.read_int64(bytes)?,
+                Ok(24) => msg.column = r.read_int64(bytes)?,
+                Ok(t) => { msg.unknown_fields.read(r, bytes, t)?; }
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(msg)
+    }
+}
+
+impl MessageWrite for Line {
+    fn get_size(&self) -> usize {
+        0
+        + self.unknown_fields.len()
+        + if self.function_index == 0i32 { 0 } else { 1 + sizeof_varint(*(&self.function_index) as u64) }
+        + if self.line == 0i64 { 0 } else { 1 + sizeof_varint(*(&self.line) as u64) }
+        + if self.column == 0i64 { 0 } else { 1 + sizeof_varint(*(&self.column) as u64) }
+    }
+
+    fn write_message<W: WriterBackend>(&self, w: &mut Writer<W>) -> Result<()> {
+        if self.function_index != 0i32 { w.write_with_tag(8, |w| w.write_int32(*&self.function_index))?; }
+        if self.line != 0i64 { w.write_with_tag(16, |w| w.write_int64(*&self.line))?; }
+        if self.column != 0i64 { w.write_with_tag(24, |w| w.write_int64(*&self.column))?; }
+        self.unknown_fields.write(w)?;
+        Ok(())
+    }
+}
+
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Debug, Default, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Function {
+    pub name_strindex: i32,
+    pub system_name_strindex: i32,
+    pub filename_strindex: i32,
+    pub start_line: i64,
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "crate::UnknownFields::is_empty"))]
+    pub unknown_fields: crate::UnknownFields,
+}
+
+impl<'a> MessageRead<'a> for Function {
+    fn from_reader(r: &mut BytesReader, bytes: &'a [u8]) -> Result<Self> {
+        let mut msg = Self::default();
+        while !r.is_eof() {
+            match r.next_tag(bytes) {
+                Ok(8) => msg.name_strindex = r.read_int32(bytes)?,
+                Ok(16) => msg.system_name_strindex = r.read_int32(bytes)?,
+                Ok(24) => msg.filename_strindex = r.read_int32(bytes)?,
+                Ok(32) => msg.start_line = r.read_int64(bytes)?,
+                Ok(t) => { msg.unknown_fields.read(r, bytes, t)?; }
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(msg)
+    }
+}
+
+impl MessageWrite for Function {
+    fn get_size(&self) -> usize {
+        0
+        + self.unknown_fields.len()
+        + if self.name_strindex == 0i32 { 0 } else { 1 + sizeof_varint(*(&self.name_strindex) as u64) }
+        + if self.system_name_strindex == 0i32 { 0 } else { 1 + sizeof_varint(*(&self.system_name_strindex) as u64) }
+        + if self.filename_strindex == 0i32 { 0 } else { 1 + sizeof_varint(*(&self.filename_strindex) as u64) }
+        + if self.start_line == 0i64 { 0 } else { 1 + sizeof_varint(*(&self.start_line) as u64) }
+    }
+
+    fn write_message<W: WriterBackend>(&self, w: &mut Writer<W>) -> Result<()> {
+        if self.name_strindex != 0i32 { w.write_with_tag(8, |w| w.write_int32(*&self.name_strindex))?; }
+        if self.system_name_strindex != 0i32 { w.write_with_tag(16, |w| w.write_int32(*&self.system_name_strindex))?; }
+        if self.filename_strindex != 0i32 { w.write_with_tag(24, |w| w.write_int32(*&self.filename_strindex))?; }
+        if self.start_line != 0i64 { w.write_with_tag(32, |w| w.write_int64(*&self.start_line))?; }
+        self.unknown_fields.write(w)?;
+        Ok(())
+    }
+}
+
@@ -1 +0,0 @@
-include!(concat!(env!("OUT_DIR"), "/proto/trace.rs"));
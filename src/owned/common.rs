@@ -0,0 +1,125 @@
+use std::borrow::Cow;
+
+use crate::opentelemetry::common::{
+    AnyValue, AnyValueOneOfvalue, ArrayValue, EntityRef, InstrumentationScope, KeyValue,
+    KeyValueList, Resource,
+};
+
+use crate::owned::IntoOwned;
+
+impl IntoOwned for Resource<'_> {
+    type Owned = Resource<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        Resource {
+            attributes: self.attributes.into_owned(),
+            dropped_attributes_count: self.dropped_attributes_count,
+            entity_refs: self.entity_refs.into_owned(),
+            unknown_fields: self.unknown_fields,
+        }
+    }
+}
+
+impl IntoOwned for EntityRef<'_> {
+    type Owned = EntityRef<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        let into_owned = |keys: Vec<Cow<'_, str>>| {
+            keys.into_iter()
+                .map(|key| Cow::Owned(key.into_owned()))
+                .collect()
+        };
+
+        EntityRef {
+            schema_url: Cow::Owned(self.schema_url.into_owned()),
+            type_pb: Cow::Owned(self.type_pb.into_owned()),
+            id_keys: into_owned(self.id_keys),
+            description_keys: into_owned(self.description_keys),
+            unknown_fields: self.unknown_fields,
+        }
+    }
+}
+
+impl IntoOwned for InstrumentationScope<'_> {
+    type Owned = InstrumentationScope<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        InstrumentationScope {
+            name: Cow::Owned(self.name.into_owned()),
+            version: Cow::Owned(self.version.into_owned()),
+            attributes: self.attributes.into_owned(),
+            dropped_attributes_count: self.dropped_attributes_count,
+            unknown_fields: self.unknown_fields,
+        }
+    }
+}
+
+impl IntoOwned for KeyValue<'_> {
+    type Owned = KeyValue<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        KeyValue {
+            key: Cow::Owned(self.key.into_owned()),
+            value: self.value.into_owned(),
+            unknown_fields: self.unknown_fields,
+        }
+    }
+}
+
+impl IntoOwned for KeyValueList<'_> {
+    type Owned = KeyValueList<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        KeyValueList {
+            values: self.values.into_owned(),
+            unknown_fields: self.unknown_fields,
+        }
+    }
+}
+
+impl IntoOwned for ArrayValue<'_> {
+    type Owned = ArrayValue<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        ArrayValue {
+            values: self.values.into_owned(),
+            unknown_fields: self.unknown_fields,
+        }
+    }
+}
+
+impl IntoOwned for AnyValue<'_> {
+    type Owned = AnyValue<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        AnyValue {
+            value: self.value.into_owned(),
+            unknown_fields: self.unknown_fields,
+        }
+    }
+}
+
+impl IntoOwned for AnyValueOneOfvalue<'_> {
+    type Owned = AnyValueOneOfvalue<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        match self {
+            AnyValueOneOfvalue::string_value(val) => {
+                AnyValueOneOfvalue::string_value(Cow::Owned(val.into_owned()))
+            }
+            AnyValueOneOfvalue::bool_value(val) => AnyValueOneOfvalue::bool_value(val),
+            AnyValueOneOfvalue::int_value(val) => AnyValueOneOfvalue::int_value(val),
+            AnyValueOneOfvalue::double_value(val) => AnyValueOneOfvalue::double_value(val),
+            AnyValueOneOfvalue::array_value(val) => {
+                AnyValueOneOfvalue::array_value(val.into_owned())
+            }
+            AnyValueOneOfvalue::kvlist_value(val) => {
+                AnyValueOneOfvalue::kvlist_value(val.into_owned())
+            }
+            AnyValueOneOfvalue::bytes_value(val) => {
+                AnyValueOneOfvalue::bytes_value(Cow::Owned(val.into_owned()))
+            }
+            AnyValueOneOfvalue::None => AnyValueOneOfvalue::None,
+        }
+    }
+}
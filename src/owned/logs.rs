@@ -0,0 +1,100 @@
+use std::borrow::Cow;
+
+use crate::opentelemetry::logs::{
+    ExportLogsPartialSuccess, ExportLogsServiceRequest, ExportLogsServiceResponse, LogRecord,
+    LogsData, ResourceLogs, ScopeLogs,
+};
+
+use crate::owned::IntoOwned;
+
+impl IntoOwned for ExportLogsServiceRequest<'_> {
+    type Owned = ExportLogsServiceRequest<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        ExportLogsServiceRequest {
+            resource_logs: self.resource_logs.into_owned(),
+            unknown_fields: self.unknown_fields,
+        }
+    }
+}
+
+impl IntoOwned for ExportLogsServiceResponse<'_> {
+    type Owned = ExportLogsServiceResponse<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        ExportLogsServiceResponse {
+            partial_success: self.partial_success.into_owned(),
+            unknown_fields: self.unknown_fields,
+        }
+    }
+}
+
+impl IntoOwned for ExportLogsPartialSuccess<'_> {
+    type Owned = ExportLogsPartialSuccess<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        ExportLogsPartialSuccess {
+            rejected_log_records: self.rejected_log_records,
+            error_message: Cow::Owned(self.error_message.into_owned()),
+            unknown_fields: self.unknown_fields,
+        }
+    }
+}
+
+impl IntoOwned for LogsData<'_> {
+    type Owned = LogsData<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        LogsData {
+            resource_logs: self.resource_logs.into_owned(),
+            unknown_fields: self.unknown_fields,
+        }
+    }
+}
+
+impl IntoOwned for ResourceLogs<'_> {
+    type Owned = ResourceLogs<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        ResourceLogs {
+            resource: self.resource.into_owned(),
+            scope_logs: self.scope_logs.into_owned(),
+            schema_url: Cow::Owned(self.schema_url.into_owned()),
+            unknown_fields: self.unknown_fields,
+        }
+    }
+}
+
+impl IntoOwned for ScopeLogs<'_> {
+    type Owned = ScopeLogs<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        ScopeLogs {
+            scope: self.scope.into_owned(),
+            log_records: self.log_records.into_owned(),
+            schema_url: Cow::Owned(self.schema_url.into_owned()),
+            unknown_fields: self.unknown_fields,
+        }
+    }
+}
+
+impl IntoOwned for LogRecord<'_> {
+    type Owned = LogRecord<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        LogRecord {
+            time_unix_nano: self.time_unix_nano,
+            observed_time_unix_nano: self.observed_time_unix_nano,
+            severity_number: self.severity_number,
+            severity_text: Cow::Owned(self.severity_text.into_owned()),
+            body: self.body.into_owned(),
+            attributes: self.attributes.into_owned(),
+            dropped_attributes_count: self.dropped_attributes_count,
+            flags: self.flags,
+            trace_id: Cow::Owned(self.trace_id.into_owned()),
+            span_id: Cow::Owned(self.span_id.into_owned()),
+            event_name: Cow::Owned(self.event_name.into_owned()),
+            unknown_fields: self.unknown_fields,
+        }
+    }
+}
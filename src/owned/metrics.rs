@@ -0,0 +1,265 @@
+use std::borrow::Cow;
+
+use crate::opentelemetry::metrics::{
+    Exemplar, ExponentialHistogram, ExponentialHistogramDataPoint, ExportMetricsPartialSuccess,
+    ExportMetricsServiceRequest, ExportMetricsServiceResponse, Gauge, Histogram,
+    HistogramDataPoint, Metric, MetricOneOfdata, MetricsData, NumberDataPoint, ResourceMetrics,
+    ScopeMetrics, Sum, Summary, SummaryDataPoint,
+};
+
+use crate::owned::IntoOwned;
+
+impl IntoOwned for ExportMetricsServiceRequest<'_> {
+    type Owned = ExportMetricsServiceRequest<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        ExportMetricsServiceRequest {
+            resource_metrics: self.resource_metrics.into_owned(),
+            unknown_fields: self.unknown_fields,
+        }
+    }
+}
+
+impl IntoOwned for ExportMetricsServiceResponse<'_> {
+    type Owned = ExportMetricsServiceResponse<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        ExportMetricsServiceResponse {
+            partial_success: self.partial_success.into_owned(),
+            unknown_fields: self.unknown_fields,
+        }
+    }
+}
+
+impl IntoOwned for ExportMetricsPartialSuccess<'_> {
+    type Owned = ExportMetricsPartialSuccess<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        ExportMetricsPartialSuccess {
+            rejected_data_points: self.rejected_data_points,
+            error_message: Cow::Owned(self.error_message.into_owned()),
+            unknown_fields: self.unknown_fields,
+        }
+    }
+}
+
+impl IntoOwned for MetricsData<'_> {
+    type Owned = MetricsData<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        MetricsData {
+            resource_metrics: self.resource_metrics.into_owned(),
+            unknown_fields: self.unknown_fields,
+        }
+    }
+}
+
+impl IntoOwned for ResourceMetrics<'_> {
+    type Owned = ResourceMetrics<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        ResourceMetrics {
+            resource: self.resource.into_owned(),
+            scope_metrics: self.scope_metrics.into_owned(),
+            schema_url: Cow::Owned(self.schema_url.into_owned()),
+            unknown_fields: self.unknown_fields,
+        }
+    }
+}
+
+impl IntoOwned for ScopeMetrics<'_> {
+    type Owned = ScopeMetrics<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        ScopeMetrics {
+            scope: self.scope.into_owned(),
+            metrics: self.metrics.into_owned(),
+            schema_url: Cow::Owned(self.schema_url.into_owned()),
+            unknown_fields: self.unknown_fields,
+        }
+    }
+}
+
+impl IntoOwned for Metric<'_> {
+    type Owned = Metric<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        Metric {
+            name: Cow::Owned(self.name.into_owned()),
+            description: Cow::Owned(self.description.into_owned()),
+            unit: Cow::Owned(self.unit.into_owned()),
+            data: self.data.into_owned(),
+            metadata: self.metadata.into_owned(),
+            unknown_fields: self.unknown_fields,
+        }
+    }
+}
+
+impl IntoOwned for MetricOneOfdata<'_> {
+    type Owned = MetricOneOfdata<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        match self {
+            MetricOneOfdata::gauge(val) => MetricOneOfdata::gauge(val.into_owned()),
+            MetricOneOfdata::sum(val) => MetricOneOfdata::sum(val.into_owned()),
+            MetricOneOfdata::histogram(val) => MetricOneOfdata::histogram(val.into_owned()),
+            MetricOneOfdata::exponential_histogram(val) => {
+                MetricOneOfdata::exponential_histogram(val.into_owned())
+            }
+            MetricOneOfdata::summary(val) => MetricOneOfdata::summary(val.into_owned()),
+            MetricOneOfdata::None => MetricOneOfdata::None,
+        }
+    }
+}
+
+impl IntoOwned for Gauge<'_> {
+    type Owned = Gauge<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        Gauge {
+            data_points: self.data_points.into_owned(),
+            unknown_fields: self.unknown_fields,
+        }
+    }
+}
+
+impl IntoOwned for Sum<'_> {
+    type Owned = Sum<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        Sum {
+            data_points: self.data_points.into_owned(),
+            aggregation_temporality: self.aggregation_temporality,
+            is_monotonic: self.is_monotonic,
+            unknown_fields: self.unknown_fields,
+        }
+    }
+}
+
+impl IntoOwned for Histogram<'_> {
+    type Owned = Histogram<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        Histogram {
+            data_points: self.data_points.into_owned(),
+            aggregation_temporality: self.aggregation_temporality,
+            unknown_fields: self.unknown_fields,
+        }
+    }
+}
+
+impl IntoOwned for ExponentialHistogram<'_> {
+    type Owned = ExponentialHistogram<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        ExponentialHistogram {
+            data_points: self.data_points.into_owned(),
+            aggregation_temporality: self.aggregation_temporality,
+            unknown_fields: self.unknown_fields,
+        }
+    }
+}
+
+impl IntoOwned for Summary<'_> {
+    type Owned = Summary<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        Summary {
+            data_points: self.data_points.into_owned(),
+            unknown_fields: self.unknown_fields,
+        }
+    }
+}
+
+impl IntoOwned for NumberDataPoint<'_> {
+    type Owned = NumberDataPoint<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        NumberDataPoint {
+            attributes: self.attributes.into_owned(),
+            start_time_unix_nano: self.start_time_unix_nano,
+            time_unix_nano: self.time_unix_nano,
+            exemplars: self.exemplars.into_owned(),
+            flags: self.flags,
+            value: self.value,
+            unknown_fields: self.unknown_fields,
+        }
+    }
+}
+
+impl IntoOwned for HistogramDataPoint<'_> {
+    type Owned = HistogramDataPoint<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        HistogramDataPoint {
+            attributes: self.attributes.into_owned(),
+            start_time_unix_nano: self.start_time_unix_nano,
+            time_unix_nano: self.time_unix_nano,
+            count: self.count,
+            sum: self.sum,
+            bucket_counts: Cow::Owned(self.bucket_counts.into_owned()),
+            explicit_bounds: Cow::Owned(self.explicit_bounds.into_owned()),
+            exemplars: self.exemplars.into_owned(),
+            flags: self.flags,
+            min: self.min,
+            max: self.max,
+            unknown_fields: self.unknown_fields,
+        }
+    }
+}
+
+impl IntoOwned for ExponentialHistogramDataPoint<'_> {
+    type Owned = ExponentialHistogramDataPoint<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        ExponentialHistogramDataPoint {
+            attributes: self.attributes.into_owned(),
+            start_time_unix_nano: self.start_time_unix_nano,
+            time_unix_nano: self.time_unix_nano,
+            count: self.count,
+            sum: self.sum,
+            scale: self.scale,
+            zero_count: self.zero_count,
+            positive: self.positive,
+            negative: self.negative,
+            flags: self.flags,
+            exemplars: self.exemplars.into_owned(),
+            min: self.min,
+            max: self.max,
+            zero_threshold: self.zero_threshold,
+            unknown_fields: self.unknown_fields,
+        }
+    }
+}
+
+impl IntoOwned for SummaryDataPoint<'_> {
+    type Owned = SummaryDataPoint<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        SummaryDataPoint {
+            attributes: self.attributes.into_owned(),
+            start_time_unix_nano: self.start_time_unix_nano,
+            time_unix_nano: self.time_unix_nano,
+            count: self.count,
+            sum: self.sum,
+            quantile_values: self.quantile_values,
+            flags: self.flags,
+            unknown_fields: self.unknown_fields,
+        }
+    }
+}
+
+impl IntoOwned for Exemplar<'_> {
+    type Owned = Exemplar<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        Exemplar {
+            filtered_attributes: self.filtered_attributes.into_owned(),
+            time_unix_nano: self.time_unix_nano,
+            span_id: Cow::Owned(self.span_id.into_owned()),
+            trace_id: Cow::Owned(self.trace_id.into_owned()),
+            value: self.value,
+            unknown_fields: self.unknown_fields,
+        }
+    }
+}
@@ -0,0 +1,29 @@
+pub(crate) mod common;
+pub(crate) mod logs;
+pub(crate) mod metrics;
+pub(crate) mod profiles;
+pub(crate) mod trace;
+
+/// Converts a message borrowing from the decoded buffer into one that owns
+/// all of its data, so it can outlive the buffer or be sent to another task.
+pub trait IntoOwned {
+    type Owned: 'static;
+
+    fn into_owned(self) -> Self::Owned;
+}
+
+impl<T: IntoOwned> IntoOwned for Option<T> {
+    type Owned = Option<T::Owned>;
+
+    fn into_owned(self) -> Self::Owned {
+        self.map(IntoOwned::into_owned)
+    }
+}
+
+impl<T: IntoOwned> IntoOwned for Vec<T> {
+    type Owned = Vec<T::Owned>;
+
+    fn into_owned(self) -> Self::Owned {
+        self.into_iter().map(IntoOwned::into_owned).collect()
+    }
+}
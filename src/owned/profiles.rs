@@ -0,0 +1,126 @@
+use std::borrow::Cow;
+
+use crate::opentelemetry::profiles::{
+    ExportProfilesPartialSuccess, ExportProfilesServiceRequest, ExportProfilesServiceResponse,
+    Link, Profile, ProfilesData, ResourceProfiles, ScopeProfiles,
+};
+
+use crate::owned::IntoOwned;
+
+impl IntoOwned for ExportProfilesServiceRequest<'_> {
+    type Owned = ExportProfilesServiceRequest<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        ExportProfilesServiceRequest {
+            resource_profiles: self.resource_profiles.into_owned(),
+            unknown_fields: self.unknown_fields,
+        }
+    }
+}
+
+impl IntoOwned for ExportProfilesServiceResponse<'_> {
+    type Owned = ExportProfilesServiceResponse<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        ExportProfilesServiceResponse {
+            partial_success: self.partial_success.into_owned(),
+            unknown_fields: self.unknown_fields,
+        }
+    }
+}
+
+impl IntoOwned for ExportProfilesPartialSuccess<'_> {
+    type Owned = ExportProfilesPartialSuccess<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        ExportProfilesPartialSuccess {
+            rejected_profiles: self.rejected_profiles,
+            error_message: Cow::Owned(self.error_message.into_owned()),
+            unknown_fields: self.unknown_fields,
+        }
+    }
+}
+
+impl IntoOwned for ProfilesData<'_> {
+    type Owned = ProfilesData<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        ProfilesData {
+            resource_profiles: self.resource_profiles.into_owned(),
+            unknown_fields: self.unknown_fields,
+        }
+    }
+}
+
+impl IntoOwned for ResourceProfiles<'_> {
+    type Owned = ResourceProfiles<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        ResourceProfiles {
+            resource: self.resource.into_owned(),
+            scope_profiles: self.scope_profiles.into_owned(),
+            schema_url: Cow::Owned(self.schema_url.into_owned()),
+            unknown_fields: self.unknown_fields,
+        }
+    }
+}
+
+impl IntoOwned for ScopeProfiles<'_> {
+    type Owned = ScopeProfiles<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        ScopeProfiles {
+            scope: self.scope.into_owned(),
+            profiles: self.profiles.into_owned(),
+            schema_url: Cow::Owned(self.schema_url.into_owned()),
+            unknown_fields: self.unknown_fields,
+        }
+    }
+}
+
+impl IntoOwned for Profile<'_> {
+    type Owned = Profile<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        Profile {
+            sample_type: self.sample_type,
+            sample: self.sample,
+            mapping_table: self.mapping_table,
+            location_table: self.location_table,
+            location_indices: self.location_indices,
+            function_table: self.function_table,
+            attribute_table: self.attribute_table.into_owned(),
+            attribute_units: self.attribute_units,
+            link_table: self.link_table.into_owned(),
+            string_table: self
+                .string_table
+                .into_iter()
+                .map(|string| Cow::Owned(string.into_owned()))
+                .collect(),
+            time_nanos: self.time_nanos,
+            duration_nanos: self.duration_nanos,
+            period_type: self.period_type,
+            period: self.period,
+            comment_strindices: self.comment_strindices,
+            default_sample_type_index: self.default_sample_type_index,
+            profile_id: Cow::Owned(self.profile_id.into_owned()),
+            attribute_indices: self.attribute_indices,
+            dropped_attributes_count: self.dropped_attributes_count,
+            original_payload_format: Cow::Owned(self.original_payload_format.into_owned()),
+            original_payload: Cow::Owned(self.original_payload.into_owned()),
+            unknown_fields: self.unknown_fields,
+        }
+    }
+}
+
+impl IntoOwned for Link<'_> {
+    type Owned = Link<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        Link {
+            trace_id: Cow::Owned(self.trace_id.into_owned()),
+            span_id: Cow::Owned(self.span_id.into_owned()),
+            unknown_fields: self.unknown_fields,
+        }
+    }
+}
@@ -0,0 +1,147 @@
+use std::borrow::Cow;
+
+use crate::opentelemetry::trace::{
+    ExportTracePartialSuccess, ExportTraceServiceRequest, ExportTraceServiceResponse,
+    ResourceSpans, ScopeSpans, Span, SpanEvent, SpanLink, Status, TracesData,
+};
+
+use crate::owned::IntoOwned;
+
+impl IntoOwned for ExportTraceServiceRequest<'_> {
+    type Owned = ExportTraceServiceRequest<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        ExportTraceServiceRequest {
+            resource_spans: self.resource_spans.into_owned(),
+            unknown_fields: self.unknown_fields,
+        }
+    }
+}
+
+impl IntoOwned for ExportTraceServiceResponse<'_> {
+    type Owned = ExportTraceServiceResponse<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        ExportTraceServiceResponse {
+            partial_success: self.partial_success.into_owned(),
+            unknown_fields: self.unknown_fields,
+        }
+    }
+}
+
+impl IntoOwned for ExportTracePartialSuccess<'_> {
+    type Owned = ExportTracePartialSuccess<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        ExportTracePartialSuccess {
+            rejected_spans: self.rejected_spans,
+            error_message: Cow::Owned(self.error_message.into_owned()),
+            unknown_fields: self.unknown_fields,
+        }
+    }
+}
+
+impl IntoOwned for TracesData<'_> {
+    type Owned = TracesData<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        TracesData {
+            resource_spans: self.resource_spans.into_owned(),
+            unknown_fields: self.unknown_fields,
+        }
+    }
+}
+
+impl IntoOwned for ResourceSpans<'_> {
+    type Owned = ResourceSpans<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        ResourceSpans {
+            resource: self.resource.into_owned(),
+            scope_spans: self.scope_spans.into_owned(),
+            schema_url: Cow::Owned(self.schema_url.into_owned()),
+            unknown_fields: self.unknown_fields,
+        }
+    }
+}
+
+impl IntoOwned for ScopeSpans<'_> {
+    type Owned = ScopeSpans<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        ScopeSpans {
+            scope: self.scope.into_owned(),
+            spans: self.spans.into_owned(),
+            schema_url: Cow::Owned(self.schema_url.into_owned()),
+            unknown_fields: self.unknown_fields,
+        }
+    }
+}
+
+impl IntoOwned for Span<'_> {
+    type Owned = Span<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        Span {
+            trace_id: Cow::Owned(self.trace_id.into_owned()),
+            span_id: Cow::Owned(self.span_id.into_owned()),
+            trace_state: Cow::Owned(self.trace_state.into_owned()),
+            parent_span_id: Cow::Owned(self.parent_span_id.into_owned()),
+            flags: self.flags,
+            name: Cow::Owned(self.name.into_owned()),
+            kind: self.kind,
+            start_time_unix_nano: self.start_time_unix_nano,
+            end_time_unix_nano: self.end_time_unix_nano,
+            attributes: self.attributes.into_owned(),
+            dropped_attributes_count: self.dropped_attributes_count,
+            events: self.events.into_owned(),
+            dropped_events_count: self.dropped_events_count,
+            links: self.links.into_owned(),
+            dropped_links_count: self.dropped_links_count,
+            status: self.status.into_owned(),
+            unknown_fields: self.unknown_fields,
+        }
+    }
+}
+
+impl IntoOwned for SpanEvent<'_> {
+    type Owned = SpanEvent<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        SpanEvent {
+            time_unix_nano: self.time_unix_nano,
+            name: Cow::Owned(self.name.into_owned()),
+            attributes: self.attributes.into_owned(),
+            dropped_attributes_count: self.dropped_attributes_count,
+            unknown_fields: self.unknown_fields,
+        }
+    }
+}
+
+impl IntoOwned for SpanLink<'_> {
+    type Owned = SpanLink<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        SpanLink {
+            trace_id: Cow::Owned(self.trace_id.into_owned()),
+            span_id: Cow::Owned(self.span_id.into_owned()),
+            trace_state: Cow::Owned(self.trace_state.into_owned()),
+            attributes: self.attributes.into_owned(),
+            dropped_attributes_count: self.dropped_attributes_count,
+            flags: self.flags,
+            unknown_fields: self.unknown_fields,
+        }
+    }
+}
+
+impl IntoOwned for Status<'_> {
+    type Owned = Status<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        Status {
+            message: Cow::Owned(self.message.into_owned()),
+            code: self.code,
+            unknown_fields: self.unknown_fields,
+        }
+    }
+}
@@ -0,0 +1,493 @@
+use crate::batch::data_points_len;
+use crate::opentelemetry::common::{InstrumentationScope, KeyValue, Resource};
+use crate::opentelemetry::logs::{ExportLogsServiceRequest, LogRecord};
+use crate::opentelemetry::metrics::{
+    ExponentialHistogramDataPoint, ExportMetricsServiceRequest, HistogramDataPoint, Metric,
+    MetricOneOfdata, NumberDataPoint, SummaryDataPoint,
+};
+use crate::opentelemetry::trace::{ExportTraceServiceRequest, Span};
+
+/// A data point of any metric type, as passed to
+/// [`ExportMetricsServiceRequest::retain_data_points`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DataPoint<'r, 'a> {
+    /// A gauge or sum data point.
+    Number(&'r NumberDataPoint<'a>),
+    Histogram(&'r HistogramDataPoint<'a>),
+    ExponentialHistogram(&'r ExponentialHistogramDataPoint<'a>),
+    Summary(&'r SummaryDataPoint<'a>),
+}
+
+impl<'r, 'a> DataPoint<'r, 'a> {
+    pub fn attributes(&self) -> &'r [KeyValue<'a>] {
+        match self {
+            DataPoint::Number(data_point) => &data_point.attributes,
+            DataPoint::Histogram(data_point) => &data_point.attributes,
+            DataPoint::ExponentialHistogram(data_point) => &data_point.attributes,
+            DataPoint::Summary(data_point) => &data_point.attributes,
+        }
+    }
+
+    pub fn time_unix_nano(&self) -> u64 {
+        match self {
+            DataPoint::Number(data_point) => data_point.time_unix_nano,
+            DataPoint::Histogram(data_point) => data_point.time_unix_nano,
+            DataPoint::ExponentialHistogram(data_point) => data_point.time_unix_nano,
+            DataPoint::Summary(data_point) => data_point.time_unix_nano,
+        }
+    }
+}
+
+// Retains the matching entries of `items`, returning how many were removed
+fn retain<T>(items: &mut Vec<T>, mut f: impl FnMut(&T) -> bool) -> usize {
+    let len = items.len();
+    items.retain(|item| f(item));
+    len - items.len()
+}
+
+// Runs `f` on each container, which returns how many children it removed,
+// and drops the containers it emptied. Those that were already empty stay.
+fn prune<T>(
+    containers: &mut Vec<T>,
+    is_empty: impl Fn(&T) -> bool,
+    mut f: impl FnMut(&mut T) -> usize,
+) {
+    containers.retain_mut(|container| f(container) == 0 || !is_empty(container));
+}
+
+fn retain_metric_data_points<'a>(
+    metric: &mut Metric<'a>,
+    mut f: impl FnMut(DataPoint<'_, 'a>, &str) -> bool,
+) -> usize {
+    let name = &metric.name;
+    let mut f = |data_point: DataPoint<'_, 'a>| f(data_point, name);
+    match &mut metric.data {
+        MetricOneOfdata::gauge(gauge) => {
+            retain(&mut gauge.data_points, |p| f(DataPoint::Number(p)))
+        }
+        MetricOneOfdata::sum(sum) => retain(&mut sum.data_points, |p| f(DataPoint::Number(p))),
+        MetricOneOfdata::histogram(histogram) => {
+            retain(&mut histogram.data_points, |p| f(DataPoint::Histogram(p)))
+        }
+        MetricOneOfdata::exponential_histogram(histogram) => {
+            retain(&mut histogram.data_points, |p| {
+                f(DataPoint::ExponentialHistogram(p))
+            })
+        }
+        MetricOneOfdata::summary(summary) => {
+            retain(&mut summary.data_points, |p| f(DataPoint::Summary(p)))
+        }
+        MetricOneOfdata::None => 0,
+    }
+}
+
+impl<'a> ExportTraceServiceRequest<'a> {
+    /// Keeps the spans for which `f` returns true, given each span with its
+    /// resource and scope, then removes the scopes and resources this left
+    /// without spans. Those that had none to begin with are kept.
+    ///
+    /// Returns the number of spans removed.
+    pub fn retain_spans<F>(&mut self, mut f: F) -> usize
+    where
+        F: FnMut(&Span<'a>, Option<&Resource<'a>>, Option<&InstrumentationScope<'a>>) -> bool,
+    {
+        let mut removed = 0;
+        prune(
+            &mut self.resource_spans,
+            |resource_spans| resource_spans.scope_spans.is_empty(),
+            |resource_spans| {
+                let resource = resource_spans.resource.as_ref();
+                let len = resource_spans.scope_spans.len();
+                prune(
+                    &mut resource_spans.scope_spans,
+                    |scope_spans| scope_spans.spans.is_empty(),
+                    |scope_spans| {
+                        let scope = scope_spans.scope.as_ref();
+                        let n = retain(&mut scope_spans.spans, |span| f(span, resource, scope));
+                        removed += n;
+                        n
+                    },
+                );
+                len - resource_spans.scope_spans.len()
+            },
+        );
+        removed
+    }
+}
+
+impl<'a> ExportLogsServiceRequest<'a> {
+    /// Keeps the log records for which `f` returns true, given each record
+    /// with its resource and scope, then removes the scopes and resources
+    /// this left without records. Those that had none to begin with are
+    /// kept.
+    ///
+    /// Returns the number of log records removed.
+    pub fn retain_log_records<F>(&mut self, mut f: F) -> usize
+    where
+        F: FnMut(&LogRecord<'a>, Option<&Resource<'a>>, Option<&InstrumentationScope<'a>>) -> bool,
+    {
+        let mut removed = 0;
+        prune(
+            &mut self.resource_logs,
+            |resource_logs| resource_logs.scope_logs.is_empty(),
+            |resource_logs| {
+                let resource = resource_logs.resource.as_ref();
+                let len = resource_logs.scope_logs.len();
+                prune(
+                    &mut resource_logs.scope_logs,
+                    |scope_logs| scope_logs.log_records.is_empty(),
+                    |scope_logs| {
+                        let scope = scope_logs.scope.as_ref();
+                        let n = retain(&mut scope_logs.log_records, |log_record| {
+                            f(log_record, resource, scope)
+                        });
+                        removed += n;
+                        n
+                    },
+                );
+                len - resource_logs.scope_logs.len()
+            },
+        );
+        removed
+    }
+}
+
+impl<'a> ExportMetricsServiceRequest<'a> {
+    /// Keeps the metrics for which `f` returns true, given each metric with
+    /// its resource and scope, then removes the scopes and resources this
+    /// left without metrics. Those that had none to begin with are kept.
+    ///
+    /// Returns the number of metrics removed.
+    pub fn retain_metrics<F>(&mut self, mut f: F) -> usize
+    where
+        F: FnMut(&Metric<'a>, Option<&Resource<'a>>, Option<&InstrumentationScope<'a>>) -> bool,
+    {
+        let mut removed = 0;
+        prune(
+            &mut self.resource_metrics,
+            |resource_metrics| resource_metrics.scope_metrics.is_empty(),
+            |resource_metrics| {
+                let resource = resource_metrics.resource.as_ref();
+                let len = resource_metrics.scope_metrics.len();
+                prune(
+                    &mut resource_metrics.scope_metrics,
+                    |scope_metrics| scope_metrics.metrics.is_empty(),
+                    |scope_metrics| {
+                        let scope = scope_metrics.scope.as_ref();
+                        let n = retain(&mut scope_metrics.metrics, |metric| {
+                            f(metric, resource, scope)
+                        });
+                        removed += n;
+                        n
+                    },
+                );
+                len - resource_metrics.scope_metrics.len()
+            },
+        );
+        removed
+    }
+
+    /// Keeps the data points for which `f` returns true, given each point
+    /// with the name of its metric, its resource and its scope, then
+    /// removes the metrics, scopes and resources this left without data
+    /// points. Those that had none to begin with are kept.
+    ///
+    /// Returns the number of data points removed.
+    pub fn retain_data_points<F>(&mut self, mut f: F) -> usize
+    where
+        F: FnMut(
+            DataPoint<'_, 'a>,
+            &str,
+            Option<&Resource<'a>>,
+            Option<&InstrumentationScope<'a>>,
+        ) -> bool,
+    {
+        let mut removed = 0;
+        prune(
+            &mut self.resource_metrics,
+            |resource_metrics| resource_metrics.scope_metrics.is_empty(),
+            |resource_metrics| {
+                let resource = resource_metrics.resource.as_ref();
+                let len = resource_metrics.scope_metrics.len();
+                prune(
+                    &mut resource_metrics.scope_metrics,
+                    |scope_metrics| scope_metrics.metrics.is_empty(),
+                    |scope_metrics| {
+                        let scope = scope_metrics.scope.as_ref();
+                        let len = scope_metrics.metrics.len();
+                        prune(
+                            &mut scope_metrics.metrics,
+                            |metric| data_points_len(metric) == 0,
+                            |metric| {
+                                let n = retain_metric_data_points(metric, |data_point, name| {
+                                    f(data_point, name, resource, scope)
+                                });
+                                removed += n;
+                                n
+                            },
+                        );
+                        len - scope_metrics.metrics.len()
+                    },
+                );
+                len - resource_metrics.scope_metrics.len()
+            },
+        );
+        removed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::opentelemetry::common::{AnyValue, AnyValueOneOfvalue};
+    use crate::opentelemetry::logs::{ResourceLogs, ScopeLogs, SeverityNumber};
+    use crate::opentelemetry::metrics::{Gauge, Histogram, ResourceMetrics, ScopeMetrics};
+    use crate::opentelemetry::trace::{ResourceSpans, ScopeSpans};
+
+    fn scope(name: &'static str) -> Option<InstrumentationScope<'static>> {
+        Some(InstrumentationScope {
+            name: name.into(),
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn retain_spans_prunes_empty_containers() {
+        let span = |name: &'static str| Span {
+            name: name.into(),
+            ..Default::default()
+        };
+        let mut request = ExportTraceServiceRequest {
+            resource_spans: vec![
+                ResourceSpans {
+                    scope_spans: vec![
+                        ScopeSpans {
+                            scope: scope("http"),
+                            spans: vec![span("GET /health"), span("GET /users")],
+                            ..Default::default()
+                        },
+                        ScopeSpans {
+                            scope: scope("probe"),
+                            spans: vec![span("GET /health")],
+                            ..Default::default()
+                        },
+                    ],
+                    ..Default::default()
+                },
+                ResourceSpans {
+                    scope_spans: vec![ScopeSpans {
+                        scope: scope("http"),
+                        spans: vec![span("GET /health")],
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        };
+
+        let mut scopes = vec![];
+        let removed = request.retain_spans(|span, _, scope| {
+            scopes.push(scope.unwrap().name.to_string());
+            span.name != "GET /health"
+        });
+        assert_eq!(removed, 3);
+        assert_eq!(scopes, ["http", "http", "probe", "http"]);
+        assert_eq!(request.resource_spans.len(), 1);
+        assert_eq!(request.resource_spans[0].scope_spans.len(), 1);
+        assert_eq!(
+            request.resource_spans[0].scope_spans[0].spans,
+            [span("GET /users")]
+        );
+
+        assert_eq!(request.retain_spans(|_, _, _| false), 1);
+        assert!(request.resource_spans.is_empty());
+    }
+
+    #[test]
+    fn retain_log_records_by_resource() {
+        let resource = |service: &'static str| {
+            Some(Resource {
+                attributes: vec![KeyValue {
+                    key: "service.name".into(),
+                    value: Some(AnyValue {
+                        value: AnyValueOneOfvalue::string_value(service.into()),
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                }],
+                ..Default::default()
+            })
+        };
+        let resource_logs = |service| ResourceLogs {
+            resource: resource(service),
+            scope_logs: vec![ScopeLogs {
+                log_records: vec![
+                    LogRecord {
+                        severity_number: SeverityNumber::SEVERITY_NUMBER_DEBUG,
+                        ..Default::default()
+                    },
+                    LogRecord {
+                        severity_number: SeverityNumber::SEVERITY_NUMBER_ERROR,
+                        ..Default::default()
+                    },
+                ],
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        let mut request = ExportLogsServiceRequest {
+            resource_logs: vec![resource_logs("api"), resource_logs("worker")],
+            ..Default::default()
+        };
+
+        // Drop the debug logs of the api only
+        let api = resource("api");
+        let removed = request.retain_log_records(|log_record, resource, _| {
+            resource != api.as_ref()
+                || log_record.severity_number != SeverityNumber::SEVERITY_NUMBER_DEBUG
+        });
+        assert_eq!(removed, 1);
+        assert_eq!(request.resource_logs[0].scope_logs[0].log_records.len(), 1);
+        assert_eq!(request.resource_logs[1].scope_logs[0].log_records.len(), 2);
+    }
+
+    #[test]
+    fn retain_metrics_and_data_points() {
+        let metric = |name: &'static str, data| Metric {
+            name: name.into(),
+            data,
+            ..Default::default()
+        };
+        let gauge = MetricOneOfdata::gauge(Gauge {
+            data_points: vec![
+                NumberDataPoint {
+                    time_unix_nano: 1,
+                    ..Default::default()
+                },
+                NumberDataPoint {
+                    time_unix_nano: 2,
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        });
+        let histogram = MetricOneOfdata::histogram(Histogram {
+            data_points: vec![HistogramDataPoint {
+                time_unix_nano: 1,
+                ..Default::default()
+            }],
+            ..Default::default()
+        });
+        let mut request = ExportMetricsServiceRequest {
+            resource_metrics: vec![ResourceMetrics {
+                scope_metrics: vec![ScopeMetrics {
+                    scope: scope("runtime"),
+                    metrics: vec![
+                        metric("memory", gauge),
+                        metric("latency", histogram),
+                        metric("debug.queue", MetricOneOfdata::None),
+                    ],
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        let removed = request.retain_metrics(|metric, _, _| !metric.name.starts_with("debug."));
+        assert_eq!(removed, 1);
+
+        let mut seen = vec![];
+        let removed = request.retain_data_points(|data_point, name, _, scope| {
+            assert_eq!(scope.unwrap().name, "runtime");
+            seen.push(name.to_string());
+            data_point.time_unix_nano() > 1
+        });
+        assert_eq!(removed, 2);
+        assert_eq!(seen, ["memory", "memory", "latency"]);
+        let metrics = &request.resource_metrics[0].scope_metrics[0].metrics;
+        assert_eq!(metrics.len(), 1);
+        assert_eq!(metrics[0].name, "memory");
+    }
+
+    #[test]
+    fn retain_keeps_containers_that_were_already_empty() {
+        let gauge = |data_points| {
+            MetricOneOfdata::gauge(Gauge {
+                data_points,
+                ..Default::default()
+            })
+        };
+        let metric = |name: &'static str, data| Metric {
+            name: name.into(),
+            data,
+            ..Default::default()
+        };
+        let mut request = ExportMetricsServiceRequest {
+            resource_metrics: vec![
+                ResourceMetrics {
+                    scope_metrics: vec![
+                        ScopeMetrics {
+                            scope: scope("runtime"),
+                            metrics: vec![
+                                metric("memory", gauge(vec![NumberDataPoint::default()])),
+                                metric("threads", gauge(vec![])),
+                                metric("unset", MetricOneOfdata::None),
+                            ],
+                            ..Default::default()
+                        },
+                        ScopeMetrics {
+                            scope: scope("http"),
+                            metrics: vec![metric(
+                                "latency",
+                                gauge(vec![NumberDataPoint::default()]),
+                            )],
+                            ..Default::default()
+                        },
+                        ScopeMetrics {
+                            scope: scope("empty"),
+                            ..Default::default()
+                        },
+                    ],
+                    ..Default::default()
+                },
+                ResourceMetrics {
+                    scope_metrics: vec![ScopeMetrics {
+                        scope: scope("http"),
+                        metrics: vec![metric("latency", gauge(vec![NumberDataPoint::default()]))],
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                },
+                ResourceMetrics::default(),
+            ],
+            ..Default::default()
+        };
+
+        let removed = request.retain_data_points(|_, name, _, _| name != "latency");
+        assert_eq!(removed, 2);
+        assert_eq!(request.resource_metrics.len(), 2);
+        assert_eq!(request.resource_metrics[1], ResourceMetrics::default());
+        let scope_metrics = &request.resource_metrics[0].scope_metrics;
+        assert_eq!(scope_metrics.len(), 2);
+        assert_eq!(scope_metrics[1].scope, scope("empty"));
+        let names = scope_metrics[0]
+            .metrics
+            .iter()
+            .map(|metric| metric.name.as_ref())
+            .collect::<Vec<_>>();
+        assert_eq!(names, ["memory", "threads", "unset"]);
+
+        // Nothing removed, nothing pruned
+        let mut request = ExportTraceServiceRequest {
+            resource_spans: vec![ResourceSpans {
+                scope_spans: vec![ScopeSpans::default()],
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        assert_eq!(request.retain_spans(|_, _, _| false), 0);
+        assert_eq!(request.resource_spans[0].scope_spans.len(), 1);
+    }
+}
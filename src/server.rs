@@ -0,0 +1,492 @@
+use std::convert::Infallible;
+use std::future::Future;
+use std::io;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use bytes::Bytes;
+use http_body_util::{BodyExt, Full, LengthLimitError, Limited};
+use hyper::body::Incoming;
+use hyper::service::service_fn;
+use hyper_util::rt::{TokioIo, TokioTimer};
+use hyper_util::server::graceful::GracefulShutdown;
+use tokio::net::{TcpListener, TcpStream};
+use tokio::task::JoinSet;
+
+use crate::compression::codings;
+use crate::grpc::{GrpcCode, GrpcStatus};
+use crate::http::{error_response, Encoding};
+use crate::{handle_otlp_http_with, DecodeOptions, ExportRequest, OtlpHttpResponse};
+
+/// Receives the requests accepted by a [`Server`].
+///
+/// Returning an error fails the export with the matching HTTP status, e.g.
+/// [`GrpcCode::Unavailable`] makes the client retry later.
+pub trait ExportHandler: Send + Sync + 'static {
+    fn export(&self, request: ExportRequest)
+        -> impl Future<Output = Result<(), GrpcStatus>> + Send;
+}
+
+/// Configuration of a [`Server`].
+#[derive(Debug, Clone)]
+pub struct ServerConfig {
+    pub addr: SocketAddr,
+    /// Maximum size of a request body as received, compressed or not.
+    pub max_body_bytes: usize,
+    /// Limits applied to the decompressed body and the decoded request.
+    pub decode_options: DecodeOptions,
+    /// Whether to accept compressed bodies, using the codecs enabled by
+    /// the `gzip`, `deflate` and `zstd` features.
+    pub compression: bool,
+    /// Time limit for receiving the headers of a request over HTTP/1.1.
+    pub header_read_timeout: Duration,
+    /// Time limit for receiving, decoding and exporting each request.
+    pub request_timeout: Duration,
+    /// How long to wait for in-flight requests after shutdown before
+    /// closing their connections.
+    pub shutdown_timeout: Duration,
+    /// Interval of the HTTP/2 pings the gRPC server sends to check that
+    /// a connection is still alive.
+    pub http2_keep_alive_interval: Duration,
+    /// Time limit for the acknowledgement of an HTTP/2 ping, after which
+    /// the connection is closed.
+    pub http2_keep_alive_timeout: Duration,
+}
+
+impl Default for ServerConfig {
+    fn default() -> Self {
+        ServerConfig {
+            addr: SocketAddr::from(([127, 0, 0, 1], 4318)),
+            max_body_bytes: 16 * 1024 * 1024,
+            decode_options: DecodeOptions::default(),
+            compression: true,
+            header_read_timeout: Duration::from_secs(30),
+            request_timeout: Duration::from_secs(60),
+            shutdown_timeout: Duration::from_secs(30),
+            http2_keep_alive_interval: Duration::from_secs(60),
+            http2_keep_alive_timeout: Duration::from_secs(20),
+        }
+    }
+}
+
+/// A minimal OTLP/HTTP receiver for logs, metrics and traces.
+///
+/// Requests are handled by [`crate::handle_otlp_http_with`] on the blocking
+/// thread pool, and the valid part of each is passed to the
+/// [`ExportHandler`].
+pub struct Server {
+    listener: TcpListener,
+    config: ServerConfig,
+}
+
+impl Server {
+    pub async fn bind(config: ServerConfig) -> io::Result<Self> {
+        let listener = TcpListener::bind(config.addr).await?;
+        Ok(Server { listener, config })
+    }
+
+    /// The address the server listens on, e.g. when bound to port 0.
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    /// Serves requests until `shutdown` completes, then stops accepting
+    /// connections and waits for the in-flight requests to finish, for at
+    /// most `shutdown_timeout`.
+    ///
+    /// Failing to accept a connection is logged and does not stop the
+    /// server.
+    pub async fn serve<H: ExportHandler>(
+        self,
+        handler: H,
+        shutdown: impl Future<Output = ()>,
+    ) -> io::Result<()> {
+        let handler = Arc::new(handler);
+        let config = Arc::new(self.config);
+        let graceful = GracefulShutdown::new();
+        let mut connections = JoinSet::new();
+        let mut builder = hyper::server::conn::http1::Builder::new();
+        builder
+            .timer(TokioTimer::new())
+            .header_read_timeout(config.header_read_timeout);
+        tokio::pin!(shutdown);
+
+        loop {
+            let stream = tokio::select! {
+                stream = accept(&self.listener) => stream,
+                () = &mut shutdown => break,
+            };
+            while connections.try_join_next().is_some() {}
+
+            let handler = handler.clone();
+            let config = config.clone();
+            let service = service_fn(move |request| {
+                let handler = handler.clone();
+                let config = config.clone();
+                async move { Ok::<_, Infallible>(handle(request, &*handler, &config).await) }
+            });
+            let connection = builder.serve_connection(TokioIo::new(stream), service);
+            let connection = graceful.watch(connection);
+            connections.spawn(async move {
+                // Errors only affect the one connection
+                let _ = connection.await;
+            });
+        }
+
+        shutdown_within(graceful, connections, config.shutdown_timeout).await;
+        Ok(())
+    }
+}
+
+// Waits for the connections to finish their in-flight requests, then
+// closes whichever are still open after `timeout`
+pub(crate) async fn shutdown_within(
+    graceful: GracefulShutdown,
+    mut connections: JoinSet<()>,
+    timeout: Duration,
+) {
+    if tokio::time::timeout(timeout, graceful.shutdown())
+        .await
+        .is_err()
+    {
+        log::warn!(
+            "closing {} connections still busy after {timeout:?}",
+            connections.len()
+        );
+        connections.shutdown().await;
+    }
+}
+
+// How long to wait before accepting again when out of file descriptors or
+// other resources
+const ACCEPT_BACKOFF: Duration = Duration::from_millis(100);
+
+// Accepts the next connection. Errors are specific to the connection
+// being accepted, or else temporary, such as running out of file
+// descriptors, so they are logged and retried.
+pub(crate) async fn accept(listener: &TcpListener) -> TcpStream {
+    loop {
+        match listener.accept().await {
+            Ok((stream, _)) => return stream,
+            Err(e) => {
+                log::warn!("failed to accept a connection: {e}");
+                let per_connection = matches!(
+                    e.kind(),
+                    io::ErrorKind::ConnectionAborted
+                        | io::ErrorKind::ConnectionRefused
+                        | io::ErrorKind::ConnectionReset
+                        | io::ErrorKind::Interrupted
+                );
+                if !per_connection {
+                    tokio::time::sleep(ACCEPT_BACKOFF).await;
+                }
+            }
+        }
+    }
+}
+
+async fn handle<H: ExportHandler>(
+    request: hyper::Request<Incoming>,
+    handler: &H,
+    config: &ServerConfig,
+) -> hyper::Response<Full<Bytes>> {
+    let response = tokio::time::timeout(config.request_timeout, receive(request, handler, config))
+        .await
+        .unwrap_or_else(|_| {
+            let status = GrpcStatus::new(GrpcCode::DeadlineExceeded, "request timed out");
+            error_response(408, &status, Encoding::Protobuf)
+        });
+
+    hyper::Response::builder()
+        .status(response.status)
+        .header(hyper::header::CONTENT_TYPE, response.content_type)
+        .body(Full::new(Bytes::from(response.body)))
+        .expect("status and content type are valid")
+}
+
+async fn receive<H: ExportHandler>(
+    request: hyper::Request<Incoming>,
+    handler: &H,
+    config: &ServerConfig,
+) -> OtlpHttpResponse {
+    let (parts, body) = request.into_parts();
+    match Limited::new(body, config.max_body_bytes).collect().await {
+        Ok(body) => {
+            let headers: Vec<_> = parts
+                .headers
+                .iter()
+                .filter_map(|(name, value)| {
+                    Some((name.as_str().to_owned(), value.to_str().ok()?.to_owned()))
+                })
+                .collect();
+            let compressed = !config.compression
+                && parts
+                    .headers
+                    .get_all(hyper::header::CONTENT_ENCODING)
+                    .iter()
+                    .any(|value| {
+                        value
+                            .to_str()
+                            .map_or(true, |value| codings(value).next().is_some())
+                    });
+            let encoding = encoding_of(&headers);
+            if compressed {
+                let status = GrpcStatus::new(GrpcCode::Unimplemented, "compression is disabled");
+                return error_response(415, &status, encoding);
+            }
+
+            // Decompressing, decoding and validating are CPU-bound
+            let options = config.decode_options.clone();
+            let response = tokio::task::spawn_blocking(move || {
+                let headers: Vec<_> = headers
+                    .iter()
+                    .map(|(name, value)| (name.as_str(), value.as_str()))
+                    .collect();
+                handle_otlp_http_with(
+                    parts.method.as_str(),
+                    parts.uri.path(),
+                    &headers,
+                    &body.to_bytes(),
+                    &options,
+                )
+            })
+            .await;
+            match response {
+                Ok(response) => export(response, handler).await,
+                Err(e) => {
+                    let status = GrpcStatus::new(GrpcCode::Internal, e.to_string());
+                    error_response(500, &status, encoding)
+                }
+            }
+        }
+        Err(e) if e.is::<LengthLimitError>() => {
+            let status = GrpcStatus::new(
+                GrpcCode::ResourceExhausted,
+                format!("body exceeds {} bytes", config.max_body_bytes),
+            );
+            error_response(413, &status, Encoding::Protobuf)
+        }
+        Err(e) => {
+            let status = GrpcStatus::new(
+                GrpcCode::InvalidArgument,
+                format!("failed to read the body: {e}"),
+            );
+            error_response(400, &status, Encoding::Protobuf)
+        }
+    }
+}
+
+async fn export<H: ExportHandler>(mut response: OtlpHttpResponse, handler: &H) -> OtlpHttpResponse {
+    let Some(request) = response.request.take() else {
+        return response;
+    };
+
+    match handler.export(request).await {
+        Ok(()) => response,
+        Err(status) => {
+            let http_status = match status.code {
+                GrpcCode::InvalidArgument => 400,
+                GrpcCode::ResourceExhausted => 429,
+                GrpcCode::Unavailable => 503,
+                _ => 500,
+            };
+            let encoding = Encoding::from_content_type(response.content_type);
+            error_response(http_status, &status, encoding.unwrap_or(Encoding::Protobuf))
+        }
+    }
+}
+
+fn encoding_of(headers: &[(String, String)]) -> Encoding {
+    headers
+        .iter()
+        .find(|(name, _)| *name == "content-type")
+        .and_then(|(_, value)| Encoding::from_content_type(value))
+        .unwrap_or(Encoding::Protobuf)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::opentelemetry::trace::ExportTraceServiceRequest;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::sync::{mpsc, oneshot};
+
+    struct Channel(mpsc::UnboundedSender<ExportRequest>);
+
+    impl ExportHandler for Channel {
+        async fn export(&self, request: ExportRequest) -> Result<(), GrpcStatus> {
+            match request {
+                ExportRequest::Logs(_) => Err(GrpcStatus::new(GrpcCode::Unavailable, "full")),
+                request => {
+                    self.0.send(request).unwrap();
+                    Ok(())
+                }
+            }
+        }
+    }
+
+    async fn post(addr: SocketAddr, path: &str, headers: &str, body: &[u8]) -> String {
+        let mut stream = tokio::net::TcpStream::connect(addr).await.unwrap();
+        let head = format!(
+            "POST {path} HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\
+             Content-Type: application/x-protobuf\r\n{headers}Content-Length: {}\r\n\r\n",
+            body.len()
+        );
+        stream.write_all(head.as_bytes()).await.unwrap();
+        stream.write_all(body).await.unwrap();
+        let mut response = vec![];
+        stream.read_to_end(&mut response).await.unwrap();
+        String::from_utf8_lossy(&response).into_owned()
+    }
+
+    #[tokio::test]
+    async fn serve_until_shutdown() {
+        let config = ServerConfig {
+            addr: SocketAddr::from(([127, 0, 0, 1], 0)),
+            max_body_bytes: 64,
+            ..Default::default()
+        };
+        let server = Server::bind(config).await.unwrap();
+        let addr = server.local_addr().unwrap();
+        let (requests, mut received) = mpsc::unbounded_channel();
+        let (stop, stopped) = oneshot::channel::<()>();
+        let serve = tokio::spawn(server.serve(Channel(requests), async {
+            let _ = stopped.await;
+        }));
+
+        let response = post(addr, "/v1/traces", "", &[]).await;
+        assert!(response.starts_with("HTTP/1.1 200"), "{response}");
+        assert_eq!(
+            received.recv().await,
+            Some(ExportRequest::Traces(ExportTraceServiceRequest::default()))
+        );
+
+        let response = post(addr, "/v1/logs", "", &[]).await;
+        assert!(response.starts_with("HTTP/1.1 503"), "{response}");
+        let response = post(addr, "/v1/traces", "", &[0; 65]).await;
+        assert!(response.starts_with("HTTP/1.1 413"), "{response}");
+
+        stop.send(()).unwrap();
+        serve.await.unwrap().unwrap();
+        assert!(tokio::net::TcpStream::connect(addr).await.is_err());
+    }
+
+    // Never finishes an export
+    struct Stuck;
+
+    impl ExportHandler for Stuck {
+        async fn export(&self, _: ExportRequest) -> Result<(), GrpcStatus> {
+            std::future::pending().await
+        }
+    }
+
+    #[tokio::test]
+    async fn timeouts() {
+        let config = ServerConfig {
+            addr: SocketAddr::from(([127, 0, 0, 1], 0)),
+            header_read_timeout: Duration::from_millis(100),
+            request_timeout: Duration::from_millis(200),
+            shutdown_timeout: Duration::from_millis(100),
+            ..Default::default()
+        };
+        let server = Server::bind(config).await.unwrap();
+        let addr = server.local_addr().unwrap();
+        let (stop, stopped) = oneshot::channel::<()>();
+        let serve = tokio::spawn(server.serve(Stuck, async {
+            let _ = stopped.await;
+        }));
+
+        // Headers that never end
+        let mut stream = tokio::net::TcpStream::connect(addr).await.unwrap();
+        stream
+            .write_all(b"POST /v1/traces HTTP/1.1\r\n")
+            .await
+            .unwrap();
+        let mut response = vec![];
+        let closed =
+            tokio::time::timeout(Duration::from_secs(5), stream.read_to_end(&mut response));
+        closed.await.unwrap().unwrap();
+
+        // A body that never ends
+        let mut stream = tokio::net::TcpStream::connect(addr).await.unwrap();
+        let head = "POST /v1/traces HTTP/1.1\r\nHost: localhost\r\n\
+                    Content-Type: application/x-protobuf\r\nContent-Length: 10\r\n\r\n";
+        stream.write_all(head.as_bytes()).await.unwrap();
+        let mut response = [0; 12];
+        stream.read_exact(&mut response).await.unwrap();
+        assert_eq!(&response, b"HTTP/1.1 408");
+
+        // A body cut short is not mistaken for one that is too large
+        let mut stream = tokio::net::TcpStream::connect(addr).await.unwrap();
+        stream.write_all(head.as_bytes()).await.unwrap();
+        stream.write_all(&[0; 5]).await.unwrap();
+        stream.shutdown().await.unwrap();
+        let mut response = vec![];
+        stream.read_to_end(&mut response).await.unwrap();
+        let response = String::from_utf8_lossy(&response);
+        assert!(response.starts_with("HTTP/1.1 400"), "{response}");
+
+        stop.send(()).unwrap();
+        serve.await.unwrap().unwrap();
+    }
+
+    #[tokio::test]
+    async fn shutdown_deadline() {
+        let config = ServerConfig {
+            addr: SocketAddr::from(([127, 0, 0, 1], 0)),
+            shutdown_timeout: Duration::from_millis(100),
+            ..Default::default()
+        };
+        let server = Server::bind(config).await.unwrap();
+        let addr = server.local_addr().unwrap();
+        let (stop, stopped) = oneshot::channel::<()>();
+        let serve = tokio::spawn(server.serve(Stuck, async {
+            let _ = stopped.await;
+        }));
+
+        // An export that never finishes does not hold up the shutdown
+        let stuck = tokio::spawn(post(addr, "/v1/traces", "", &[]));
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        stop.send(()).unwrap();
+        let serve = tokio::time::timeout(Duration::from_secs(5), serve).await;
+        serve.unwrap().unwrap().unwrap();
+        assert_eq!(stuck.await.unwrap(), "");
+    }
+
+    #[tokio::test]
+    async fn reject_compressed_bodies_when_disabled() {
+        let config = ServerConfig {
+            addr: SocketAddr::from(([127, 0, 0, 1], 0)),
+            compression: false,
+            ..Default::default()
+        };
+        let server = Server::bind(config).await.unwrap();
+        let addr = server.local_addr().unwrap();
+        let (requests, _received) = mpsc::unbounded_channel();
+        let (stop, stopped) = oneshot::channel::<()>();
+        let serve = tokio::spawn(server.serve(Channel(requests), async {
+            let _ = stopped.await;
+        }));
+
+        for encoding in ["Identity", "identity, IDENTITY", ""] {
+            let headers = format!("Content-Encoding: {encoding}\r\n");
+            let response = post(addr, "/v1/traces", &headers, &[]).await;
+            assert!(
+                response.starts_with("HTTP/1.1 200"),
+                "{encoding}: {response}"
+            );
+        }
+        for encoding in ["gzip", "identity, gzip", "GZIP"] {
+            let headers = format!("Content-Encoding: {encoding}\r\n");
+            let response = post(addr, "/v1/traces", &headers, &[]).await;
+            assert!(
+                response.starts_with("HTTP/1.1 415"),
+                "{encoding}: {response}"
+            );
+        }
+
+        stop.send(()).unwrap();
+        serve.await.unwrap().unwrap();
+    }
+}
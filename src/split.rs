@@ -0,0 +1,308 @@
+use std::borrow::Cow;
+use std::ops::Range;
+
+use quick_protobuf::sizeofs::sizeof_varint;
+use quick_protobuf::{BytesReader, Writer};
+
+use crate::legacy::TAG_INSTRUMENTATION_LIBRARY_ITEMS;
+use crate::opentelemetry::logs::ExportLogsServiceRequest;
+use crate::opentelemetry::metrics::ExportMetricsServiceRequest;
+use crate::opentelemetry::trace::ExportTraceServiceRequest;
+use crate::stream::{TAG_RESOURCE_ITEMS, TAG_SCOPE_ITEMS};
+
+// One field of an encoded message, tag and length prefix included
+struct Entry {
+    tag: u32,
+    range: Range<usize>,
+}
+
+fn entries(bytes: &[u8]) -> Result<Vec<Entry>, crate::Error> {
+    let mut entries = vec![];
+    let mut reader = BytesReader::from_bytes(bytes);
+    while !reader.is_eof() {
+        let start = bytes.len() - reader.len();
+        let tag = reader.next_tag(bytes)?;
+        reader.read_unknown(bytes, tag)?;
+        let end = bytes.len() - reader.len();
+        entries.push(Entry {
+            tag,
+            range: start..end,
+        });
+    }
+    Ok(entries)
+}
+
+// The value of a length-delimited entry
+fn value<'a>(bytes: &'a [u8], entry: &Entry) -> Result<&'a [u8], crate::Error> {
+    let entry = &bytes[entry.range.clone()];
+    let mut reader = BytesReader::from_bytes(entry);
+    reader.next_tag(entry)?;
+    Ok(reader.read_bytes(entry)?)
+}
+
+fn split_encoded(bytes: &[u8], max_bytes: usize) -> Result<Vec<Cow<'_, [u8]>>, crate::Error> {
+    let mut requests = vec![];
+    let mut current = 0..0;
+    for entry in entries(bytes)? {
+        if entry.range.end - current.start <= max_bytes {
+            current.end = entry.range.end;
+            continue;
+        }
+
+        if !current.is_empty() {
+            requests.push(Cow::Borrowed(&bytes[current.clone()]));
+        }
+        if entry.tag == TAG_RESOURCE_ITEMS && entry.range.len() > max_bytes {
+            split_resource(value(bytes, &entry)?, max_bytes, &mut requests)?;
+            current = entry.range.end..entry.range.end;
+        } else {
+            current = entry.range;
+        }
+    }
+    if !current.is_empty() {
+        requests.push(Cow::Borrowed(&bytes[current]));
+    }
+    Ok(requests)
+}
+
+// Splits one `Resource*` message into requests of one resource each, which
+// repeat its other fields around a share of its scopes. Pre-1.0
+// `instrumentation_library_*` entries are scopes too, and are kept as such.
+fn split_resource(
+    resource: &[u8],
+    max_bytes: usize,
+    requests: &mut Vec<Cow<'_, [u8]>>,
+) -> Result<(), crate::Error> {
+    let mut context = vec![];
+    let mut scopes = vec![];
+    for entry in entries(resource)? {
+        if entry.tag == TAG_SCOPE_ITEMS || entry.tag == TAG_INSTRUMENTATION_LIBRARY_ITEMS {
+            scopes.push(&resource[entry.range]);
+        } else {
+            context.extend_from_slice(&resource[entry.range]);
+        }
+    }
+
+    let size = |scopes_len: usize| {
+        let len = context.len() + scopes_len;
+        1 + sizeof_varint(len as u64) + len
+    };
+    let mut start = 0;
+    let mut scopes_len = 0;
+    for (i, scope) in scopes.iter().enumerate() {
+        if i > start && size(scopes_len + scope.len()) > max_bytes {
+            requests.push(Cow::Owned(write_resource(&context, &scopes[start..i])));
+            start = i;
+            scopes_len = 0;
+        }
+        scopes_len += scope.len();
+    }
+    requests.push(Cow::Owned(write_resource(&context, &scopes[start..])));
+    Ok(())
+}
+
+fn write_resource(context: &[u8], scopes: &[&[u8]]) -> Vec<u8> {
+    let len = context.len() + scopes.iter().map(|scope| scope.len()).sum::<usize>();
+    let mut out = Vec::with_capacity(1 + sizeof_varint(len as u64) + len);
+    let mut w = Writer::new(&mut out);
+    w.write_tag(TAG_RESOURCE_ITEMS)
+        .and_then(|()| w.write_varint(len as u64))
+        .expect("writing to a Vec cannot fail");
+    out.extend_from_slice(context);
+    for scope in scopes {
+        out.extend_from_slice(scope);
+    }
+    out
+}
+
+impl ExportTraceServiceRequest<'_> {
+    /// Splits an encoded request into encoded requests of at most
+    /// `max_bytes` each, without decoding any span.
+    ///
+    /// Runs of whole `ResourceSpans` are borrowed from `bytes`. A resource
+    /// larger than `max_bytes` is split between its `ScopeSpans` instead,
+    /// copying the resource into each part; a single scope that is still
+    /// too large is returned on its own, over the limit.
+    pub fn split_encoded(
+        bytes: &[u8],
+        max_bytes: usize,
+    ) -> Result<Vec<Cow<'_, [u8]>>, crate::Error> {
+        split_encoded(bytes, max_bytes)
+    }
+}
+
+impl ExportLogsServiceRequest<'_> {
+    /// Splits an encoded request into encoded requests of at most
+    /// `max_bytes` each, without decoding any log record.
+    ///
+    /// Runs of whole `ResourceLogs` are borrowed from `bytes`. A resource
+    /// larger than `max_bytes` is split between its `ScopeLogs` instead,
+    /// copying the resource into each part; a single scope that is still
+    /// too large is returned on its own, over the limit.
+    pub fn split_encoded(
+        bytes: &[u8],
+        max_bytes: usize,
+    ) -> Result<Vec<Cow<'_, [u8]>>, crate::Error> {
+        split_encoded(bytes, max_bytes)
+    }
+}
+
+impl ExportMetricsServiceRequest<'_> {
+    /// Splits an encoded request into encoded requests of at most
+    /// `max_bytes` each, without decoding any metric.
+    ///
+    /// Runs of whole `ResourceMetrics` are borrowed from `bytes`. A resource
+    /// larger than `max_bytes` is split between its `ScopeMetrics` instead,
+    /// copying the resource into each part; a single scope that is still
+    /// too large is returned on its own, over the limit.
+    pub fn split_encoded(
+        bytes: &[u8],
+        max_bytes: usize,
+    ) -> Result<Vec<Cow<'_, [u8]>>, crate::Error> {
+        split_encoded(bytes, max_bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::opentelemetry::common::{InstrumentationScope, Resource};
+    use crate::opentelemetry::trace::{ResourceSpans, ScopeSpans, Span};
+
+    fn resource_spans(name: &str, scopes: usize) -> ResourceSpans<'static> {
+        ResourceSpans {
+            resource: Some(Resource {
+                dropped_attributes_count: 1,
+                ..Default::default()
+            }),
+            scope_spans: (0..scopes)
+                .map(|i| ScopeSpans {
+                    scope: Some(InstrumentationScope {
+                        name: format!("{name}-{i}").into(),
+                        ..Default::default()
+                    }),
+                    spans: vec![Span {
+                        name: "span".into(),
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                })
+                .collect(),
+            schema_url: "https://some_url.com".into(),
+            ..Default::default()
+        }
+    }
+
+    fn decode(bytes: &[u8]) -> ExportTraceServiceRequest<'_> {
+        ExportTraceServiceRequest::try_from(bytes).unwrap()
+    }
+
+    #[test]
+    fn split_between_resources() {
+        let request = ExportTraceServiceRequest {
+            resource_spans: vec![
+                resource_spans("a", 1),
+                resource_spans("b", 1),
+                resource_spans("c", 1),
+            ],
+            ..Default::default()
+        };
+        let bytes = request.encode_to_vec();
+        let resource_len = bytes.len() / 3;
+
+        let parts = ExportTraceServiceRequest::split_encoded(&bytes, 2 * resource_len).unwrap();
+        assert_eq!(parts.len(), 2);
+        assert!(parts.iter().all(|part| matches!(part, Cow::Borrowed(_))));
+        assert_eq!(parts.concat(), bytes);
+        assert_eq!(
+            decode(&parts[0]).resource_spans,
+            request.resource_spans[..2]
+        );
+        assert_eq!(
+            decode(&parts[1]).resource_spans,
+            request.resource_spans[2..]
+        );
+
+        let parts = ExportTraceServiceRequest::split_encoded(&bytes, bytes.len()).unwrap();
+        assert_eq!(parts, [Cow::Borrowed(&bytes[..])]);
+        assert!(ExportTraceServiceRequest::split_encoded(&[], 1)
+            .unwrap()
+            .is_empty());
+        assert!(ExportTraceServiceRequest::split_encoded(&bytes[..bytes.len() - 1], 1).is_err());
+    }
+
+    #[test]
+    fn split_large_resource_between_scopes() {
+        let small = resource_spans("a", 1);
+        let large = resource_spans("b", 5);
+        let request = ExportTraceServiceRequest {
+            resource_spans: vec![small.clone(), large.clone()],
+            ..Default::default()
+        };
+        let bytes = request.encode_to_vec();
+        let max_bytes = crate::encode::encode_to_vec(&large).len() / 2;
+
+        let parts = ExportTraceServiceRequest::split_encoded(&bytes, max_bytes).unwrap();
+        assert!(parts.len() > 2);
+        assert!(matches!(parts[0], Cow::Borrowed(_)));
+        assert_eq!(decode(&parts[0]).resource_spans, [small]);
+
+        let mut scope_spans = vec![];
+        for part in &parts[1..] {
+            assert!(matches!(part, Cow::Owned(_)));
+            assert!(part.len() <= max_bytes, "{} > {max_bytes}", part.len());
+            let mut resource_spans = decode(part).resource_spans;
+            assert_eq!(resource_spans.len(), 1);
+            let mut resource_spans = resource_spans.remove(0);
+            scope_spans.append(&mut resource_spans.scope_spans);
+            assert_eq!(
+                resource_spans,
+                ResourceSpans {
+                    scope_spans: vec![],
+                    ..large.clone()
+                }
+            );
+        }
+        assert_eq!(scope_spans, large.scope_spans);
+
+        // A scope over the limit is kept whole
+        let parts = ExportTraceServiceRequest::split_encoded(&bytes, 1).unwrap();
+        assert_eq!(parts.len(), 6);
+    }
+
+    #[test]
+    fn split_large_legacy_resource_between_scopes() {
+        let large = resource_spans("b", 5);
+        let encoded = crate::encode::encode_to_vec(&large);
+        // The same resource, with its scopes as pre-1.0 instrumentation
+        // library entries
+        let mut legacy = vec![];
+        for entry in entries(&encoded).unwrap() {
+            let mut w = Writer::new(&mut legacy);
+            if entry.tag == TAG_SCOPE_ITEMS {
+                w.write_tag(TAG_INSTRUMENTATION_LIBRARY_ITEMS).unwrap();
+                w.write_bytes(value(&encoded, &entry).unwrap()).unwrap();
+            } else {
+                legacy.extend_from_slice(&encoded[entry.range]);
+            }
+        }
+        let mut bytes = vec![];
+        let mut w = Writer::new(&mut bytes);
+        w.write_tag(TAG_RESOURCE_ITEMS).unwrap();
+        w.write_bytes(&legacy).unwrap();
+        let max_bytes = bytes.len() / 2;
+
+        let parts = ExportTraceServiceRequest::split_encoded(&bytes, max_bytes).unwrap();
+        assert!(parts.len() > 1);
+        let mut scope_spans = vec![];
+        for part in &parts {
+            assert!(part.len() <= max_bytes, "{} > {max_bytes}", part.len());
+            let mut resource_spans = ExportTraceServiceRequest::decode_legacy(part)
+                .unwrap()
+                .resource_spans;
+            assert_eq!(resource_spans.len(), 1);
+            scope_spans.append(&mut resource_spans[0].scope_spans);
+        }
+        assert_eq!(scope_spans, large.scope_spans);
+    }
+}
@@ -0,0 +1,410 @@
+use std::borrow::Cow;
+use std::marker::PhantomData;
+
+use quick_protobuf::{BytesReader, MessageRead};
+
+use crate::opentelemetry::common::Resource;
+use crate::opentelemetry::logs::{ExportLogsServiceRequest, ResourceLogs, ScopeLogs};
+use crate::opentelemetry::metrics::{ExportMetricsServiceRequest, ResourceMetrics, ScopeMetrics};
+use crate::opentelemetry::trace::{ExportTraceServiceRequest, ResourceSpans, ScopeSpans};
+use crate::UnknownFields;
+
+// Field tags shared by all three signals: `resource_*` in the export
+// request, and `resource`, `scope_*` and `schema_url` in `Resource*`
+pub(crate) const TAG_RESOURCE_ITEMS: u32 = 10;
+const TAG_RESOURCE: u32 = 10;
+pub(crate) const TAG_SCOPE_ITEMS: u32 = 18;
+const TAG_SCHEMA_URL: u32 = 26;
+
+/// Iterates over the length-delimited occurrences of one field of an
+/// encoded message without decoding anything else.
+pub(crate) struct FieldSlices<'a> {
+    bytes: &'a [u8],
+    reader: BytesReader,
+    tag: u32,
+    done: bool,
+}
+
+impl<'a> FieldSlices<'a> {
+    pub(crate) fn new(bytes: &'a [u8], tag: u32) -> Self {
+        FieldSlices {
+            bytes,
+            reader: BytesReader::from_bytes(bytes),
+            tag,
+            done: false,
+        }
+    }
+}
+
+impl<'a> Iterator for FieldSlices<'a> {
+    type Item = Result<&'a [u8], crate::Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        while !self.reader.is_eof() {
+            let result = match self.reader.next_tag(self.bytes) {
+                Ok(tag) if tag == self.tag => self.reader.read_bytes(self.bytes).map(Some),
+                Ok(tag) => self.reader.read_unknown(self.bytes, tag).map(|_| None),
+                Err(e) => Err(e),
+            };
+
+            match result {
+                Ok(Some(slice)) => return Some(Ok(slice)),
+                Ok(None) => continue,
+                Err(e) => {
+                    self.done = true;
+                    return Some(Err(e.into()));
+                }
+            }
+        }
+
+        self.done = true;
+        None
+    }
+}
+
+pub(crate) fn read_message<'a, M: MessageRead<'a>>(bytes: &'a [u8]) -> Result<M, crate::Error> {
+    let mut reader = BytesReader::from_bytes(bytes);
+    Ok(M::from_reader(&mut reader, bytes)?)
+}
+
+/// Streams the `resource_*` entries of an encoded export request, decoding
+/// one `ResourceSpans`/`ResourceLogs`/`ResourceMetrics` per iteration.
+///
+/// Only the yielded entry is held in memory, so each one can be validated
+/// and processed before the rest of the request has been decoded. The
+/// iterator stops after the first error.
+pub struct ResourceStream<'a, M> {
+    fields: FieldSlices<'a>,
+    _message: PhantomData<M>,
+}
+
+impl<'a, M: MessageRead<'a>> ResourceStream<'a, M> {
+    fn new(bytes: &'a [u8]) -> Self {
+        ResourceStream {
+            fields: FieldSlices::new(bytes, TAG_RESOURCE_ITEMS),
+            _message: PhantomData,
+        }
+    }
+}
+
+impl<'a, M: MessageRead<'a>> Iterator for ResourceStream<'a, M> {
+    type Item = Result<M, crate::Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let item = self.fields.next()?.and_then(read_message);
+        if item.is_err() {
+            self.fields.done = true;
+        }
+        Some(item)
+    }
+}
+
+/// A `Resource*` message that can be rebuilt around a single scope entry.
+pub trait ScopedResource<'a>: MessageRead<'a> {
+    type Scope: MessageRead<'a>;
+
+    fn from_scope(
+        resource: Option<Resource<'a>>,
+        schema_url: Cow<'a, str>,
+        unknown_fields: UnknownFields,
+        scope: Self::Scope,
+    ) -> Self;
+}
+
+impl<'a> ScopedResource<'a> for ResourceSpans<'a> {
+    type Scope = ScopeSpans<'a>;
+
+    fn from_scope(
+        resource: Option<Resource<'a>>,
+        schema_url: Cow<'a, str>,
+        unknown_fields: UnknownFields,
+        scope: Self::Scope,
+    ) -> Self {
+        ResourceSpans {
+            resource,
+            scope_spans: vec![scope],
+            schema_url,
+            unknown_fields,
+        }
+    }
+}
+
+impl<'a> ScopedResource<'a> for ResourceLogs<'a> {
+    type Scope = ScopeLogs<'a>;
+
+    fn from_scope(
+        resource: Option<Resource<'a>>,
+        schema_url: Cow<'a, str>,
+        unknown_fields: UnknownFields,
+        scope: Self::Scope,
+    ) -> Self {
+        ResourceLogs {
+            resource,
+            scope_logs: vec![scope],
+            schema_url,
+            unknown_fields,
+        }
+    }
+}
+
+impl<'a> ScopedResource<'a> for ResourceMetrics<'a> {
+    type Scope = ScopeMetrics<'a>;
+
+    fn from_scope(
+        resource: Option<Resource<'a>>,
+        schema_url: Cow<'a, str>,
+        unknown_fields: UnknownFields,
+        scope: Self::Scope,
+    ) -> Self {
+        ResourceMetrics {
+            resource,
+            scope_metrics: vec![scope],
+            schema_url,
+            unknown_fields,
+        }
+    }
+}
+
+struct ScopeContext<'a> {
+    resource: Option<Resource<'a>>,
+    schema_url: Cow<'a, str>,
+    unknown_fields: UnknownFields,
+    scopes: FieldSlices<'a>,
+}
+
+impl<'a> ScopeContext<'a> {
+    // Reads the resource, schema url and unknown fields of an encoded
+    // `Resource*` message, skipping over its scopes, which are streamed
+    // afterwards
+    fn new(bytes: &'a [u8]) -> Result<Self, crate::Error> {
+        let mut context = ScopeContext {
+            resource: None,
+            schema_url: Cow::Borrowed(""),
+            unknown_fields: UnknownFields::default(),
+            scopes: FieldSlices::new(bytes, TAG_SCOPE_ITEMS),
+        };
+
+        let mut reader = BytesReader::from_bytes(bytes);
+        while !reader.is_eof() {
+            match reader.next_tag(bytes)? {
+                TAG_RESOURCE => context.resource = Some(reader.read_message(bytes)?),
+                TAG_SCHEMA_URL => context.schema_url = Cow::Borrowed(reader.read_string(bytes)?),
+                TAG_SCOPE_ITEMS => reader.read_unknown(bytes, TAG_SCOPE_ITEMS)?,
+                tag => context.unknown_fields.read(&mut reader, bytes, tag)?,
+            }
+        }
+
+        Ok(context)
+    }
+}
+
+/// Streams an encoded export request one scope at a time.
+///
+/// Every item is a `Resource*` message holding exactly one `Scope*` entry
+/// along with its resource, schema url and unknown fields, so it can be
+/// validated and handled on its own. The iterator stops after the first error.
+pub struct ScopeStream<'a, M> {
+    resources: FieldSlices<'a>,
+    current: Option<ScopeContext<'a>>,
+    _message: PhantomData<M>,
+}
+
+impl<'a, M: ScopedResource<'a>> ScopeStream<'a, M> {
+    fn new(bytes: &'a [u8]) -> Self {
+        ScopeStream {
+            resources: FieldSlices::new(bytes, TAG_RESOURCE_ITEMS),
+            current: None,
+            _message: PhantomData,
+        }
+    }
+
+    fn fail(&mut self, error: crate::Error) -> Option<Result<M, crate::Error>> {
+        self.resources.done = true;
+        self.current = None;
+        Some(Err(error))
+    }
+}
+
+impl<'a, M: ScopedResource<'a>> Iterator for ScopeStream<'a, M> {
+    type Item = Result<M, crate::Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(context) = &mut self.current {
+                match context.scopes.next() {
+                    Some(Ok(bytes)) => {
+                        return match read_message(bytes) {
+                            Ok(scope) => Some(Ok(M::from_scope(
+                                context.resource.clone(),
+                                context.schema_url.clone(),
+                                context.unknown_fields.clone(),
+                                scope,
+                            ))),
+                            Err(e) => self.fail(e),
+                        }
+                    }
+                    Some(Err(e)) => return self.fail(e),
+                    None => self.current = None,
+                }
+            }
+
+            match self.resources.next()? {
+                Ok(bytes) => match ScopeContext::new(bytes) {
+                    Ok(context) => self.current = Some(context),
+                    Err(e) => return self.fail(e),
+                },
+                Err(e) => return self.fail(e),
+            }
+        }
+    }
+}
+
+impl<'a> ExportTraceServiceRequest<'a> {
+    /// Lazily decodes an encoded request one `ResourceSpans` at a time.
+    pub fn stream_resources(bytes: &'a [u8]) -> ResourceStream<'a, ResourceSpans<'a>> {
+        ResourceStream::new(bytes)
+    }
+
+    /// Lazily decodes an encoded request one `ScopeSpans` at a time.
+    pub fn stream_scopes(bytes: &'a [u8]) -> ScopeStream<'a, ResourceSpans<'a>> {
+        ScopeStream::new(bytes)
+    }
+}
+
+impl<'a> ExportLogsServiceRequest<'a> {
+    /// Lazily decodes an encoded request one `ResourceLogs` at a time.
+    pub fn stream_resources(bytes: &'a [u8]) -> ResourceStream<'a, ResourceLogs<'a>> {
+        ResourceStream::new(bytes)
+    }
+
+    /// Lazily decodes an encoded request one `ScopeLogs` at a time.
+    pub fn stream_scopes(bytes: &'a [u8]) -> ScopeStream<'a, ResourceLogs<'a>> {
+        ScopeStream::new(bytes)
+    }
+}
+
+impl<'a> ExportMetricsServiceRequest<'a> {
+    /// Lazily decodes an encoded request one `ResourceMetrics` at a time.
+    pub fn stream_resources(bytes: &'a [u8]) -> ResourceStream<'a, ResourceMetrics<'a>> {
+        ResourceStream::new(bytes)
+    }
+
+    /// Lazily decodes an encoded request one `ScopeMetrics` at a time.
+    pub fn stream_scopes(bytes: &'a [u8]) -> ScopeStream<'a, ResourceMetrics<'a>> {
+        ScopeStream::new(bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::opentelemetry::common::{InstrumentationScope, Resource};
+    use crate::opentelemetry::trace::{
+        ExportTraceServiceRequest, ResourceSpans, ScopeSpans, Span, Validate,
+    };
+    use quick_protobuf::{MessageWrite, Writer};
+    use std::borrow::Cow;
+
+    fn scope_spans(name: &'static str, spans: usize) -> ScopeSpans<'static> {
+        ScopeSpans {
+            scope: Some(InstrumentationScope {
+                name: Cow::from(name),
+                ..Default::default()
+            }),
+            spans: vec![
+                Span {
+                    name: Cow::from("span"),
+                    ..Default::default()
+                };
+                spans
+            ],
+            schema_url: Cow::from(""),
+            ..Default::default()
+        }
+    }
+
+    fn encode(request: &ExportTraceServiceRequest) -> Vec<u8> {
+        let mut out = vec![];
+        request
+            .write_message(&mut Writer::new(&mut out))
+            .expect("failed to write");
+        out
+    }
+
+    fn request() -> ExportTraceServiceRequest<'static> {
+        ExportTraceServiceRequest {
+            resource_spans: vec![
+                ResourceSpans {
+                    resource: Some(Resource {
+                        dropped_attributes_count: 1,
+                        ..Default::default()
+                    }),
+                    scope_spans: vec![scope_spans("a", 1), scope_spans("b", 2)],
+                    schema_url: Cow::from("https://some_url.com"),
+                    ..Default::default()
+                },
+                ResourceSpans {
+                    resource: None,
+                    scope_spans: vec![scope_spans("c", 3)],
+                    schema_url: Cow::from(""),
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn stream_resources_yields_every_resource() {
+        let request = request();
+        let out = encode(&request);
+
+        let streamed = ExportTraceServiceRequest::stream_resources(&out)
+            .map(|item| {
+                let item = item.expect("Parsing of resource spans failed");
+                item.validate().expect("validation failed");
+                item
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(streamed, request.resource_spans);
+    }
+
+    #[test]
+    fn stream_scopes_keeps_resource_context() {
+        let out = encode(&request());
+
+        let streamed = ExportTraceServiceRequest::stream_scopes(&out)
+            .collect::<Result<Vec<_>, _>>()
+            .expect("Parsing of scope spans failed");
+
+        assert_eq!(streamed.len(), 3);
+        for item in &streamed {
+            assert_eq!(item.scope_spans.len(), 1);
+        }
+        assert_eq!(streamed[0].scope_spans[0], scope_spans("a", 1));
+        assert_eq!(streamed[1].scope_spans[0], scope_spans("b", 2));
+        assert_eq!(streamed[1].resource, streamed[0].resource);
+        assert_eq!(streamed[1].schema_url, "https://some_url.com");
+        assert_eq!(streamed[2].resource, None);
+        assert_eq!(streamed[2].scope_spans[0].spans.len(), 3);
+    }
+
+    #[test]
+    fn stream_stops_after_truncated_input() {
+        let out = encode(&request());
+        let truncated = &out[..out.len() - 3];
+
+        let mut stream = ExportTraceServiceRequest::stream_resources(truncated);
+        assert!(stream.next().expect("first resource").is_ok());
+        assert!(stream.next().expect("second resource").is_err());
+        assert!(stream.next().is_none());
+
+        let results = ExportTraceServiceRequest::stream_scopes(truncated).collect::<Vec<_>>();
+        assert!(results.last().expect("at least one item").is_err());
+        assert_eq!(results.iter().filter(|item| item.is_err()).count(), 1);
+    }
+}
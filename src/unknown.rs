@@ -0,0 +1,267 @@
+use quick_protobuf::{BytesReader, Writer, WriterBackend};
+
+const WIRE_TYPE_VARINT: u32 = 0;
+const WIRE_TYPE_FIXED64: u32 = 1;
+const WIRE_TYPE_LENGTH_DELIMITED: u32 = 2;
+const WIRE_TYPE_FIXED32: u32 = 5;
+
+/// Encoded fields a message did not recognise when it was decoded.
+///
+/// Every message has them, in its `unknown_fields`, but they are only
+/// filled with the `unknown-fields` feature: without it they are skipped
+/// like before and stay empty. They are written back after the known fields
+/// when the message is encoded, so fields from newer OTLP versions travel
+/// with their message through `retain_*`, `split`, `merge` and the like.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct UnknownFields {
+    // Tags included, in the order they were read
+    bytes: Vec<u8>,
+}
+
+impl UnknownFields {
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    /// Number of encoded bytes of the fields, tags included.
+    pub fn len(&self) -> usize {
+        self.bytes.len()
+    }
+
+    /// The encoded fields, tags included.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    pub fn clear(&mut self) {
+        self.bytes.clear();
+    }
+
+    /// Removes the fields with the given number.
+    pub fn remove(&mut self, number: u32) {
+        let bytes = std::mem::take(&mut self.bytes);
+        let mut reader = BytesReader::from_bytes(&bytes);
+        while !reader.is_eof() {
+            let start = bytes.len() - reader.len();
+            // Only ever holds fields that were read successfully
+            let tag = reader.next_tag(&bytes).expect("valid unknown fields");
+            reader
+                .read_unknown(&bytes, tag)
+                .expect("valid unknown fields");
+            if tag >> 3 != number {
+                self.bytes
+                    .extend_from_slice(&bytes[start..bytes.len() - reader.len()]);
+            }
+        }
+    }
+
+    // Called by the generated readers on the fields they do not know, after
+    // reading the tag. The value is checked the same way with and without
+    // the feature.
+    pub(crate) fn read(
+        &mut self,
+        r: &mut BytesReader,
+        bytes: &[u8],
+        tag: u32,
+    ) -> quick_protobuf::Result<()> {
+        if !cfg!(feature = "unknown-fields") {
+            return r.read_unknown(bytes, tag);
+        }
+
+        // Re-encoded, as the reader does not tell where the value starts
+        let mut w = Writer::new(&mut self.bytes);
+        match tag & 7 {
+            WIRE_TYPE_VARINT => {
+                let value = r.read_varint64(bytes)?;
+                w.write_tag(tag)?;
+                w.write_varint(value)
+            }
+            WIRE_TYPE_FIXED64 => {
+                let value = r.read_fixed64(bytes)?;
+                w.write_tag(tag)?;
+                w.write_fixed64(value)
+            }
+            WIRE_TYPE_LENGTH_DELIMITED => {
+                let value = r.read_bytes(bytes)?;
+                w.write_tag(tag)?;
+                w.write_bytes(value)
+            }
+            WIRE_TYPE_FIXED32 => {
+                let value = r.read_fixed32(bytes)?;
+                w.write_tag(tag)?;
+                w.write_fixed32(value)
+            }
+            // Groups and invalid wire types are errors
+            _ => r.read_unknown(bytes, tag),
+        }
+    }
+
+    // Called by the generated writers after the known fields
+    pub(crate) fn write<W: WriterBackend>(&self, w: &mut Writer<W>) -> quick_protobuf::Result<()> {
+        // `Writer` has no way to write raw bytes, this is rarely more than a
+        // few of them
+        self.bytes.iter().try_for_each(|byte| w.write_u8(*byte))
+    }
+}
+
+#[cfg(all(test, feature = "unknown-fields"))]
+mod tests {
+    use std::borrow::Cow;
+
+    use quick_protobuf::Writer;
+
+    use crate::encode::encode_to_vec;
+    use crate::opentelemetry::common::{AnyValue, AnyValueOneOfvalue, KeyValue};
+    use crate::opentelemetry::profiles::{ExportProfilesServiceRequest, Sample};
+    use crate::opentelemetry::trace::{ExportTraceServiceRequest, Span};
+
+    // Appends one field of each wire type with tags no OTLP message uses
+    fn with_unknown_fields(mut message: Vec<u8>, value: u64) -> Vec<u8> {
+        let mut w = Writer::new(&mut message);
+        w.write_with_tag(2000 << 3, |w| w.write_uint64(value))
+            .unwrap();
+        w.write_with_tag(2001 << 3 | 1, |w| w.write_fixed64(7))
+            .unwrap();
+        w.write_with_tag(2002 << 3 | 2, |w| w.write_string("new"))
+            .unwrap();
+        w.write_with_tag(2003 << 3 | 5, |w| w.write_fixed32(9))
+            .unwrap();
+        message
+    }
+
+    fn field(number: u32, message: &[u8]) -> Vec<u8> {
+        let mut out = vec![];
+        Writer::new(&mut out)
+            .write_with_tag(number << 3 | 2, |w| w.write_bytes(message))
+            .unwrap();
+        out
+    }
+
+    fn span(name: &str) -> Vec<u8> {
+        encode_to_vec(&Span {
+            name: Cow::from(name),
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn unknown_fields_survive_roundtrip() {
+        let value = with_unknown_fields(
+            encode_to_vec(&AnyValue {
+                value: AnyValueOneOfvalue::int_value(1),
+                ..Default::default()
+            }),
+            1,
+        );
+        let key_value = with_unknown_fields(
+            [
+                encode_to_vec(&KeyValue {
+                    key: Cow::from("key"),
+                    ..Default::default()
+                }),
+                field(2, &value),
+            ]
+            .concat(),
+            2,
+        );
+        let first = with_unknown_fields(span("first"), 3);
+        let second = [span("second"), field(9, &key_value)].concat();
+        let scope_spans = [field(2, &first), field(2, &second)].concat();
+        let bytes = with_unknown_fields(field(1, &field(2, &scope_spans)), 4);
+
+        let decoded = ExportTraceServiceRequest::try_from(&bytes[..]).unwrap();
+        let spans = &decoded.resource_spans[0].scope_spans[0].spans;
+        assert_eq!(spans[0].name, "first");
+        assert_eq!(
+            spans[0].unknown_fields.as_bytes(),
+            with_unknown_fields(vec![], 3)
+        );
+        assert!(spans[1].unknown_fields.is_empty());
+        let key_value = &spans[1].attributes[0];
+        assert_eq!(
+            key_value.unknown_fields.as_bytes(),
+            with_unknown_fields(vec![], 2)
+        );
+        assert_eq!(
+            key_value.value.as_ref().unwrap().unknown_fields.as_bytes(),
+            with_unknown_fields(vec![], 1)
+        );
+        assert_eq!(decoded.encode_to_vec(), bytes);
+
+        let mut unknown = decoded.unknown_fields.clone();
+        unknown.remove(2001);
+        unknown.remove(2003);
+        let mut expected = vec![];
+        let mut w = Writer::new(&mut expected);
+        w.write_with_tag(2000 << 3, |w| w.write_uint64(4)).unwrap();
+        w.write_with_tag(2002 << 3 | 2, |w| w.write_string("new"))
+            .unwrap();
+        assert_eq!(unknown.as_bytes(), expected);
+    }
+
+    #[test]
+    fn unknown_fields_stay_with_their_span() {
+        // Spans `0` to `5` in two scopes of two resources, each with
+        // unknown fields holding its number
+        let resource = |spans: std::ops::Range<u64>| {
+            let spans: Vec<_> = spans
+                .map(|i| field(2, &with_unknown_fields(span(&i.to_string()), i)))
+                .collect();
+            field(1, &field(2, &spans.concat()))
+        };
+        let bytes = [resource(0..3), resource(3..6)].concat();
+        let mut request = ExportTraceServiceRequest::try_from(&bytes[..]).unwrap();
+
+        // Removing spans and splitting the rest moves them around
+        assert_eq!(request.retain_spans(|span, _, _| span.name != "1"), 1);
+        let requests = request.split(2, usize::MAX);
+        assert_eq!(requests.len(), 3);
+
+        let mut names = vec![];
+        for request in requests {
+            let bytes = request.encode_to_vec();
+            let decoded = ExportTraceServiceRequest::try_from(&bytes[..]).unwrap();
+            assert_eq!(decoded, request);
+            for span in decoded
+                .resource_spans
+                .iter()
+                .flat_map(|r| &r.scope_spans[0].spans)
+            {
+                let i = span.name.parse().unwrap();
+                assert_eq!(
+                    span.unknown_fields.as_bytes(),
+                    with_unknown_fields(vec![], i)
+                );
+                names.push(i);
+            }
+        }
+        assert_eq!(names, [0, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn unknown_fields_of_profiles() {
+        let sample = with_unknown_fields(
+            encode_to_vec(&Sample {
+                value: vec![1],
+                ..Default::default()
+            }),
+            1,
+        );
+        let profile = with_unknown_fields(field(2, &sample), 2);
+        let bytes = field(1, &field(2, &field(2, &profile)));
+
+        let decoded = ExportProfilesServiceRequest::try_from(&bytes[..]).unwrap();
+        let profile = &decoded.resource_profiles[0].scope_profiles[0].profiles[0];
+        assert_eq!(
+            profile.unknown_fields.as_bytes(),
+            with_unknown_fields(vec![], 2)
+        );
+        assert_eq!(profile.sample[0].value, [1]);
+        assert_eq!(
+            profile.sample[0].unknown_fields.as_bytes(),
+            with_unknown_fields(vec![], 1)
+        );
+        assert_eq!(encode_to_vec(&decoded), bytes);
+    }
+}
@@ -1,5 +1,7 @@
+use url::Url;
+
 use crate::opentelemetry::common::{
-    AnyValue, AnyValueOneOfvalue, ArrayValue, InstrumentationScope, KeyValue, Resource,
+    AnyValue, AnyValueOneOfvalue, ArrayValue, EntityRef, InstrumentationScope, KeyValue, Resource,
 };
 
 pub trait CommonValidate {
@@ -12,6 +14,46 @@ impl CommonValidate for Resource<'_> {
             attribute.validate()?;
         }
 
+        for entity_ref in &self.entity_refs {
+            entity_ref.validate()?;
+
+            // Referenced keys MUST exist in the resource attributes
+            let keys = entity_ref
+                .id_keys
+                .iter()
+                .chain(&entity_ref.description_keys);
+            for key in keys {
+                if !self
+                    .attributes
+                    .iter()
+                    .any(|attribute| attribute.key == *key)
+                {
+                    return Err(crate::Error::InvalidEntityRef(format!(
+                        "key `{}` is not a resource attribute",
+                        key
+                    )));
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl CommonValidate for EntityRef<'_> {
+    fn validate(&self) -> Result<(), crate::Error> {
+        if self.type_pb.is_empty() {
+            return Err(crate::Error::InvalidEntityRef("empty type".to_string()));
+        }
+
+        if self.id_keys.is_empty() {
+            return Err(crate::Error::InvalidEntityRef("no id keys".to_string()));
+        }
+
+        if !self.schema_url.is_empty() {
+            Url::parse(self.schema_url.as_ref())?;
+        }
+
         Ok(())
     }
 }
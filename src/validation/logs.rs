@@ -29,7 +29,7 @@ impl LogValidate for ResourceLogs<'_> {
             scope_log.validate()?;
         }
 
-        if self.schema_url != "" {
+        if !self.schema_url.is_empty() {
             Url::parse(self.schema_url.as_ref())?;
         }
 
@@ -48,7 +48,7 @@ impl LogValidate for ScopeLogs<'_> {
             log_record.validate()?;
         }
 
-        if self.schema_url != "" {
+        if !self.schema_url.is_empty() {
             Url::parse(self.schema_url.as_ref())?;
         }
 
@@ -57,7 +57,7 @@ impl MetricValidate for ResourceMetrics<'_> {
             None => Ok(()),
         }?;
 
-        if self.schema_url != "" {
+        if !self.schema_url.is_empty() {
             Url::parse(self.schema_url.as_ref())?;
         }
 
@@ -76,7 +76,7 @@ impl MetricValidate for ScopeMetrics<'_> {
             metric.validate()?;
         }
 
-        if self.schema_url != "" {
+        if !self.schema_url.is_empty() {
             Url::parse(self.schema_url.as_ref())?;
         }
 
@@ -103,6 +103,10 @@ impl MetricValidate for Metric<'_> {
             MetricOneOfdata::None => Ok(()),
         }?;
 
+        for attribute in &self.metadata {
+            attribute.validate()?;
+        }
+
         Ok(())
     }
 }
@@ -1,4 +1,5 @@
 pub(crate) mod common;
 pub(crate) mod logs;
 pub(crate) mod metrics;
+pub(crate) mod profiles;
 pub(crate) mod trace;
@@ -0,0 +1,220 @@
+use url::Url;
+
+use crate::opentelemetry::profiles::{
+    ExportProfilesServiceRequest, Location, Mapping, Profile, ResourceProfiles, Sample,
+    ScopeProfiles,
+};
+
+use crate::validation::common::*;
+
+pub trait ProfileValidate {
+    fn validate(&self) -> Result<(), crate::Error>;
+}
+
+// Index 0 into an empty table is accepted, as it is also what an unset
+// index decodes to
+fn check_index(field: &'static str, index: i32, len: usize) -> Result<(), crate::Error> {
+    match usize::try_from(index) {
+        Ok(i) if i < len || (i == 0 && len == 0) => Ok(()),
+        _ => Err(crate::Error::IndexOutOfBounds {
+            field,
+            index: index.into(),
+            len,
+        }),
+    }
+}
+
+// Optional indices are unset rather than 0 when there is nothing to point at
+fn check_optional_index(
+    field: &'static str,
+    index: Option<i32>,
+    len: usize,
+) -> Result<(), crate::Error> {
+    match index {
+        Some(index) if usize::try_from(index).map_or(true, |i| i >= len) => {
+            Err(crate::Error::IndexOutOfBounds {
+                field,
+                index: index.into(),
+                len,
+            })
+        }
+        _ => Ok(()),
+    }
+}
+
+fn check_indices(field: &'static str, indices: &[i32], len: usize) -> Result<(), crate::Error> {
+    for index in indices {
+        check_index(field, *index, len)?;
+    }
+
+    Ok(())
+}
+
+impl ProfileValidate for ExportProfilesServiceRequest<'_> {
+    fn validate(&self) -> Result<(), crate::Error> {
+        for resource_profile in &self.resource_profiles {
+            resource_profile.validate()?;
+        }
+
+        Ok(())
+    }
+}
+
+impl ProfileValidate for ResourceProfiles<'_> {
+    fn validate(&self) -> Result<(), crate::Error> {
+        match &self.resource {
+            Some(resource) => resource.validate(),
+            None => Ok(()),
+        }?;
+
+        for scope_profile in &self.scope_profiles {
+            scope_profile.validate()?;
+        }
+
+        if !self.schema_url.is_empty() {
+            Url::parse(self.schema_url.as_ref())?;
+        }
+
+        Ok(())
+    }
+}
+
+impl ProfileValidate for ScopeProfiles<'_> {
+    fn validate(&self) -> Result<(), crate::Error> {
+        match &self.scope {
+            Some(scope) => scope.validate(),
+            None => Ok(()),
+        }?;
+
+        for profile in &self.profiles {
+            profile.validate()?;
+        }
+
+        if !self.schema_url.is_empty() {
+            Url::parse(self.schema_url.as_ref())?;
+        }
+
+        Ok(())
+    }
+}
+
+impl ProfileValidate for Profile<'_> {
+    fn validate(&self) -> Result<(), crate::Error> {
+        // An empty table stands for one holding only the empty string
+        if let Some(first) = self.string_table.first() {
+            if !first.is_empty() {
+                return Err(crate::Error::InvalidStringTable);
+            }
+        }
+        let strings = self.string_table.len();
+        let attributes = self.attribute_table.len();
+
+        for value_type in self.sample_type.iter().chain(&self.period_type) {
+            check_index("type_strindex", value_type.type_strindex, strings)?;
+            check_index("unit_strindex", value_type.unit_strindex, strings)?;
+        }
+        check_index(
+            "default_sample_type_index",
+            self.default_sample_type_index,
+            self.sample_type.len(),
+        )?;
+        check_indices("comment_strindices", &self.comment_strindices, strings)?;
+        check_indices("attribute_indices", &self.attribute_indices, attributes)?;
+        check_indices(
+            "location_indices",
+            &self.location_indices,
+            self.location_table.len(),
+        )?;
+
+        for sample in &self.sample {
+            self.validate_sample(sample)?;
+        }
+        for mapping in &self.mapping_table {
+            self.validate_mapping(mapping)?;
+        }
+        for location in &self.location_table {
+            self.validate_location(location)?;
+        }
+        for function in &self.function_table {
+            check_index("name_strindex", function.name_strindex, strings)?;
+            check_index(
+                "system_name_strindex",
+                function.system_name_strindex,
+                strings,
+            )?;
+            check_index("filename_strindex", function.filename_strindex, strings)?;
+        }
+        for unit in &self.attribute_units {
+            check_index(
+                "attribute_key_strindex",
+                unit.attribute_key_strindex,
+                strings,
+            )?;
+            check_index("unit_strindex", unit.unit_strindex, strings)?;
+        }
+        for attribute in &self.attribute_table {
+            attribute.validate()?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Profile<'_> {
+    fn validate_sample(&self, sample: &Sample) -> Result<(), crate::Error> {
+        let locations = self.location_indices.len();
+        check_index(
+            "locations_start_index",
+            sample.locations_start_index,
+            locations + 1,
+        )?;
+        let end = i64::from(sample.locations_start_index) + i64::from(sample.locations_length);
+        if sample.locations_length < 0 || end > locations as i64 {
+            return Err(crate::Error::IndexOutOfBounds {
+                field: "locations_length",
+                index: sample.locations_length.into(),
+                len: locations,
+            });
+        }
+
+        check_indices(
+            "attribute_indices",
+            &sample.attribute_indices,
+            self.attribute_table.len(),
+        )?;
+        check_optional_index("link_index", sample.link_index, self.link_table.len())
+    }
+
+    fn validate_mapping(&self, mapping: &Mapping) -> Result<(), crate::Error> {
+        check_index(
+            "filename_strindex",
+            mapping.filename_strindex,
+            self.string_table.len(),
+        )?;
+        check_indices(
+            "attribute_indices",
+            &mapping.attribute_indices,
+            self.attribute_table.len(),
+        )
+    }
+
+    fn validate_location(&self, location: &Location) -> Result<(), crate::Error> {
+        check_optional_index(
+            "mapping_index",
+            location.mapping_index,
+            self.mapping_table.len(),
+        )?;
+        for line in &location.line {
+            check_index(
+                "function_index",
+                line.function_index,
+                self.function_table.len(),
+            )?;
+        }
+        check_indices(
+            "attribute_indices",
+            &location.attribute_indices,
+            self.attribute_table.len(),
+        )
+    }
+}
@@ -1,7 +1,7 @@
 use url::Url;
 
 use crate::opentelemetry::trace::{
-    ResourceSpans, ScopeSpans, Span, SpanEvent, SpanLink, Status, ExportTraceServiceRequest,
+    ExportTraceServiceRequest, ResourceSpans, ScopeSpans, Span, SpanEvent, SpanLink, Status,
 };
 
 use crate::validation::common::*;
@@ -31,7 +31,7 @@ impl TraceValidate for ResourceSpans<'_> {
             scope_spans.validate()?;
         }
 
-        if self.schema_url != "" {
+        if !self.schema_url.is_empty() {
             Url::parse(self.schema_url.as_ref())?;
         }
 
@@ -45,7 +45,7 @@ impl TraceValidate for ScopeSpans<'_> {
             span.validate()?;
         }
 
-        if self.schema_url != "" {
+        if !self.schema_url.is_empty() {
             Url::parse(self.schema_url.as_ref())?;
         }
 
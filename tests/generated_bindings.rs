@@ -0,0 +1,48 @@
+#[path = "../build/codegen.rs"]
+mod codegen;
+
+use std::path::{Path, PathBuf};
+
+// Run with UPDATE_BINDINGS set to overwrite the checked-in bindings instead
+#[test]
+fn checked_in_bindings_match_protos() {
+    let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let checked_in = manifest_dir.join("src/opentelemetry_types");
+    let out_dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("bindings");
+    codegen::generate(&manifest_dir.join("proto"), &out_dir);
+
+    let update = std::env::var_os("UPDATE_BINDINGS").is_some();
+    if update {
+        std::fs::remove_dir_all(checked_in.join("opentelemetry")).unwrap();
+    }
+
+    // The root mod.rs is hand-written
+    let root = out_dir.join("mod.rs");
+    for path in codegen::generated_files(&out_dir)
+        .into_iter()
+        .filter(|path| path != &root)
+    {
+        let target = checked_in.join(path.strip_prefix(&out_dir).unwrap());
+        let generated = std::fs::read_to_string(&path).unwrap();
+        if update {
+            std::fs::create_dir_all(target.parent().unwrap()).unwrap();
+            std::fs::write(&target, generated).unwrap();
+        } else {
+            let existing = std::fs::read_to_string(&target).unwrap_or_default();
+            assert!(
+                existing == generated,
+                "{} is out of date, run scripts/regenerate-bindings.sh",
+                target.display()
+            );
+        }
+    }
+
+    let generated = codegen::generated_files(&out_dir).len() - 1;
+    let existing = codegen::generated_files(&checked_in.join("opentelemetry")).len();
+    assert_eq!(
+        existing,
+        generated,
+        "stale bindings in {}",
+        checked_in.display()
+    );
+}